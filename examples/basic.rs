@@ -35,7 +35,7 @@ async fn main() -> io::Result<()> {
             }
         }
         // Handle the event in the file explorer.
-        file_explorer.handle(&event).await?;
+        file_explorer.handle_event(&event).await?;
     }
 
     disable_raw_mode()?;
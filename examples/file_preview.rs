@@ -1,18 +1,17 @@
 use std::{
-    borrow::Cow,
-    fs::read_to_string,
     io::{self, stdout},
+    time::Duration,
 };
 
 use crossterm::{
-    event::{read, Event, KeyCode},
+    event::{poll, read, Event, KeyCode},
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
 use ratatui::crossterm;
 use ratatui::{prelude::*, widgets::*};
 
-use ratatui_async_explorer::{File, FileExplorer, Theme};
+use ratatui_async_explorer::{FileExplorer, Preview, PreviewContent, PreviewOptions, Theme};
 
 #[tokio::main]
 async fn main() -> io::Result<()> {
@@ -22,27 +21,26 @@ async fn main() -> io::Result<()> {
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
     let layout = Layout::horizontal([Constraint::Ratio(1, 3), Constraint::Ratio(2, 3)]);
 
-    // Create a new file explorer with the default theme and title.
+    // Create a new file explorer with the default theme and title, previewing the current entry.
     let theme = get_theme();
     let mut file_explorer = FileExplorer::with_theme(theme).await?;
+    file_explorer.set_preview(Some(PreviewOptions::new()));
 
     loop {
-        // Get the content of the current selected file (if it's indeed a file).
-        let file_content = get_file_content(file_explorer.current());
+        // Start loading the preview of the current entry, or take the loaded one.
+        file_explorer.poll_preview();
 
-        let file_content = match file_content {
-            Ok(file_content) => file_content,
-            _ => "Couldn't load file.".into(),
-        };
-
-        // Render the file explorer widget and the file content.
+        // Render the file explorer widget and the preview.
         terminal.draw(|f| {
             let chunks = layout.split(f.area());
+            let content = file_explorer
+                .preview()
+                .map_or(&PreviewContent::Loading, |preview| preview.content());
 
             f.render_widget(&file_explorer.widget(), chunks[0]);
             f.render_widget(Clear, chunks[1]);
             f.render_widget(
-                Paragraph::new(file_content).block(
+                Preview::new(content).block(
                     Block::default()
                         .borders(Borders::ALL)
                         .border_type(BorderType::Double),
@@ -51,7 +49,10 @@ async fn main() -> io::Result<()> {
             );
         })?;
 
-        // Read the next event from the terminal.
+        // Wait a little for the next event, to render the preview once loaded.
+        if !poll(Duration::from_millis(50))? {
+            continue;
+        }
         let event = read()?;
         if let Event::Key(key) = event {
             if key.code == KeyCode::Char('q') {
@@ -59,7 +60,7 @@ async fn main() -> io::Result<()> {
             }
         }
         // Handle the event in the file explorer.
-        file_explorer.handle(&event).await?;
+        file_explorer.handle_event(&event).await?;
     }
 
     disable_raw_mode()?;
@@ -67,17 +68,6 @@ async fn main() -> io::Result<()> {
     Ok(())
 }
 
-fn get_file_content(file: &File) -> io::Result<Cow<'_, str>> {
-    // If the path is a file, read its content.
-    if file.is_file() {
-        read_to_string(file.path()).map(Into::into)
-    } else if file.is_dir() {
-        Ok("".into())
-    } else {
-        Ok("<not a regular file>".into())
-    }
-}
-
 fn get_theme() -> Theme {
     Theme::default()
         .with_block(Block::default().borders(Borders::ALL))
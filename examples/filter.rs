@@ -90,7 +90,7 @@ async fn main() -> io::Result<()> {
         }
 
         if !is_searching {
-            file_explorer.handle(&event).await?;
+            file_explorer.handle_event(&event).await?;
         }
     }
 
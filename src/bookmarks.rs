@@ -0,0 +1,195 @@
+use std::{
+    collections::BTreeMap,
+    io::{Error, ErrorKind, Result},
+    path::{Path, PathBuf},
+};
+
+/// Directories saved under single-character marks, like the marks of vim or ranger.
+///
+/// Bookmarks can be saved to and loaded from a text file holding one `<mark> <path>` line
+/// per bookmark. Empty lines and lines starting with `#` are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use ratatui_async_explorer::Bookmarks;
+///
+/// let mut bookmarks = Bookmarks::new();
+/// bookmarks.insert('p', "/home/user/projects").unwrap();
+///
+/// assert_eq!(bookmarks.get('p'), Some(Path::new("/home/user/projects")));
+/// assert_eq!(bookmarks.to_string(), "p /home/user/projects\n");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bookmarks {
+    marks: BTreeMap<char, PathBuf>,
+}
+
+impl Bookmarks {
+    /// Creates an empty set of bookmarks.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the bookmarks saved in the file at `path`.
+    ///
+    /// A missing file is not an error and gives no bookmarks, so that the first run of an
+    /// application works out of the box.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or a line is not a valid bookmark.
+    pub async fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        match tokio::fs::read_to_string(path).await {
+            Ok(contents) => contents.parse(),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Saves the bookmarks to the file at `path`, creating its parent directories.
+    ///
+    /// The file is replaced atomically, so it is never left half written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub async fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+
+        tokio::fs::write(&temporary, self.to_string()).await?;
+        tokio::fs::rename(&temporary, path).await
+    }
+
+    /// Saves `path` under `mark`, returning the path it was previously bound to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `mark` is a whitespace or `#`, which the file format reserves.
+    pub fn insert<P: Into<PathBuf>>(&mut self, mark: char, path: P) -> Result<Option<PathBuf>> {
+        if mark.is_whitespace() || mark == '#' {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid bookmark mark: {:?}", mark),
+            ));
+        }
+
+        Ok(self.marks.insert(mark, path.into()))
+    }
+
+    /// Removes the bookmark under `mark`, returning its path.
+    pub fn remove(&mut self, mark: char) -> Option<PathBuf> {
+        self.marks.remove(&mark)
+    }
+
+    /// Returns the path saved under `mark`.
+    #[must_use]
+    pub fn get(&self, mark: char) -> Option<&Path> {
+        self.marks.get(&mark).map(PathBuf::as_path)
+    }
+
+    /// Returns the bookmarks sorted by mark, e.g. to render them.
+    pub fn iter(&self) -> impl Iterator<Item = (char, &Path)> {
+        self.marks
+            .iter()
+            .map(|(mark, path)| (*mark, path.as_path()))
+    }
+
+    /// Returns the number of bookmarks.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.marks.len()
+    }
+
+    /// Returns whether there is no bookmark.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.marks.is_empty()
+    }
+}
+
+impl std::fmt::Display for Bookmarks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (mark, path) in &self.marks {
+            writeln!(f, "{} {}", mark, path.display())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Bookmarks {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut bookmarks = Self::new();
+
+        for (number, line) in s.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut chars = line.chars();
+            let (Some(mark), Some(' ')) = (chars.next(), chars.next()) else {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid bookmark on line {}: {}", number + 1, line),
+                ));
+            };
+
+            bookmarks.insert(mark, chars.as_str())?;
+        }
+
+        Ok(bookmarks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let bookmarks: Bookmarks = "# Favourite places\n\nw /srv/www\nd /home/me/My Documents\n"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            bookmarks.iter().collect::<Vec<_>>(),
+            [
+                ('d', Path::new("/home/me/My Documents")),
+                ('w', Path::new("/srv/www"))
+            ]
+        );
+
+        assert!("w".parse::<Bookmarks>().is_err());
+        assert!("ww /srv/www".parse::<Bookmarks>().is_err());
+        assert!(Bookmarks::new().insert(' ', "/").is_err());
+    }
+
+    #[tokio::test]
+    async fn save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config/bookmarks");
+
+        assert!(Bookmarks::load(&path).await.unwrap().is_empty());
+
+        let mut bookmarks = Bookmarks::new();
+        bookmarks.insert('1', "/tmp").unwrap();
+        bookmarks.insert('é', "/home/me").unwrap();
+        bookmarks.save(&path).await.unwrap();
+
+        assert_eq!(Bookmarks::load(&path).await.unwrap(), bookmarks);
+    }
+}
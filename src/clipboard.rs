@@ -0,0 +1,147 @@
+use std::path::PathBuf;
+
+/// Whether the entries of a [`Clipboard`] are copied or moved when pasted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClipboardMode {
+    /// The entries were yanked and will be copied.
+    Copy,
+    /// The entries were cut and will be moved.
+    Cut,
+}
+
+/// The entries captured by [`Input::Yank`](crate::Input::Yank) or
+/// [`Input::Cut`](crate::Input::Cut), waiting to be pasted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clipboard {
+    pub(crate) paths: Vec<PathBuf>,
+    pub(crate) mode: ClipboardMode,
+}
+
+impl Clipboard {
+    /// Returns the paths of the captured entries.
+    #[inline]
+    #[must_use]
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    /// Returns whether the entries will be copied or moved.
+    #[inline]
+    #[must_use]
+    pub const fn mode(&self) -> ClipboardMode {
+        self.mode
+    }
+}
+
+/// What to do when pasting an entry whose name is already taken in the destination.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ConflictPolicy {
+    /// Leave the existing entry alone and do not paste.
+    #[default]
+    Skip,
+    /// Replace the existing entry. It is moved to the [trash](crate::FileExplorer::set_trash)
+    /// when there is one, so that undoing the paste restores it, and is otherwise only removed
+    /// once the pasted entry is in place.
+    Overwrite,
+    /// Paste under the first free name among `name (1).ext`, `name (2).ext`...
+    AutoRename,
+}
+
+/// Returns the `n`-th alternative name for `name`, e.g. `report (2).pdf`.
+///
+/// The counter goes before the last extension of files, and at the end of
+/// directories and of names without extension.
+pub(crate) fn numbered_name(name: &str, is_dir: bool, n: usize) -> String {
+    match name.rsplit_once('.') {
+        // A leading dot marks a hidden file, not an extension
+        Some((stem, extension)) if !is_dir && !stem.is_empty() => {
+            format!("{} ({}).{}", stem, n, extension)
+        }
+        _ => format!("{} ({})", name, n),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{filesystem::InMemoryFileSystem, FileExplorer, FileSystem, Input};
+    use std::sync::Arc;
+
+    #[test]
+    fn numbered_names() {
+        assert_eq!(numbered_name("report.pdf", false, 1), "report (1).pdf");
+        assert_eq!(numbered_name("app.tar.gz", false, 2), "app.tar (2).gz");
+        assert_eq!(numbered_name(".env", false, 1), ".env (1)");
+        assert_eq!(numbered_name("v1.2", true, 1), "v1.2 (1)");
+    }
+
+    async fn explorer(policy: ConflictPolicy) -> FileExplorer<InMemoryFileSystem> {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/src/a.txt", "new")
+            .with_file("/src/b.txt", "b")
+            .with_file("/dst/a.txt", "old");
+
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/src".to_string())
+            .await
+            .unwrap();
+        file_explorer.set_conflict_policy(policy);
+        file_explorer.handle(Input::SelectAll).await.unwrap();
+        file_explorer
+    }
+
+    #[tokio::test]
+    async fn cut_and_skip() {
+        let mut file_explorer = explorer(ConflictPolicy::Skip).await;
+        file_explorer.handle(Input::Cut).await.unwrap();
+        assert_eq!(file_explorer.clipboard().unwrap().paths().len(), 2);
+
+        file_explorer.set_cwd("/dst").await.unwrap();
+        file_explorer.handle(Input::Paste).await.unwrap();
+
+        let fs = file_explorer.filesystem();
+        assert_eq!(fs.contents("/dst/a.txt").unwrap(), b"old");
+        assert!(fs.exists("/src/a.txt").await.unwrap());
+        assert!(!fs.exists("/src/b.txt").await.unwrap());
+        assert!(fs.exists("/dst/b.txt").await.unwrap());
+
+        assert!(file_explorer.clipboard().is_none());
+        assert_eq!(file_explorer.selected_paths().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn yank_and_overwrite() {
+        let mut file_explorer = explorer(ConflictPolicy::Overwrite).await;
+        file_explorer.handle(Input::Yank).await.unwrap();
+
+        file_explorer.set_cwd("/dst").await.unwrap();
+        let results = file_explorer.paste().await.unwrap();
+        assert!(results.iter().all(|(_, result)| result.is_ok()));
+
+        let fs = file_explorer.filesystem();
+        assert_eq!(fs.contents("/dst/a.txt").unwrap(), b"new");
+        assert!(fs.exists("/src/a.txt").await.unwrap());
+        assert_eq!(
+            file_explorer.clipboard().unwrap().mode(),
+            ClipboardMode::Copy
+        );
+    }
+
+    #[tokio::test]
+    async fn paste_into_itself() {
+        let fs = InMemoryFileSystem::new().with_file("/dir/inner/file", "");
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string())
+            .await
+            .unwrap();
+        file_explorer.set_conflict_policy(ConflictPolicy::Overwrite);
+
+        file_explorer.handle(Input::Cut).await.unwrap();
+        file_explorer.set_cwd("/dir/inner").await.unwrap();
+        let err = file_explorer.handle(Input::Paste).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(file_explorer
+            .filesystem()
+            .exists("/dir/inner/file")
+            .await
+            .unwrap());
+    }
+}
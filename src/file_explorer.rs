@@ -1,10 +1,29 @@
-use std::{collections::HashSet, io::Result, path::PathBuf, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    io::{Error, ErrorKind, Result},
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
 
 use ratatui::widgets::WidgetRef;
 
 use crate::{
-    filesystem::{FileSystem, LocalFileSystem},
-    input::Input,
+    bookmarks::Bookmarks,
+    clipboard::{numbered_name, Clipboard, ClipboardMode, ConflictPolicy},
+    filesystem::{FilePermissions, FileSystem, LocalFileSystem, Trash},
+    filter::{FilterLayer, FilterStack},
+    frecency::Frecency,
+    fuzzy::fuzzy_match,
+    git::{GitStatus, GitStatuses},
+    gitignore::{IgnoreCache, IgnoreRules},
+    history::{self, History, HistoryEntry},
+    input::{Input, TextEdit},
+    journal::{Journal, Operation},
+    path_entry::{self, PathEntry},
+    positions::{Position, Positions},
+    preview::{PreviewLoader, PreviewOptions},
+    search::{Found, Search, SearchKind, SearchOptions},
+    sort::{SortBy, SortOrder, SortSettings},
     widget::Renderer,
     Theme,
 };
@@ -16,6 +35,35 @@ use crate::{
 /// derives `Clone`.
 pub type FileFilter = Arc<dyn Fn(&File) -> Option<File> + Send + Sync>;
 
+/// The outcome of a batch operation on the [selected paths](FileExplorer::selected_paths):
+/// every path the operation was applied to, in order, along with its result.
+pub type BatchResults = Vec<(PathBuf, Result<()>)>;
+
+/// Turns the failures of a batch into a single error: the failure itself when there is only
+/// one, or an error listing all of them otherwise.
+fn batch_error(results: BatchResults) -> Result<()> {
+    let mut failures: Vec<(PathBuf, Error)> = results
+        .into_iter()
+        .filter_map(|(path, result)| result.err().map(|e| (path, e)))
+        .collect();
+
+    match failures.len() {
+        0 => Ok(()),
+        1 => Err(failures.remove(0).1),
+        count => {
+            let details: Vec<String> = failures
+                .iter()
+                .map(|(path, e)| format!("{}: {}", path.display(), e))
+                .collect();
+            Err(Error::other(format!(
+                "{} entries failed: {}",
+                count,
+                details.join("; ")
+            )))
+        }
+    }
+}
+
 /// A file explorer that allows browsing and selecting files and directories.
 ///
 /// The `FileExplorer` struct represents a file explorer widget that can be used to navigate
@@ -28,7 +76,7 @@ pub type FileFilter = Arc<dyn Fn(&File) -> Option<File> + Send + Sync>;
 ///
 /// Creating a new `FileExplorer` widget:
 ///
-/// ```no_run
+/// ```
 /// use ratatui_async_explorer::FileExplorer;
 ///
 /// # tokio_test::block_on(async {
@@ -54,7 +102,7 @@ pub type FileFilter = Arc<dyn Fn(&File) -> Option<File> + Send + Sync>;
 ///
 /// Accessing information about the current file selected and or the current working directory:
 ///
-/// ```no_run
+/// ```
 /// use ratatui_async_explorer::FileExplorer;
 ///
 /// # tokio_test::block_on(async {
@@ -73,12 +121,32 @@ pub struct FileExplorer<F: FileSystem = LocalFileSystem> {
     files: Vec<File>,
     filtered_files: Vec<File>,
     show_hidden: bool,
+    show_ignored: bool,
+    ignore_rules: IgnoreCache,
+    git_status: bool,
+    git_prefixes: HashMap<PathBuf, Option<String>>,
     selected: usize,
     theme: Theme<F>,
-    search_filter: Option<String>,
-    filter: Option<FileFilter>,
+    filters: FilterStack,
+    fuzzy_filter: Option<String>,
     scroll_offset: usize,
     selected_paths: HashSet<PathBuf>,
+    selection_anchor: Option<PathBuf>,
+    pending_delete: Option<File>,
+    trash: Option<Trash<F>>,
+    sort: SortSettings,
+    clipboard: Option<Clipboard>,
+    conflict_policy: ConflictPolicy,
+    journal: Journal<F>,
+    history: History,
+    positions: Positions,
+    bookmarks: Bookmarks,
+    bookmarks_file: Option<PathBuf>,
+    frecency: Frecency,
+    path_entry: Option<PathEntry>,
+    search: Option<Search>,
+    preview_options: Option<PreviewOptions>,
+    preview: Option<PreviewLoader>,
 }
 
 impl<F: FileSystem> std::fmt::Debug for FileExplorer<F> {
@@ -88,11 +156,31 @@ impl<F: FileSystem> std::fmt::Debug for FileExplorer<F> {
             .field("files", &self.files)
             .field("filtered_files", &self.filtered_files)
             .field("show_hidden", &self.show_hidden)
+            .field("show_ignored", &self.show_ignored)
+            .field("ignore_rules", &self.ignore_rules)
+            .field("git_status", &self.git_status)
+            .field("git_prefixes", &self.git_prefixes)
             .field("selected", &self.selected)
-            .field("search_filter", &self.search_filter)
-            .field("filter", &self.filter.as_ref().map(|_| "..."))
+            .field("filters", &self.filters)
+            .field("fuzzy_filter", &self.fuzzy_filter)
             .field("scroll_offset", &self.scroll_offset)
             .field("selected_paths", &self.selected_paths)
+            .field("selection_anchor", &self.selection_anchor)
+            .field("pending_delete", &self.pending_delete)
+            .field("trash", &self.trash)
+            .field("sort", &self.sort)
+            .field("clipboard", &self.clipboard)
+            .field("conflict_policy", &self.conflict_policy)
+            .field("journal", &self.journal)
+            .field("history", &self.history)
+            .field("positions", &self.positions)
+            .field("bookmarks", &self.bookmarks)
+            .field("bookmarks_file", &self.bookmarks_file)
+            .field("frecency", &self.frecency)
+            .field("path_entry", &self.path_entry)
+            .field("search", &self.search)
+            .field("preview_options", &self.preview_options)
+            .field("preview", &self.preview)
             .finish()
     }
 }
@@ -115,11 +203,30 @@ impl<F: FileSystem> FileExplorer<F> {
     /// use ratatui_async_explorer::{FileExplorer, LocalFileSystem};
     ///
     /// # async fn example() -> std::io::Result<()> {
-    /// let fs = Arc::new(LocalFileSystem);
+    /// let fs = Arc::new(LocalFileSystem::new());
     /// let file_explorer = FileExplorer::with_fs(fs, "/home/user".to_string()).await?;
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// An [`InMemoryFileSystem`](crate::filesystem::InMemoryFileSystem) is handy to test
+    /// code driving the explorer without touching the disk:
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use ratatui_async_explorer::{filesystem::InMemoryFileSystem, FileExplorer};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let fs = InMemoryFileSystem::new()
+    ///     .with_file("/project/Cargo.toml", "[package]")
+    ///     .with_dir("/project/src");
+    ///
+    /// let file_explorer = FileExplorer::with_fs(Arc::new(fs), "/project".to_string())
+    ///     .await
+    ///     .unwrap();
+    /// assert_eq!(file_explorer.files().len(), 3); // `../`, `src/` and `Cargo.toml`
+    /// # })
+    /// ```
     pub async fn with_fs(filesystem: Arc<F>, initial_path: String) -> Result<Self> {
         let cwd = PathBuf::from(initial_path);
 
@@ -129,15 +236,36 @@ impl<F: FileSystem> FileExplorer<F> {
             files: vec![],
             filtered_files: vec![],
             show_hidden: false,
+            show_ignored: true,
+            ignore_rules: IgnoreCache::default(),
+            git_status: false,
+            git_prefixes: HashMap::new(),
             selected: 0,
             theme: Theme::default(),
-            search_filter: None,
-            filter: None,
+            filters: FilterStack::default(),
+            fuzzy_filter: None,
             scroll_offset: 0,
             selected_paths: HashSet::new(),
+            selection_anchor: None,
+            pending_delete: None,
+            trash: None,
+            sort: SortSettings::default(),
+            clipboard: None,
+            conflict_policy: ConflictPolicy::default(),
+            journal: Journal::default(),
+            history: History::default(),
+            positions: Positions::default(),
+            bookmarks: Bookmarks::new(),
+            bookmarks_file: None,
+            frecency: Frecency::new(),
+            path_entry: None,
+            search: None,
+            preview_options: None,
+            preview: None,
         };
 
         file_explorer.get_and_set_files().await?;
+        file_explorer.frecency.visit(file_explorer.cwd.clone());
 
         Ok(file_explorer)
     }
@@ -204,17 +332,91 @@ impl<F: FileSystem> FileExplorer<F> {
         crate::widget::StatefulRenderer(self)
     }
 
+    /// Handles an event of the terminal backend, like [`handle`](#method.handle) does, except
+    /// that while the "go to path" prompt is open, the printable characters and Backspace edit
+    /// the typed path instead of triggering their key bindings.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if [`handle`](#method.handle) does for the converted input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui::crossterm::event::{Event, KeyCode, KeyEvent};
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string()).await.unwrap();
+    /// let key = |code| Event::Key(KeyEvent::from(code));
+    ///
+    /// file_explorer.handle_event(&key(KeyCode::Char('g'))).await.unwrap();
+    /// for c in "Documents".chars() {
+    ///     file_explorer.handle_event(&key(KeyCode::Char(c))).await.unwrap();
+    /// }
+    /// assert_eq!(file_explorer.path_entry().unwrap().input(), "/Documents");
+    ///
+    /// file_explorer.handle_event(&key(KeyCode::Enter)).await.unwrap();
+    /// assert_eq!(file_explorer.cwd().display().to_string(), "/Documents");
+    /// # })
+    /// ```
+    pub async fn handle_event<E>(&mut self, event: &E) -> Result<()>
+    where
+        E: TextEdit,
+        for<'a> Input: From<&'a E>,
+    {
+        let edited = self
+            .path_entry
+            .as_ref()
+            .and_then(|entry| event.edit(entry.input()));
+
+        match edited {
+            Some(input) => self.handle(Input::PathEntryInput(input)).await,
+            None => self.handle(Input::from(event)).await,
+        }
+    }
+
     /// Handles input from user and updates the state of the file explorer.
     /// The different inputs are interpreted as follows:
     /// - `Up`: Move the selection up.
     /// - `Down`: Move the selection down.
-    /// - `Left`: Move to the parent directory.
-    /// - `Right`: Move to the selected directory.
+    /// - `Left`: Move to the parent directory, restoring the position it was left at or else
+    ///   selecting the directory we came from.
+    /// - `Right`: Move to the selected directory, restoring the position it was left at. While
+    ///   the "go to path" prompt is open, go to the typed path instead, like `Confirm`.
     /// - `Home`: Select the first entry.
     /// - `End`: Select the last entry.
     /// - `PageUp`: Scroll the selection up.
     /// - `PageDown`: Scroll the selection down.
     /// - `ToggleShowHidden`: Toggle between showing hidden files or not.
+    /// - `Delete`: Ask to delete the selected file or directory.
+    /// - `Confirm`: Delete the entry awaiting confirmation, or move it to the trash if one is
+    ///   [enabled](#method.set_trash).
+    /// - `Cancel`: Forget about the entry awaiting confirmation.
+    /// - `Rename`: Rename the selected entry and keep it selected.
+    /// - `CopyTo`: Copy the selected entry into another directory.
+    /// - `MoveTo`: Move the selected entry into another directory.
+    /// - `CycleSort`: Sort by the [next key](crate::SortBy::next), keeping the selection.
+    /// - `ToggleSortOrder`: Reverse the sort order, keeping the selection.
+    /// - `ToggleDirsFirst`: Toggle between listing directories before files or not.
+    /// - `ToggleSelection`: Add the current entry to the [selected paths](#method.selected_paths),
+    ///   or remove it from them.
+    /// - `SelectRange`: Select every visible entry between the last toggled one and the current one.
+    /// - `SelectAll`: Select every visible entry.
+    /// - `InvertSelection`: Toggle the selection of every visible entry.
+    /// - `ClearSelection`: Unselect everything.
+    /// - `Yank`: Put the selected paths, or the current entry if none, in the
+    ///   [clipboard](#method.clipboard) to copy them.
+    /// - `Cut`: Put the selected paths, or the current entry if none, in the clipboard to move them.
+    /// - `Paste`: [Paste](#method.paste) the clipboard into the current working directory.
+    ///   When several entries fail, the error lists all of them.
+    /// - `Back`: [Go back](#method.back) to the previous location.
+    /// - `Forward`: [Go forward](#method.forward) to the location left by going back.
+    /// - `Undo`: [Undo](#method.undo) the last file operation.
+    /// - `Redo`: [Redo](#method.redo) the last undone file operation.
     /// - `None`: Do nothing.
     ///
     /// [`Input`](crate::input::Input) implement [`From<Event>`](https://doc.rust-lang.org/stable/std/convert/trait.From.html)
@@ -224,7 +426,10 @@ impl<F: FileSystem> FileExplorer<F> {
     ///
     /// # Errors
     ///
-    /// Will return `Err` if the new current working directory can not be listed.
+    /// Will return `Err` if the new current working directory can not be listed, or if a
+    /// file operation fails. Any input other than `Confirm` (and `None`) cancels a pending
+    /// deletion. `Rename`, `CopyTo` and `MoveTo` never overwrite an existing
+    /// entry and fail with [`ErrorKind::AlreadyExists`] instead.
     ///
     /// # Examples
     ///
@@ -237,20 +442,22 @@ impl<F: FileSystem> FileExplorer<F> {
     ///     └── resume.pdf
     /// ```
     /// You can handle input like this:
-    /// ```no_run
+    /// ```
     /// use ratatui_async_explorer::{FileExplorer, Input};
     ///
     /// # tokio_test::block_on(async {
-    /// let mut file_explorer = FileExplorer::new().await.unwrap();
-    ///
-    /// /* user select `password.png` */
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// # file_explorer.select_file("passport.png");
     ///
     /// file_explorer.handle(Input::Down).await.unwrap();
     /// assert_eq!(file_explorer.current().name(), "resume.pdf");
     ///
     /// file_explorer.handle(Input::Up).await.unwrap();
     /// file_explorer.handle(Input::Up).await.unwrap();
-    /// assert_eq!(file_explorer.current().name(), "Documents");
+    /// assert_eq!(file_explorer.current().name(), "../");
     ///
     /// file_explorer.handle(Input::Left).await.unwrap();
     /// assert_eq!(file_explorer.cwd().display().to_string(), "/");
@@ -264,6 +471,12 @@ impl<F: FileSystem> FileExplorer<F> {
 
         let input = input.into();
 
+        // Only an explicit confirmation may act on a pending deletion, any other
+        // action abandons it so that a stray key press can never delete anything.
+        if !matches!(input, Input::Delete | Input::Confirm | Input::None) {
+            self.pending_delete = None;
+        }
+
         match input {
             Input::Up => {
                 if !self.filtered_files.is_empty() {
@@ -314,28 +527,29 @@ impl<F: FileSystem> FileExplorer<F> {
                     }
                 }
             }
-            Input::Left => {
-                let parent = self.cwd.parent();
+            Input::Right if self.path_entry.is_some() => self.submit_path_entry().await?,
+            Input::Left if self.search.is_some() => self.exit_search().await?,
+            Input::Right if self.search.is_some() => {
+                let result = self.current_entry().cloned();
+                self.exit_search().await?;
 
-                if let Some(parent) = parent {
-                    // Remember the current directory name to select it after navigating back
-                    let current_dir_name = self
-                        .cwd
-                        .file_name()
-                        .and_then(|n| n.to_str())
-                        .map(|s| format!("{}/", s));
-
-                    self.cwd = parent.to_path_buf();
+                if let Some(result) = result.filter(|result| !result.is_navigation_entry()) {
+                    if let Some(dir) = self.filesystem.parent(&result.path.to_string_lossy()) {
+                        self.set_cwd(dir).await?;
+                        self.select_path(&result.path);
+                    }
+                }
+            }
+            Input::Left => {
+                if let Some(parent) = self.filesystem.parent(&self.cwd.to_string_lossy()) {
+                    self.leave_cwd();
+                    let previous = std::mem::replace(&mut self.cwd, PathBuf::from(parent));
                     self.get_and_set_files().await?;
+                    self.frecency.visit(self.cwd.clone());
 
-                    // Try to select the directory we just came from
-                    if let Some(dir_name) = current_dir_name {
-                        if !self.select_file(&dir_name) {
-                            // If we can't find it (shouldn't happen), default to first item
-                            self.selected = 0;
-                        }
-                    } else {
-                        self.selected = 0;
+                    // Without a position to come back to, select the directory we came from
+                    if !self.restore_position() {
+                        self.select_path(&previous);
                     }
                 }
             }
@@ -343,33 +557,190 @@ impl<F: FileSystem> FileExplorer<F> {
                 // Use the is_dir field from File struct instead of PathBuf::is_dir()
                 // This is important for remote filesystems (SFTP) where PathBuf::is_dir()
                 // would check the local filesystem and always return false
-                if self.files[self.selected].is_dir {
+                let Some(file) = self.current_entry() else {
+                    return Ok(());
+                };
+
+                if file.is_dir || self.is_browsable_archive(file).await {
+                    self.leave_cwd();
                     self.cwd = self.files.swap_remove(self.selected).path;
                     self.get_and_set_files().await?;
-                    self.selected = 0;
+                    self.restore_position();
+                    self.frecency.visit(self.cwd.clone());
                 }
             }
             Input::ToggleShowHidden => self.set_show_hidden(!self.show_hidden).await?,
+            Input::ToggleShowIgnored => self.set_show_ignored(!self.show_ignored).await?,
             Input::Delete => {
-                // Get the currently selected file
-                let current_file = &self.files[self.selected];
-
-                // Skip deletion for directories and parent directory (..)
-                if !current_file.is_dir {
-                    let file_path = current_file.path.to_string_lossy().to_string();
+                // Never offer to delete the parent directory (..)
+                if let Some(file) = self
+                    .current_entry()
+                    .filter(|file| !file.is_navigation_entry())
+                {
+                    self.pending_delete = Some(file.clone());
+                }
+            }
+            Input::Confirm => {
+                if self.path_entry.is_some() {
+                    self.submit_path_entry().await?;
+                } else if let Some(file) = self.pending_delete.take() {
+                    let file_path = file.path.to_string_lossy().to_string();
 
-                    // Attempt to delete the file
-                    self.filesystem.delete(&file_path).await?;
+                    if let Some(trash) = &self.trash {
+                        let item = trash.trash(&file_path).await?;
+                        self.journal.record(vec![Operation::Trash {
+                            trash: trash.clone(),
+                            item,
+                        }]);
+                    } else if file.is_dir {
+                        self.filesystem.delete_dir_all(&file_path).await?;
+                    } else {
+                        self.filesystem.delete(&file_path).await?;
+                    }
+                    self.ignore_rules.invalidate(&file.path);
 
                     // Refresh the file list
                     self.get_and_set_files().await?;
 
                     // Adjust selection: stay on the same index if possible, or move to the last item
-                    if self.selected >= self.files.len() && !self.files.is_empty() {
-                        self.selected = self.files.len() - 1;
+                    self.clamp_selected();
+                }
+            }
+            Input::Cancel => {
+                self.pending_delete = None;
+                self.path_entry = None;
+                self.cancel_search();
+            }
+            Input::StartPathEntry => self.start_path_entry().await,
+            Input::PathEntryInput(input) => self.set_path_entry_input(input).await,
+            Input::CompletePath => self.complete_path_entry().await,
+            Input::CycleSort => self.set_sort_by(self.sort.sort_by.next()),
+            Input::ToggleSortOrder => self.set_sort_order(self.sort.order.reversed()),
+            Input::ToggleDirsFirst => self.set_dirs_first(!self.sort.dirs_first),
+            Input::ToggleSelection => {
+                if let Some(path) = self.operable_current() {
+                    if !self.selected_paths.remove(&path) {
+                        self.selected_paths.insert(path.clone());
+                    }
+                    self.selection_anchor = Some(path);
+                }
+            }
+            Input::SelectRange => {
+                let visible = self.visible_paths();
+
+                if let Some(current) = self.operable_current() {
+                    if let Some(end) = visible.iter().position(|path| *path == current) {
+                        let start = self
+                            .selection_anchor
+                            .as_ref()
+                            .and_then(|anchor| visible.iter().position(|path| path == anchor))
+                            .unwrap_or(end);
+
+                        self.selected_paths
+                            .extend(visible[start.min(end)..=start.max(end)].iter().cloned());
+                        self.selection_anchor = Some(current);
+                    }
+                }
+            }
+            Input::SelectAll => self.selected_paths.extend(self.visible_paths()),
+            Input::InvertSelection => {
+                for path in self.visible_paths() {
+                    if !self.selected_paths.remove(&path) {
+                        self.selected_paths.insert(path);
+                    }
+                }
+            }
+            Input::ClearSelection => {
+                self.selected_paths.clear();
+                self.selection_anchor = None;
+            }
+            Input::Back => {
+                self.back().await?;
+            }
+            Input::Forward => {
+                self.forward().await?;
+            }
+            Input::AddBookmark(mark) => self.add_bookmark(mark).await?,
+            Input::RemoveBookmark(mark) => {
+                self.remove_bookmark(mark).await?;
+            }
+            Input::JumpToBookmark(mark) => {
+                self.jump_to_bookmark(mark).await?;
+            }
+            Input::ToggleFilterLayer(name) => {
+                self.toggle_filter_layer(&name);
+            }
+            Input::Undo => {
+                self.undo().await?;
+            }
+            Input::Redo => {
+                self.redo().await?;
+            }
+            Input::Yank => self.fill_clipboard(ClipboardMode::Copy),
+            Input::Cut => self.fill_clipboard(ClipboardMode::Cut),
+            Input::Paste => {
+                // The other entries have been pasted anyway, report every failure at once
+                let results = self.paste().await?;
+                batch_error(results)?;
+            }
+            Input::Rename(new_name) => {
+                if let Some(from) = self.operable_current() {
+                    Self::validate_name(&new_name)?;
+                    // Search results may live in subdirectories of `cwd`
+                    let to = from.parent().unwrap_or(&self.cwd).join(new_name);
+                    self.ensure_vacant(&to).await?;
+
+                    self.filesystem
+                        .rename(&from.to_string_lossy(), &to.to_string_lossy())
+                        .await?;
+                    self.ignore_rules.invalidate(&from);
+                    self.ignore_rules.invalidate(&to);
+                    self.journal.record(vec![Operation::Rename {
+                        from,
+                        to: to.clone(),
+                    }]);
+
+                    self.get_and_set_files().await?;
+                    if !self.select_path(&to) {
+                        self.clamp_selected();
                     }
                 }
             }
+            Input::CopyTo(dir) => {
+                if let Some(from) = self.operable_current() {
+                    let to = Self::destination(&from, &dir)?;
+                    self.ensure_vacant(&to).await?;
+
+                    self.filesystem
+                        .copy(&from.to_string_lossy(), &to.to_string_lossy())
+                        .await?;
+                    self.ignore_rules.invalidate(&to);
+                    self.journal.record(vec![Operation::Copy {
+                        from: from.clone(),
+                        to,
+                    }]);
+
+                    // The copy may have landed in the current directory
+                    self.get_and_set_files().await?;
+                    self.select_path(&from);
+                }
+            }
+            Input::MoveTo(dir) => {
+                if let Some(from) = self.operable_current() {
+                    let to = Self::destination(&from, &dir)?;
+                    self.ensure_vacant(&to).await?;
+
+                    self.filesystem
+                        .move_to(&from.to_string_lossy(), &to.to_string_lossy())
+                        .await?;
+                    self.ignore_rules.invalidate(&from);
+                    self.ignore_rules.invalidate(&to);
+                    self.journal.record(vec![Operation::Move { from, to }]);
+
+                    self.get_and_set_files().await?;
+                    self.clamp_selected();
+                }
+            }
             Input::None => (),
         }
 
@@ -378,17 +749,26 @@ impl<F: FileSystem> FileExplorer<F> {
 
     /// Sets the current working directory of the file explorer.
     ///
+    /// If `cwd` was visited before, the entry that was selected and the scroll position
+    /// are restored, otherwise the first entry is selected. The ignore rules and the git
+    /// repository of the directories are looked up again, see
+    /// [`set_show_ignored`](#method.set_show_ignored) and
+    /// [`set_git_status`](#method.set_git_status).
+    ///
     /// # Errors
     ///
     /// Will return `Err` if the directory `cwd` can not be listed.
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```
     /// use ratatui_async_explorer::FileExplorer;
     ///
     /// # tokio_test::block_on(async {
-    /// let mut file_explorer = FileExplorer::new().await.unwrap();
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string()).await.unwrap();
     ///
     /// file_explorer.set_cwd("/Documents").await.unwrap();
     /// assert_eq!(file_explorer.cwd().display().to_string(), "/Documents");
@@ -396,604 +776,2744 @@ impl<F: FileSystem> FileExplorer<F> {
     /// ```
     #[inline]
     pub async fn set_cwd<P: Into<PathBuf>>(&mut self, cwd: P) -> Result<()> {
-        self.cwd = cwd.into();
+        let cwd = cwd.into();
+        let changed = cwd != self.cwd;
+        if changed {
+            self.leave_cwd();
+        } else {
+            self.remember_position();
+        }
+
+        self.cwd = cwd;
+        self.ignore_rules.clear();
+        self.git_prefixes.clear();
         self.get_and_set_files().await?;
-        self.selected = 0;
+        self.restore_position();
+        if changed {
+            self.frecency.visit(self.cwd.clone());
+        }
 
         Ok(())
     }
 
-    /// Sets whether hidden files should be shown in the file explorer.
+    /// Goes back to the previous location, restoring the entry that was selected and the
+    /// scroll position, like the back button of a browser. Returns `false` if there is no
+    /// previous location.
+    ///
+    /// Every change of working directory ([`Input::Left`], [`Input::Right`],
+    /// [`set_cwd`](#method.set_cwd)...) is recorded in the history.
     ///
     /// # Errors
     ///
-    /// Will return `Err` if the directory `cwd` can not be listed.
+    /// Will return `Err` if the previous location can not be listed anymore, in which case
+    /// nothing changes.
     ///
     /// # Examples
     ///
-    /// ```no_run
-    /// use ratatui_async_explorer::FileExplorer;
+    /// ```
+    /// use ratatui_async_explorer::{FileExplorer, Input};
     ///
     /// # tokio_test::block_on(async {
-    /// let mut file_explorer = FileExplorer::new().await.unwrap();
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// file_explorer.select_file("resume.pdf");
+    /// file_explorer.set_cwd("/").await.unwrap();
+    ///
+    /// file_explorer.handle(Input::Back).await.unwrap();
+    /// assert_eq!(file_explorer.cwd().display().to_string(), "/Documents");
+    /// assert_eq!(file_explorer.current().name(), "resume.pdf");
     ///
-    /// file_explorer.set_show_hidden(true).await.unwrap();
-    /// assert_eq!(file_explorer.show_hidden(), true);
+    /// file_explorer.handle(Input::Forward).await.unwrap();
+    /// assert_eq!(file_explorer.cwd().display().to_string(), "/");
     /// # })
     /// ```
-    #[inline]
-    pub async fn set_show_hidden(&mut self, show_hidden: bool) -> Result<()> {
-        self.show_hidden = show_hidden;
-        self.get_and_set_files().await?;
-        self.selected = 0;
+    pub async fn back(&mut self) -> Result<bool> {
+        let Some(entry) = self.history.back.pop() else {
+            return Ok(false);
+        };
 
-        Ok(())
+        let left = self.location();
+        if let Err(e) = self.restore_location(&entry).await {
+            self.history.back.push(entry);
+            return Err(e);
+        }
+        history::push(&mut self.history.forward, left);
+
+        Ok(true)
     }
 
-    /// Sets the theme of the file explorer.
+    /// Goes forward to the location left by [`back`](#method.back), restoring the entry that
+    /// was selected and the scroll position. Returns `false` if there is no such location.
     ///
-    /// # Examples
-    ///
-    /// ```no_run
-    /// use ratatui_async_explorer::{FileExplorer, Theme};
+    /// Visiting a new location forgets the forward locations.
     ///
-    /// # tokio_test::block_on(async {
-    /// let mut file_explorer = FileExplorer::new().await.unwrap();
+    /// # Errors
     ///
-    /// file_explorer.set_theme(Theme::default().add_default_title());
-    /// # })
-    /// ```
+    /// Will return `Err` if the next location can not be listed anymore, in which case
+    /// nothing changes.
+    pub async fn forward(&mut self) -> Result<bool> {
+        let Some(entry) = self.history.forward.pop() else {
+            return Ok(false);
+        };
+
+        let left = self.location();
+        if let Err(e) = self.restore_location(&entry).await {
+            self.history.forward.push(entry);
+            return Err(e);
+        }
+        history::push(&mut self.history.back, left);
+
+        Ok(true)
+    }
+
+    /// Returns the locations [`back`](#method.back) goes to, the most recent one last.
     #[inline]
-    pub fn set_theme(&mut self, theme: Theme<F>) {
-        self.theme = theme;
+    #[must_use]
+    pub fn back_history(&self) -> &[HistoryEntry] {
+        &self.history.back
     }
 
-    /// Sets the search filter to filter files and directories by name.
-    ///
-    /// When a search filter is set, only files and directories whose names contain
-    /// the filter string (case-insensitive) will be displayed. Set to `None` to clear
-    /// the filter and show all files.
-    ///
-    /// # Examples
-    ///
-    /// ```no_run
-    /// use ratatui_async_explorer::FileExplorer;
+    /// Returns the locations [`forward`](#method.forward) goes to, the next one last.
+    #[inline]
+    #[must_use]
+    pub fn forward_history(&self) -> &[HistoryEntry] {
+        &self.history.forward
+    }
+
+    /// Loads the bookmarks saved in `path`, replacing the current ones, and saves every
+    /// later change of the bookmarks back to it. A missing file gives no bookmarks.
     ///
-    /// # tokio_test::block_on(async {
-    /// let mut file_explorer = FileExplorer::new().await.unwrap();
+    /// See [`Bookmarks`] for the format of the file.
     ///
-    /// // Filter to show only files containing "test"
-    /// file_explorer.set_search_filter(Some("test".to_string()));
+    /// # Errors
     ///
-    /// // Clear the filter
-    /// file_explorer.set_search_filter(None);
-    /// # })
-    /// ```
-    #[inline]
-    pub fn set_search_filter(&mut self, filter: Option<String>) {
-        match filter {
-            Some(ref query) => {
-                let q = query.to_lowercase();
-                self.filter = Some(Arc::new(move |file: &File| {
-                    if file.name().to_lowercase().contains(&q) {
-                        Some(file.clone())
-                    } else {
-                        None
-                    }
-                }));
-                self.search_filter = Some(query.clone());
-            }
-            None => {
-                self.filter = None;
-                self.search_filter = None;
-            }
-        }
-        self.filtered_files = self.compute_filtered_files();
+    /// Will return `Err` if the file cannot be read or is not valid, in which case
+    /// nothing changes.
+    pub async fn set_bookmarks_file<P: Into<PathBuf>>(&mut self, path: P) -> Result<()> {
+        let path = path.into();
+        self.bookmarks = Bookmarks::load(&path).await?;
+        self.bookmarks_file = Some(path);
+
+        Ok(())
     }
 
-    /// Sets a custom closure-based filter with filter_map semantics.
+    /// Bookmarks the current working directory under `mark`, replacing the directory
+    /// previously bookmarked under it.
     ///
-    /// The closure receives a reference to a [`File`] and returns `Some(File)` to include it
-    /// (possibly transformed), or `None` to exclude it.
+    /// # Errors
     ///
-    /// Setting a filter clears any existing search filter set via [`set_search_filter`](#method.set_search_filter).
+    /// Will return `Err` if `mark` is a whitespace or `#`, or the
+    /// [bookmarks file](#method.set_bookmarks_file) cannot be written.
     ///
     /// # Examples
     ///
-    /// ```no_run
-    /// use ratatui_async_explorer::FileExplorer;
+    /// ```
+    /// use ratatui_async_explorer::{FileExplorer, Input};
     ///
     /// # tokio_test::block_on(async {
-    /// let mut file_explorer = FileExplorer::new().await.unwrap();
-    ///
-    /// // Filter to show only directories
-    /// file_explorer.set_filter(Some(|file: &ratatui_async_explorer::File| {
-    ///     if file.is_dir() {
-    ///         Some(file.clone())
-    ///     } else {
-    ///         None
-    ///     }
-    /// }));
-    ///
-    /// // Clear the filter
-    /// file_explorer.clear_filter();
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// file_explorer.handle(Input::AddBookmark('d')).await.unwrap();
+    /// file_explorer.set_cwd("/").await.unwrap();
+    ///
+    /// file_explorer.handle(Input::JumpToBookmark('d')).await.unwrap();
+    /// assert_eq!(file_explorer.cwd().display().to_string(), "/Documents");
     /// # })
     /// ```
-    pub fn set_filter<P>(&mut self, filter: Option<P>)
-    where
-        P: Fn(&File) -> Option<File> + Send + Sync + 'static,
-    {
-        self.filter = filter.map(|f| Arc::new(f) as FileFilter);
-        self.search_filter = None;
-        self.filtered_files = self.compute_filtered_files();
-    }
+    pub async fn add_bookmark(&mut self, mark: char) -> Result<()> {
+        let previous = self.bookmarks.insert(mark, self.cwd.clone())?;
 
-    /// Clears both the closure-based filter and the search filter, showing all files.
-    pub fn clear_filter(&mut self) {
-        self.filter = None;
-        self.search_filter = None;
-        self.filtered_files = self.compute_filtered_files();
+        if let Err(e) = self.save_bookmarks().await {
+            match previous {
+                Some(path) => self.bookmarks.insert(mark, path)?,
+                None => self.bookmarks.remove(mark),
+            };
+            return Err(e);
+        }
+
+        Ok(())
     }
 
-    /// Returns the current search filter, if any.
+    /// Removes the bookmark under `mark`, returning the directory it pointed to.
     ///
-    /// # Examples
+    /// # Errors
     ///
-    /// ```no_run
-    /// use ratatui_async_explorer::FileExplorer;
+    /// Will return `Err` if the [bookmarks file](#method.set_bookmarks_file) cannot be
+    /// written, in which case the bookmark is kept.
+    pub async fn remove_bookmark(&mut self, mark: char) -> Result<Option<PathBuf>> {
+        let Some(path) = self.bookmarks.remove(mark) else {
+            return Ok(None);
+        };
+
+        if let Err(e) = self.save_bookmarks().await {
+            self.bookmarks.insert(mark, path)?;
+            return Err(e);
+        }
+
+        Ok(Some(path))
+    }
+
+    /// Changes the current working directory to the one bookmarked under `mark`. Returns
+    /// `false` if there is no such bookmark.
     ///
-    /// # tokio_test::block_on(async {
-    /// let mut file_explorer = FileExplorer::new().await.unwrap();
-    /// assert_eq!(file_explorer.search_filter(), None);
+    /// # Errors
     ///
-    /// file_explorer.set_search_filter(Some("test".to_string()));
-    /// assert_eq!(file_explorer.search_filter(), Some("test"));
-    /// # })
-    /// ```
+    /// Will return `Err` if the bookmarked directory cannot be listed.
+    pub async fn jump_to_bookmark(&mut self, mark: char) -> Result<bool> {
+        let Some(path) = self.bookmarks.get(mark).map(Path::to_path_buf) else {
+            return Ok(false);
+        };
+
+        self.set_cwd(path).await?;
+
+        Ok(true)
+    }
+
+    /// Returns the bookmarks, e.g. to render them.
     #[inline]
     #[must_use]
-    pub fn search_filter(&self) -> Option<&str> {
-        self.search_filter.as_deref()
+    pub fn bookmarks(&self) -> &Bookmarks {
+        &self.bookmarks
     }
 
-    //
+    /// Changes the current working directory to the most frecent visited directory matching
+    /// `query`, other than the current one. Returns `false` if there is no such directory.
+    ///
+    /// See [`Frecency::matches`] for how `query` is matched. Directories which do not exist
+    /// anymore are forgotten along the way.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the matching directory cannot be listed.
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```
     /// use ratatui_async_explorer::FileExplorer;
     ///
     /// # tokio_test::block_on(async {
-    /// let file_explorer = FileExplorer::new().await.unwrap();
-    /// let filesystem = file_explorer.filesystem();
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// file_explorer.set_cwd("/.git").await.unwrap();
+    ///
+    /// assert!(file_explorer.jump("doc").await.unwrap());
+    /// assert_eq!(file_explorer.cwd().display().to_string(), "/Documents");
     /// # })
     /// ```
-    pub fn filesystem(&self) -> &F {
-        &self.filesystem
-    }
+    pub async fn jump(&mut self, query: &str) -> Result<bool> {
+        let candidates: Vec<PathBuf> = self
+            .frecency
+            .matches(query)
+            .into_iter()
+            .filter(|path| *path != self.cwd)
+            .map(Path::to_path_buf)
+            .collect();
 
-    /// Returns the cached filtered files with their original indices.
+        for path in candidates {
+            if self
+                .filesystem
+                .is_dir(&path.to_string_lossy())
+                .await
+                .unwrap_or(false)
+            {
+                self.set_cwd(path).await?;
+                return Ok(true);
+            }
+
+            self.frecency.remove(&path);
+        }
+
+        Ok(false)
+    }
+
+    /// Opens the "go to path" prompt, filled with the current working directory.
+    ///
+    /// While it is open, the typed path is set with
+    /// [`set_path_entry_input`](#method.set_path_entry_input), completed with
+    /// [`complete_path_entry`](#method.complete_path_entry) and gone to with
+    /// [`submit_path_entry`](#method.submit_path_entry), or the matching [`Input`] variants.
     ///
     /// # Examples
     ///
-    /// ```no_run
-    /// use ratatui_async_explorer::FileExplorer;
+    /// ```
+    /// use ratatui_async_explorer::{FileExplorer, Input};
     ///
-    /// # async fn example() -> std::io::Result<()> {
-    /// let mut file_explorer = FileExplorer::new().await?;
-    /// file_explorer.set_search_filter(Some("test".to_string()));
-    /// let filtered_files = file_explorer.filtered_files();
-    /// println!("Found {} filtered files", filtered_files.len());
-    /// # Ok(())
-    /// # }
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string()).await.unwrap();
+    /// file_explorer.handle(Input::StartPathEntry).await.unwrap();
+    /// assert_eq!(file_explorer.path_entry().unwrap().input(), "/");
+    ///
+    /// file_explorer.handle(Input::PathEntryInput("/Documents/r".to_string())).await.unwrap();
+    /// file_explorer.handle(Input::CompletePath).await.unwrap();
+    /// assert_eq!(file_explorer.path_entry().unwrap().input(), "/Documents/resume.pdf");
+    ///
+    /// file_explorer.handle(Input::Confirm).await.unwrap();
+    /// assert_eq!(file_explorer.cwd().display().to_string(), "/Documents");
+    /// assert_eq!(file_explorer.current().name(), "resume.pdf");
+    /// # })
     /// ```
+    pub async fn start_path_entry(&mut self) {
+        let mut input = self.cwd.to_string_lossy().to_string();
+        if !input.ends_with('/') {
+            input.push('/');
+        }
+
+        self.set_path_entry_input(input).await;
+    }
+
+    /// Sets the path typed in the "go to path" prompt, opening it if needed, and reads its
+    /// completions.
+    ///
+    /// The path may be relative to the current working directory, start with `~` and
+    /// contain environment variables like `$HOME` or `${HOME}`.
+    pub async fn set_path_entry_input<S: Into<String>>(&mut self, input: S) {
+        self.path_entry = Some(PathEntry {
+            input: input.into(),
+            ..PathEntry::default()
+        });
+
+        self.refresh_path_completions().await;
+    }
+
+    /// Completes the path typed in the "go to path" prompt, like the Tab key of a shell. Does
+    /// nothing if the prompt is not open.
+    pub async fn complete_path_entry(&mut self) {
+        if let Some(entry) = &mut self.path_entry {
+            if entry.complete() {
+                self.refresh_path_completions().await;
+            }
+        }
+    }
+
+    /// Goes to the path typed in the "go to path" prompt and closes it: to the directory
+    /// itself, or to the parent directory of a file with the file selected. Does nothing if
+    /// the prompt is not open.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the path does not exist or cannot be listed, in which case the
+    /// prompt stays open.
+    pub async fn submit_path_entry(&mut self) -> Result<()> {
+        let Some(entry) = &self.path_entry else {
+            return Ok(());
+        };
+
+        let path = path_entry::resolve(&entry.input, &self.cwd);
+        let path_str = path.to_string_lossy().to_string();
+
+        if self.filesystem.is_dir(&path_str).await.unwrap_or(false) {
+            self.set_cwd(path).await?;
+        } else if self.filesystem.exists(&path_str).await? {
+            let parent = path.parent().unwrap_or(&path).to_path_buf();
+            self.set_cwd(parent).await?;
+            self.select_path(&path);
+        } else {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("No such file or directory: {}", path_str),
+            ));
+        }
+
+        self.path_entry = None;
+
+        Ok(())
+    }
+
+    /// Closes the "go to path" prompt without going anywhere.
+    #[inline]
+    pub fn cancel_path_entry(&mut self) {
+        self.path_entry = None;
+    }
+
+    /// Returns the "go to path" prompt if it is open, e.g. to render it.
     #[inline]
     #[must_use]
-    pub fn filtered_files(&self) -> &[File] {
-        &self.filtered_files
+    pub fn path_entry(&self) -> Option<&PathEntry> {
+        self.path_entry.as_ref()
     }
 
-    /// Sets the selected file or directory index in the filtered view.
-    /// When no search filter is active, this works the same as before.
-    /// When a search filter is active, this accepts an index within the filtered list
-    /// and converts it to the corresponding original index.
+    /// Starts searching the subtree of the current working directory for the entries whose
+    /// names match `query`, and shows the results instead of the listing.
     ///
-    /// The file explorer add the parent directory at the beginning of the
-    /// [`Vec`](https://doc.rust-lang.org/stable/std/vec/struct.Vec.html) of files, so setting the selected index to 0 will select the parent directory
-    /// (if the current working directory not the root directory).
+    /// By default, the names must contain `query` ignoring the case, see [`SearchOptions`]
+    /// to match a regular expression instead.
     ///
-    /// # Panics
+    /// The directories are walked in the background through the [`FileSystem`] trait, and
+    /// the matches found so far are added to the results by
+    /// [`poll_search`](#method.poll_search). The results are headed by a `./` entry, and
+    /// named after their path relative to the current working directory. Symbolic links to
+    /// directories are not followed, and directories which cannot be read are skipped.
     ///
-    /// Panics if `selected` is greater or equal to the number of files in the current view
-    /// (filtered files if a filter is active, or all files if no filter).
+    /// [`Input::Right`] on a result goes to its directory with it selected, while
+    /// [`Input::Left`] or [`Input::Right`] on `./` go back to the listing. Starting another
+    /// search cancels the previous one.
+    ///
+    /// Must be called within a Tokio runtime.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `query` is not a valid regular expression, while
+    /// [`SearchOptions::with_regex`] is set.
     ///
     /// # Examples
     ///
-    /// Suppose you have this tree file, with `passport.png` selected inside `file_explorer`:
-    /// ```plaintext
-    /// /
-    /// ├── .git
-    /// └── Documents
-    ///     ├── passport.png  <- selected (index 2)
-    ///     └── resume.pdf
     /// ```
-    /// You can set the selected index like this:
-    /// ```no_run
-    /// use ratatui_async_explorer::FileExplorer;
+    /// use ratatui_async_explorer::{FileExplorer, Input, SearchOptions};
     ///
     /// # tokio_test::block_on(async {
-    /// let mut file_explorer = FileExplorer::new().await.unwrap();
-    ///
-    /// /* user select `password.png` */
-    ///
-    /// // Because the file explorer add the parent directory at the beginning
-    /// // of the [`Vec`](https://doc.rust-lang.org/stable/std/vec/struct.Vec.html) of files, index 0 is indeed the parent directory.
-    /// file_explorer.set_selected_idx(0);
-    /// assert_eq!(file_explorer.current().path().display().to_string(), "/");
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string()).await.unwrap();
+    /// file_explorer
+    ///     .start_search("resume", SearchOptions::new().with_max_depth(5))
+    ///     .unwrap();
+    ///
+    /// // Typically polled on every tick of the event loop
+    /// while !file_explorer.search().unwrap().is_finished() {
+    ///     tokio::task::yield_now().await;
+    /// }
+    /// file_explorer.poll_search();
+    /// assert_eq!(file_explorer.files()[1].name(), "Documents/resume.pdf");
     ///
-    /// file_explorer.set_selected_idx(1);
-    /// assert_eq!(file_explorer.current().path().display().to_string(), "/Documents");
+    /// file_explorer.handle(Input::Down).await.unwrap();
+    /// file_explorer.handle(Input::Right).await.unwrap();
+    /// assert_eq!(file_explorer.cwd().display().to_string(), "/Documents");
+    /// assert_eq!(file_explorer.current().name(), "resume.pdf");
     /// # })
     /// ```
-    ///
-    /// Note: Attempting to set an index >= the number of files will cause a panic.
-    #[inline]
-    pub fn set_selected_idx(&mut self, selected: usize) {
-        assert!(selected < self.filtered_files.len());
-
-        if let Some(file) = self.filtered_files.get(selected) {
-            self.selected = file.idx();
-        }
+    pub fn start_search<S: Into<String>>(&mut self, query: S, options: SearchOptions) -> Result<()>
+    where
+        F: 'static,
+    {
+        self.spawn_search(query.into(), SearchKind::Name, options)
     }
 
-    /// Returns the current file or directory selected.
+    /// Starts searching the files in the subtree of the current working directory for the
+    /// lines matching `pattern`, like `grep -r`, and shows the matched lines instead of the
+    /// listing.
+    ///
+    /// The files are read chunk by chunk through [`FileSystem::read_chunk`], up to
+    /// [`SearchOptions::max_file_size`] bytes each, and binary files are skipped. By default,
+    /// the lines must contain `pattern` ignoring the case, see [`SearchOptions`] to match a
+    /// regular expression instead.
+    ///
+    /// Every matched line is shown as a `path:line: text` entry, and available as a
+    /// [`ContentMatch`](crate::ContentMatch) from [`Search::content_matches`]. Apart from
+    /// that, the results behave like those of [`start_search`](#method.start_search):
+    /// [`Input::Right`] on a line goes to the directory of its file with the file selected.
+    ///
+    /// Must be called within a Tokio runtime.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `pattern` is not a valid regular expression, while
+    /// [`SearchOptions::with_regex`] is set.
     ///
     /// # Examples
     ///
-    /// Suppose you have this tree file, with `passport.png` selected inside `file_explorer`:
-    /// ```plaintext
-    /// /
-    /// ├── .git
-    /// └── Documents
-    ///     ├── passport.png  <- selected
-    ///     └── resume.pdf
     /// ```
-    /// You can get the current file like this:
-    /// ```no_run
-    /// use ratatui_async_explorer::FileExplorer;
+    /// use ratatui_async_explorer::{FileExplorer, Input, SearchOptions};
     ///
     /// # tokio_test::block_on(async {
-    /// let file_explorer = FileExplorer::new().await.unwrap();
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// let fs = InMemoryFileSystem::new()
+    ///     .with_file("/app/config/default.toml", "[server]\nport = 8080\n")
+    ///     .with_file("/app/README.md", "Set `port` to change the port.\n");
+    /// let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/app".to_string()).await.unwrap();
+    ///
+    /// let options = SearchOptions::new().with_regex(true);
+    /// file_explorer.start_content_search(r"^port\s*=", options).unwrap();
+    /// while !file_explorer.search().unwrap().is_finished() {
+    ///     tokio::task::yield_now().await;
+    /// }
+    /// file_explorer.poll_search();
     ///
-    /// /* user select `password.png` */
+    /// let found = &file_explorer.search().unwrap().content_matches()[0];
+    /// assert_eq!(found.file().name(), "config/default.toml");
+    /// assert_eq!(found.line_number(), 2);
+    /// assert_eq!(file_explorer.files()[1].name(), "config/default.toml:2: port = 8080");
     ///
-    /// let file = file_explorer.current();
-    /// assert_eq!(file.name(), "passport.png");
+    /// file_explorer.handle(Input::Down).await.unwrap();
+    /// file_explorer.handle(Input::Right).await.unwrap();
+    /// assert_eq!(file_explorer.cwd().display().to_string(), "/app/config");
+    /// assert_eq!(file_explorer.current().name(), "default.toml");
     /// # })
     /// ```
+    pub fn start_content_search<S: Into<String>>(
+        &mut self,
+        pattern: S,
+        options: SearchOptions,
+    ) -> Result<()>
+    where
+        F: 'static,
+    {
+        self.spawn_search(pattern.into(), SearchKind::Content, options)
+    }
+
+    /// Start a search in the background, and show its results instead of the listing.
+    fn spawn_search(
+        &mut self,
+        query: String,
+        kind: SearchKind,
+        options: SearchOptions,
+    ) -> Result<()>
+    where
+        F: 'static,
+    {
+        let search = Search::spawn(
+            self.filesystem.clone(),
+            self.cwd.clone(),
+            query,
+            kind,
+            options,
+        )?;
+
+        match self.search.replace(search) {
+            Some(previous) => previous.cancel(),
+            None => self.remember_position(),
+        }
+
+        self.files = vec![File {
+            idx: 0,
+            name: "./".to_owned(),
+            path: self.cwd.clone(),
+            is_dir: true,
+            is_file: false,
+            is_hidden: false,
+            size: None,
+            modified: None,
+            permissions: None,
+            symlink_target: None,
+            match_positions: Vec::new(),
+            git_status: None,
+        }];
+        self.filtered_files = self.compute_filtered_files();
+        self.selected = 0;
+        self.scroll_offset = 0;
+
+        Ok(())
+    }
+
+    /// Adds the matches found by the running search since the last call to the results.
+    /// Returns `true` if there are new results, e.g. to render them.
+    pub fn poll_search(&mut self) -> bool {
+        let Some(search) = &mut self.search else {
+            return false;
+        };
+
+        let found = search.take_found();
+        if found.is_empty() {
+            return false;
+        }
+
+        for Found { entry, line } in found {
+            let mut file = File::from_entry(entry);
+            file.idx = self.files.len();
+
+            if let Some((line_number, line)) = line {
+                let name = format!("{}:{}: {}", file.name, line_number, line);
+                search.push_content_match(file.clone(), line_number, line);
+                file.name = name;
+            }
+
+            self.files.push(file);
+        }
+        self.filtered_files = self.compute_filtered_files();
+
+        true
+    }
+
+    /// Stops walking the directories of the running search, keeping the results found so far.
+    pub fn cancel_search(&mut self) {
+        if let Some(search) = &self.search {
+            search.cancel();
+        }
+    }
+
+    /// Leaves the results of the search, cancelling it if it is still running, and lists the
+    /// current working directory again.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the directory `cwd` can not be listed.
+    pub async fn exit_search(&mut self) -> Result<()> {
+        if self.search.is_some() {
+            self.get_and_set_files().await?;
+            self.restore_position();
+        }
+
+        Ok(())
+    }
+
+    /// Returns the search whose results are shown, if any.
     #[inline]
     #[must_use]
-    pub fn current(&self) -> &File {
-        &self.files[self.selected]
+    pub fn search(&self) -> Option<&Search> {
+        self.search.as_ref()
     }
 
-    /// Select a file by name in the current directory.
+    /// Enables the preview of the current entry with the given options, or disables it with
+    /// `None`, see [`poll_preview`](#method.poll_preview).
+    pub fn set_preview(&mut self, options: Option<PreviewOptions>) {
+        self.preview_options = options;
+        if let Some(preview) = self.preview.take() {
+            preview.cancel();
+        }
+    }
+
+    /// Keeps the preview in sync with the current entry. Returns `true` if the preview
+    /// changed, e.g. to render it.
     ///
-    /// Returns true if the file was found and selected, false otherwise.
+    /// When the selection has moved since the last call, the load of the previous entry is
+    /// cancelled and the current one starts loading in the background through the
+    /// [`FileSystem`]: the first bytes of a file, up to [`PreviewOptions::max_bytes`], or the
+    /// entries of a directory. Otherwise, the loaded [`PreviewContent`](crate::PreviewContent)
+    /// is taken once ready. The preview is reloaded whenever the directory is listed again.
     ///
-    /// # Examples
+    /// Does nothing unless the preview is [enabled](#method.set_preview). Must be called
+    /// within a Tokio runtime.
     ///
-    /// ```no_run
-    /// use ratatui_async_explorer::FileExplorer;
+    /// # Examples
     ///
-    /// # async fn example() -> std::io::Result<()> {
-    /// let mut file_explorer = FileExplorer::new().await?;
+    /// ```
+    /// use ratatui_async_explorer::{FileExplorer, PreviewContent, PreviewOptions};
     ///
-    /// // Select a specific file
-    /// if file_explorer.select_file("myfile.txt") {
-    ///     println!("Selected myfile.txt");
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string()).await.unwrap();
+    /// file_explorer.set_preview(Some(PreviewOptions::new()));
+    /// file_explorer.select_file("Documents/");
+    ///
+    /// // Typically polled on every tick of the event loop
+    /// file_explorer.poll_preview();
+    /// while file_explorer.preview().unwrap().is_loading() {
+    ///     tokio::task::yield_now().await;
+    ///     file_explorer.poll_preview();
     /// }
-    /// # Ok(())
-    /// # }
+    ///
+    /// let PreviewContent::Directory { entries, .. } = file_explorer.preview().unwrap().content()
+    /// else {
+    ///     panic!("Documents/ is a directory");
+    /// };
+    /// assert_eq!(entries[0].name(), "passport.png");
+    /// # })
     /// ```
-    pub fn select_file(&mut self, filename: &str) -> bool {
-        if let Some(index) = self.files.iter().position(|f| f.name == filename) {
-            self.selected = index;
-            true
-        } else {
-            false
+    pub fn poll_preview(&mut self) -> bool
+    where
+        F: 'static,
+    {
+        let Some(options) = self.preview_options else {
+            return false;
+        };
+        let Some(current) = self.files.get(self.selected) else {
+            return false;
+        };
+
+        if let Some(preview) = &mut self.preview {
+            if preview.path() == current.path() {
+                return preview.poll();
+            }
+            preview.cancel();
         }
+
+        self.preview = Some(PreviewLoader::spawn(
+            self.filesystem.clone(),
+            current,
+            self.show_hidden,
+            self.sort,
+            options,
+        ));
+
+        true
     }
 
-    /// Returns the current working directory of the file explorer.
+    /// Returns the preview of the current entry, if it is enabled and has been polled.
+    #[inline]
+    #[must_use]
+    pub const fn preview(&self) -> Option<&PreviewLoader> {
+        self.preview.as_ref()
+    }
+
+    /// Returns the frecency database of the visited directories, e.g. to
+    /// [save](Frecency::save) it when the application exits.
+    #[inline]
+    #[must_use]
+    pub fn frecency(&self) -> &Frecency {
+        &self.frecency
+    }
+
+    /// Replaces the frecency database of the visited directories, e.g. with one
+    /// [loaded](Frecency::load) when the application starts.
+    #[inline]
+    pub fn set_frecency(&mut self, frecency: Frecency) {
+        self.frecency = frecency;
+    }
+
+    /// Sets the trash used when a deletion is confirmed.
+    ///
+    /// While a trash is set, deleted entries are moved into it instead of being removed
+    /// permanently. Set to `None` to delete permanently again. The trash goes through a
+    /// [`FileSystem`] of the same type as the explorer, usually the same one, see
+    /// [`Trash::with_fs`].
     ///
     /// # Examples
     ///
-    /// Suppose you have this tree file, with `passport.png` selected inside `file_explorer`:
-    /// ```plaintext
-    /// /
-    /// ├── .git
-    /// └── Documents
-    ///     ├── passport.png  <- selected
-    ///     └── resume.pdf
     /// ```
-    /// You can get the current working directory like this:
-    /// ```no_run
-    /// use ratatui_async_explorer::FileExplorer;
+    /// use ratatui_async_explorer::{filesystem::Trash, FileExplorer, Input};
     ///
     /// # tokio_test::block_on(async {
-    /// let file_explorer = FileExplorer::new().await.unwrap();
+    /// use std::sync::Arc;
+    /// use ratatui_async_explorer::filesystem::InMemoryFileSystem;
     ///
-    /// /* user select `password.png` */
+    /// let fs = Arc::new(InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf"));
+    /// let mut file_explorer = FileExplorer::with_fs(Arc::clone(&fs), "/Documents".to_string()).await.unwrap();
     ///
-    /// let cwd = file_explorer.cwd();
-    /// assert_eq!(cwd.display().to_string(), "/Documents");
+    /// file_explorer.set_trash(Some(Trash::with_fs(fs, "/.trash")));
+    ///
+    /// file_explorer.select_file("resume.pdf");
+    /// file_explorer.handle(Input::Delete).await.unwrap();
+    /// file_explorer.handle(Input::Confirm).await.unwrap();
+    /// assert_eq!(file_explorer.trash().unwrap().list().await.unwrap().len(), 1);
     /// # })
     /// ```
     #[inline]
+    pub fn set_trash(&mut self, trash: Option<Trash<F>>) {
+        self.trash = trash;
+    }
+
+    /// Returns the trash used when a deletion is confirmed, if any.
+    #[inline]
     #[must_use]
-    pub const fn cwd(&self) -> &PathBuf {
-        &self.cwd
+    pub const fn trash(&self) -> Option<&Trash<F>> {
+        self.trash.as_ref()
     }
 
-    /// Indicates whether hidden files are currently visible in the file explorer.
-    /// # Examples
+    /// Creates a new directory named `name` in the current working directory and selects it.
     ///
+    /// `name` may contain several components (e.g. `src/bin`), in which case every
+    /// missing directory is created and the top-level one is selected.
     ///
-    /// You can get the current value like this:
-    /// ```no_run
+    /// # Errors
+    ///
+    /// Will return `Err` if `name` already exists, if the directory can not be created
+    /// or if `cwd` can not be listed afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
     /// use ratatui_async_explorer::FileExplorer;
     ///
     /// # tokio_test::block_on(async {
-    /// let mut file_explorer = FileExplorer::new().await.unwrap();
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string()).await.unwrap();
     ///
-    /// // By default, hidden files are not shown.
-    /// assert_eq!(file_explorer.show_hidden(), false);
-    ///
-    /// file_explorer.set_show_hidden(true).await.unwrap();
-    /// assert_eq!(file_explorer.show_hidden(), true);
+    /// file_explorer.create_dir("new_project").await.unwrap();
+    /// assert_eq!(file_explorer.current().name(), "new_project/");
     /// # })
     /// ```
-    #[inline]
-    #[must_use]
-    pub const fn show_hidden(&self) -> bool {
-        self.show_hidden
-    }
+    pub async fn create_dir(&mut self, name: &str) -> Result<()> {
+        let path = self.cwd.join(name);
+        self.ensure_vacant(&path).await?;
+        let root = self.first_missing(&path).await?;
 
-    /// Returns the a [`Vec`](https://doc.rust-lang.org/stable/std/vec/struct.Vec.html) of files and directories in the current working directory
-    /// of the file explorer, plus the parent directory if it exist.
-    /// When a search filter is active, returns only the filtered files.
+        self.filesystem
+            .create_dir_all(&path.to_string_lossy())
+            .await?;
+        self.ignore_rules.invalidate(&path);
+        self.journal.record(vec![Operation::Create {
+            path,
+            root,
+            is_dir: true,
+        }]);
+
+        self.get_and_set_files().await?;
+        self.select_created(name);
+
+        Ok(())
+    }
+
+    /// Creates a new empty file named `name` in the current working directory and selects it.
+    ///
+    /// If `name` contains several components (e.g. `src/main.rs`), the missing parent
+    /// directories are created first and the top-level one is selected.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `name` already exists, if the file can not be created
+    /// or if `cwd` can not be listed afterwards.
     ///
     /// # Examples
     ///
-    /// Suppose you have this tree file, with `passport.png` selected inside `file_explorer`:
-    /// ```plaintext
-    /// /
-    /// ├── .git
-    /// └── Documents
-    ///     ├── passport.png  <- selected
-    ///     └── resume.pdf
     /// ```
-    /// You can get the [`Vec`](https://doc.rust-lang.org/stable/std/vec/struct.Vec.html) of files and directories like this:
-    /// ```no_run
     /// use ratatui_async_explorer::FileExplorer;
     ///
     /// # tokio_test::block_on(async {
-    /// let file_explorer = FileExplorer::new().await.unwrap();
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string()).await.unwrap();
+    ///
+    /// file_explorer.create_file("README.md").await.unwrap();
+    /// assert_eq!(file_explorer.current().name(), "README.md");
+    /// # })
+    /// ```
+    pub async fn create_file(&mut self, name: &str) -> Result<()> {
+        let path = self.cwd.join(name);
+        let root = self.first_missing(&path).await?;
+
+        if let Some(parent) = path.parent().filter(|parent| *parent != self.cwd) {
+            self.filesystem
+                .create_dir_all(&parent.to_string_lossy())
+                .await?;
+        }
+        self.filesystem.create_file(&path.to_string_lossy()).await?;
+        self.ignore_rules.invalidate(&path);
+        self.journal.record(vec![Operation::Create {
+            path,
+            root,
+            is_dir: false,
+        }]);
+
+        self.get_and_set_files().await?;
+        self.select_created(name);
+
+        Ok(())
+    }
+
+    /// Deletes every [selected path](#method.selected_paths), or moves them to the trash if
+    /// one is [enabled](#method.set_trash).
     ///
-    /// /* user select `password.png` */
+    /// Directories are deleted recursively. Unlike [`Input::Delete`], no confirmation is
+    /// asked. A failure on one path does not stop the others.
     ///
-    /// let files = file_explorer.files();
-    /// assert_eq!(files.len(), 4); // 3 files/directory and the parent directory
+    /// # Errors
+    ///
+    /// Will return `Err` if `cwd` can not be listed afterwards. The result of each
+    /// deletion is reported in the returned [`BatchResults`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::{FileExplorer, Input};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// file_explorer.handle(Input::SelectAll).await.unwrap();
+    ///
+    /// let results = file_explorer.delete_selected().await.unwrap();
+    /// assert!(results.iter().all(|(_, result)| result.is_ok()));
+    /// assert_eq!(file_explorer.files().len(), 1); // Only `../` is left
+    /// assert!(file_explorer.selected_paths().is_empty());
     /// # })
     /// ```
-    #[inline]
-    #[must_use]
-    pub fn files(&self) -> &[File] {
-        &self.files
+    pub async fn delete_selected(&mut self) -> Result<BatchResults> {
+        let mut results = Vec::new();
+        let mut operations = Vec::new();
+
+        for path in self.sorted_selection() {
+            let file_path = path.to_string_lossy().to_string();
+
+            let result = if let Some(trash) = &self.trash {
+                trash.trash(&file_path).await.map(|item| {
+                    operations.push(Operation::Trash {
+                        trash: trash.clone(),
+                        item,
+                    });
+                })
+            } else {
+                match self.filesystem.is_dir(&file_path).await {
+                    Ok(true) => self.filesystem.delete_dir_all(&file_path).await,
+                    Ok(false) => self.filesystem.delete(&file_path).await,
+                    Err(e) => Err(e),
+                }
+            };
+            self.ignore_rules.invalidate(&path);
+            results.push((path, result));
+        }
+
+        self.finish_batch(operations).await?;
+        Ok(results)
     }
 
-    /// Returns all files and directories in the current working directory
-    /// without any filtering applied. This is useful when you need access
-    /// to the complete file list regardless of search filters.
+    /// Copies every [selected path](#method.selected_paths) into the directory `dir`.
+    ///
+    /// Like [`Input::CopyTo`], existing entries are never overwritten. A failure on one
+    /// path does not stop the others.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `cwd` can not be listed afterwards. The result of each
+    /// copy is reported in the returned [`BatchResults`].
+    pub async fn copy_selected_to<P: AsRef<Path>>(&mut self, dir: P) -> Result<BatchResults> {
+        let mut results = Vec::new();
+        let mut operations = Vec::new();
+
+        for path in self.sorted_selection() {
+            let result = async {
+                let to = Self::destination(&path, dir.as_ref())?;
+                self.ensure_vacant(&to).await?;
+
+                self.filesystem
+                    .copy(&path.to_string_lossy(), &to.to_string_lossy())
+                    .await?;
+                Ok(Operation::Copy {
+                    from: path.clone(),
+                    to,
+                })
+            }
+            .await;
+            results.push((path, result.map(|operation| operations.push(operation))));
+        }
+
+        self.finish_batch(operations).await?;
+        Ok(results)
+    }
+
+    /// Moves every [selected path](#method.selected_paths) into the directory `dir`.
+    ///
+    /// Like [`Input::MoveTo`], existing entries are never overwritten. A failure on one
+    /// path does not stop the others. Moved paths are no longer selected.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `cwd` can not be listed afterwards. The result of each
+    /// move is reported in the returned [`BatchResults`].
     ///
     /// # Examples
     ///
-    /// ```no_run
-    /// use ratatui_async_explorer::FileExplorer;
+    /// ```
+    /// use std::path::Path;
+    /// use ratatui_async_explorer::{FileExplorer, Input};
     ///
     /// # tokio_test::block_on(async {
-    /// let mut file_explorer = FileExplorer::new().await.unwrap();
-    /// file_explorer.set_search_filter(Some("test".to_string()));
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// let fs = InMemoryFileSystem::from_tree("/\n├── archive/\n│   └── b.txt\n├── a.txt\n└── b.txt");
+    /// let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string()).await.unwrap();
+    /// file_explorer.select_file("a.txt");
+    /// file_explorer.handle(Input::ToggleSelection).await.unwrap();
+    /// file_explorer.handle(Input::Down).await.unwrap();
+    /// file_explorer.handle(Input::ToggleSelection).await.unwrap();
     ///
-    /// let all_files = file_explorer.all_files();
-    /// let filtered_files = file_explorer.files();
-    /// // all_files.len() >= filtered_files.len()
+    /// let results = file_explorer.move_selected_to("/archive").await.unwrap();
+    /// assert_eq!(results[0].0, Path::new("/a.txt"));
+    /// assert!(results[0].1.is_ok());
+    /// assert_eq!(results[1].0, Path::new("/b.txt"));
+    /// assert!(results[1].1.is_err()); // `/archive/b.txt` already exists
+    ///
+    /// // Only the path that could not be moved is still selected
+    /// assert_eq!(file_explorer.selected_paths().len(), 1);
     /// # })
     /// ```
-    #[inline]
-    #[must_use]
-    pub const fn all_files(&self) -> &Vec<File> {
-        &self.files
+    pub async fn move_selected_to<P: AsRef<Path>>(&mut self, dir: P) -> Result<BatchResults> {
+        let mut results = Vec::new();
+        let mut operations = Vec::new();
+
+        for path in self.sorted_selection() {
+            let result = async {
+                let to = Self::destination(&path, dir.as_ref())?;
+                self.ensure_vacant(&to).await?;
+
+                self.filesystem
+                    .move_to(&path.to_string_lossy(), &to.to_string_lossy())
+                    .await?;
+                Ok(Operation::Move {
+                    from: path.clone(),
+                    to,
+                })
+            }
+            .await;
+            results.push((path, result.map(|operation| operations.push(operation))));
+        }
+
+        self.finish_batch(operations).await?;
+        Ok(results)
     }
 
-    /// Returns the index of the selected file or directory in the filtered view.
-    /// When no search filter is active, this returns the same as the original index.
-    /// When a search filter is active, this returns the index within the filtered list.
+    /// Sets the permissions of every [selected path](#method.selected_paths).
+    ///
+    /// A failure on one path does not stop the others.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `cwd` can not be listed afterwards. The result of each
+    /// change is reported in the returned [`BatchResults`].
     ///
     /// # Examples
     ///
-    /// Suppose you have this tree file, with `passport.png` selected inside `file_explorer`:
-    /// ```plaintext
-    /// /
-    /// ├── .git
-    /// └── Documents
-    ///     ├── passport.png  <- selected
-    ///     └── resume.pdf
     /// ```
-    /// You can get the selected index like this:
-    /// ```no_run
-    /// use ratatui_async_explorer::FileExplorer;
+    /// use ratatui_async_explorer::{FileExplorer, FilePermissions, Input};
     ///
     /// # tokio_test::block_on(async {
-    /// let file_explorer = FileExplorer::new().await.unwrap();
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// # file_explorer.select_file("passport.png");
+    /// file_explorer.handle(Input::ToggleSelection).await.unwrap();
+    ///
+    /// let read_only = FilePermissions::from_mode(0o444);
+    /// file_explorer.chmod_selected(read_only).await.unwrap();
+    /// assert_eq!(file_explorer.current().permissions(), Some(read_only));
+    /// # })
+    /// ```
+    pub async fn chmod_selected(&mut self, permissions: FilePermissions) -> Result<BatchResults> {
+        let mut results = Vec::new();
+        let mut operations = Vec::new();
+
+        let paths = self.sorted_selection();
+        // Changes can only be undone when the previous permissions are known
+        let mut previous = self.permissions_of(&paths).await;
+
+        for path in paths {
+            let previous = previous.remove(&path);
+
+            let result = self
+                .filesystem
+                .set_permissions(&path.to_string_lossy(), permissions)
+                .await
+                .map(|()| {
+                    if let Some(from) = previous {
+                        operations.push(Operation::SetPermissions {
+                            path: path.clone(),
+                            from,
+                            to: permissions,
+                        });
+                    }
+                });
+            results.push((path, result));
+        }
+
+        self.finish_batch(operations).await?;
+        Ok(results)
+    }
+
+    /// Pastes the entries of the [clipboard](#method.clipboard) into the current working
+    /// directory, copying or moving them depending on how they were captured.
     ///
-    /// /* user select `password.png` */
+    /// When a name is already taken, the [conflict policy](#method.set_conflict_policy)
+    /// decides what happens; skipped entries are reported as successful. Pasting an entry
+    /// into the directory it comes from makes a numbered copy of it (or does nothing for
+    /// a cut). The clipboard is emptied once cut entries are pasted.
     ///
-    /// let selected_idx = file_explorer.selected_idx();
+    /// # Errors
     ///
-    /// // Because the file explorer add the parent directory at the beginning
-    /// // of the [`Vec`](https://doc.rust-lang.org/stable/std/vec/struct.Vec.html) of files, the selected index will be 2.
-    /// assert_eq!(selected_idx, 2);
+    /// Will return `Err` if `cwd` can not be listed afterwards. The result of each
+    /// entry is reported in the returned [`BatchResults`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::{ConflictPolicy, FileExplorer, Input};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// let fs = InMemoryFileSystem::from_tree("/\n├── backup/\n│   └── report.pdf\n└── report.pdf");
+    /// let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string()).await.unwrap();
+    /// file_explorer.set_conflict_policy(ConflictPolicy::AutoRename);
+    ///
+    /// file_explorer.select_file("report.pdf");
+    /// file_explorer.handle(Input::Yank).await.unwrap();
+    ///
+    /// file_explorer.set_cwd("/backup").await.unwrap();
+    /// file_explorer.handle(Input::Paste).await.unwrap();
+    ///
+    /// let names: Vec<_> = file_explorer.files().iter().map(|file| file.name()).collect();
+    /// assert_eq!(names, ["../", "report (1).pdf", "report.pdf"]);
+    /// # })
+    /// ```
+    pub async fn paste(&mut self) -> Result<BatchResults> {
+        let Some(clipboard) = self.clipboard.clone() else {
+            return Ok(Vec::new());
+        };
+
+        let mut results = Vec::new();
+        let mut operations = Vec::new();
+
+        for from in clipboard.paths {
+            let result = self
+                .paste_entry(&from, clipboard.mode, &mut operations)
+                .await;
+            results.push((from, result));
+        }
+
+        if clipboard.mode == ClipboardMode::Cut {
+            self.clipboard = None;
+        }
+
+        self.finish_batch(operations).await?;
+        Ok(results)
+    }
+
+    /// Reverts the last file operation performed through the file explorer, returning
+    /// `false` if there is nothing to undo.
+    ///
+    /// Renames, moves, copies, creations, permission changes and pastes are journaled, as
+    /// well as deletions that went through the [trash](#method.set_trash). Permanent
+    /// deletions can not be undone. A batch operation is undone as a whole.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the operation can not be reverted, e.g. because its result was
+    /// modified since. Within a batch, the operations reverted before the failure can then be
+    /// [redone](#method.redo) and the others can still be undone. Also fails if `cwd` can not
+    /// be listed afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::{FileExplorer, Input};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// # file_explorer.select_file("passport.png");
+    /// file_explorer.handle(Input::Rename("id.png".to_string())).await.unwrap();
+    /// file_explorer.create_dir("taxes").await.unwrap();
+    ///
+    /// assert!(file_explorer.undo().await.unwrap());
+    /// assert!(file_explorer.undo().await.unwrap());
+    /// assert!(!file_explorer.undo().await.unwrap());
+    /// assert!(file_explorer.select_file("passport.png"));
+    ///
+    /// assert!(file_explorer.redo().await.unwrap());
+    /// assert!(file_explorer.select_file("id.png"));
     /// # })
     /// ```
+    pub async fn undo(&mut self) -> Result<bool> {
+        let Some(operations) = self.journal.undo.pop() else {
+            return Ok(false);
+        };
+
+        // The operations are reverted from the last one, and on failure the journal keeps
+        // those not reverted yet on the undo stack
+        let mut remaining = operations;
+        let mut reverted = Vec::with_capacity(remaining.len());
+        let mut result = Ok(());
+        while let Some(operation) = remaining.pop() {
+            match operation.revert(&*self.filesystem).await {
+                Ok(operation) => reverted.push(operation),
+                Err(e) => {
+                    remaining.push(operation);
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        reverted.reverse();
+        for path in reverted.iter().flat_map(Operation::paths) {
+            self.ignore_rules.invalidate(path);
+        }
+
+        if !remaining.is_empty() {
+            self.journal.undo.push(remaining);
+        }
+        if !reverted.is_empty() {
+            self.journal.redo.push(reverted);
+        }
+
+        self.finish_batch(Vec::new()).await?;
+        result.map(|()| true)
+    }
+
+    /// Performs again the last file operation reverted by [`undo`](#method.undo), returning
+    /// `false` if there is nothing to redo.
+    ///
+    /// Any new file operation makes the undone ones impossible to redo.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the operation can not be performed again. Within a batch, the
+    /// operations performed before the failure can then be [undone](#method.undo) and the
+    /// others can still be redone. Also fails if `cwd` can not be listed afterwards.
+    pub async fn redo(&mut self) -> Result<bool> {
+        let Some(operations) = self.journal.redo.pop() else {
+            return Ok(false);
+        };
+
+        let mut remaining = operations.into_iter();
+        let mut applied = Vec::with_capacity(remaining.len());
+        let mut result = Ok(());
+        for operation in remaining.by_ref() {
+            match operation.apply(&*self.filesystem).await {
+                Ok(operation) => applied.push(operation),
+                Err(e) => {
+                    // The failed operation stays on the redo stack with the following ones
+                    let remaining: Vec<_> = std::iter::once(operation).chain(remaining).collect();
+                    self.journal.redo.push(remaining);
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+
+        for path in applied.iter().flat_map(Operation::paths) {
+            self.ignore_rules.invalidate(path);
+        }
+        if !applied.is_empty() {
+            self.journal.undo.push(applied);
+        }
+
+        self.finish_batch(Vec::new()).await?;
+        result.map(|()| true)
+    }
+
+    /// Returns whether there is a file operation to [`undo`](#method.undo).
     #[inline]
     #[must_use]
-    pub fn selected_idx(&self) -> usize {
-        self.filtered_selected_idx().unwrap_or(0)
+    pub fn can_undo(&self) -> bool {
+        !self.journal.undo.is_empty()
     }
 
-    /// Returns the original index of the selected file in the complete file list.
-    /// This is useful when you need to know the actual position in the unfiltered list.
+    /// Returns whether there is an undone file operation to [`redo`](#method.redo).
+    #[inline]
+    #[must_use]
+    pub fn can_redo(&self) -> bool {
+        !self.journal.redo.is_empty()
+    }
+
+    /// Sets what happens when pasting an entry whose name is already taken.
+    ///
+    /// Defaults to [`ConflictPolicy::Skip`].
+    #[inline]
+    pub fn set_conflict_policy(&mut self, policy: ConflictPolicy) {
+        self.conflict_policy = policy;
+    }
+
+    /// Empties the clipboard.
+    #[inline]
+    pub fn clear_clipboard(&mut self) {
+        self.clipboard = None;
+    }
+
+    /// Sets whether hidden files should be shown in the file explorer.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the directory `cwd` can not be listed.
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```
     /// use ratatui_async_explorer::FileExplorer;
     ///
     /// # tokio_test::block_on(async {
     /// let mut file_explorer = FileExplorer::new().await.unwrap();
-    /// file_explorer.set_search_filter(Some("test".to_string()));
     ///
-    /// let filtered_idx = file_explorer.selected_idx();
-    /// let original_idx = file_explorer.original_selected_idx();
-    /// // original_idx >= filtered_idx
+    /// file_explorer.set_show_hidden(true).await.unwrap();
+    /// assert_eq!(file_explorer.show_hidden(), true);
     /// # })
     /// ```
     #[inline]
-    #[must_use]
-    pub const fn original_selected_idx(&self) -> usize {
-        self.selected
+    pub async fn set_show_hidden(&mut self, show_hidden: bool) -> Result<()> {
+        self.show_hidden = show_hidden;
+        self.get_and_set_files().await?;
+        self.selected = 0;
+
+        Ok(())
+    }
+
+    /// Sets whether the entries ignored by the `.gitignore`, `.ignore` and `.git/info/exclude`
+    /// files should be shown in the file explorer.
+    ///
+    /// When they are hidden, the rules of the current directory and of its parents are read
+    /// through the [`FileSystem`] the first time a directory is listed, up to the root of the
+    /// git repository. They are kept for the most recently listed directories until a file
+    /// operation of the file explorer touches an ignore file, or until
+    /// [`set_cwd`](#method.set_cwd) is called. The `.gitignore` and `.git/info/exclude` files
+    /// only apply inside a git repository, while the `.ignore` files always apply, like in
+    /// [ripgrep](https://github.com/BurntSushi/ripgrep).
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the directory `cwd` can not be listed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n├── .gitignore\n├── node_modules/\n├── target/\n└── src/");
+    /// # let fs = fs.with_file("/.gitignore", "target/\nnode_modules/\n");
+    /// let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string()).await.unwrap();
+    ///
+    /// file_explorer.set_show_ignored(false).await.unwrap();
+    /// let names: Vec<_> = file_explorer.files().iter().map(|file| file.name()).collect();
+    /// assert_eq!(names, ["src/"]);
+    /// # })
+    /// ```
+    pub async fn set_show_ignored(&mut self, show_ignored: bool) -> Result<()> {
+        self.show_ignored = show_ignored;
+        self.get_and_set_files().await?;
+        self.selected = 0;
+
+        Ok(())
+    }
+
+    /// Sets whether the [git status](File::git_status) of the entries should be computed.
+    ///
+    /// While it is enabled, `git status` is run in the current directory every time it is
+    /// listed, which requires git to be installed. Whether the directory is inside a git
+    /// repository is only checked the first time, until [`set_cwd`](#method.set_cwd) is
+    /// called, and the entries are left without status outside of one. git is only run in
+    /// the directories the [`FileSystem`] reports as [local](FileSystem::is_local), so the
+    /// entries of other filesystems or of archives never have a status.
+    ///
+    /// The widget styles the names with
+    /// [`Theme::git_status_style`](crate::Theme::git_status_style) and appends the
+    /// [`GitStatus::symbol`](crate::GitStatus::symbol) to them.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the directory `cwd` can not be listed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ratatui_async_explorer::{FileExplorer, GitStatus};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut file_explorer = FileExplorer::new().await.unwrap();
+    ///
+    /// file_explorer.set_git_status(true).await.unwrap();
+    /// let modified = file_explorer
+    ///     .files()
+    ///     .iter()
+    ///     .filter(|file| file.git_status() == Some(GitStatus::Modified))
+    ///     .count();
+    /// # })
+    /// ```
+    pub async fn set_git_status(&mut self, enabled: bool) -> Result<()> {
+        self.git_status = enabled;
+        self.get_and_set_files().await
+    }
+
+    /// Sets the key used to sort the entries, keeping the current selection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::{FileExplorer, SortBy};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// let fs = InMemoryFileSystem::new()
+    ///     .with_file("/notes/big.txt", "a lot of text")
+    ///     .with_file("/notes/small.txt", "");
+    /// let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/notes".to_string()).await.unwrap();
+    /// assert_eq!(file_explorer.files()[1].name(), "big.txt");
+    ///
+    /// file_explorer.set_sort_by(SortBy::Size);
+    /// assert_eq!(file_explorer.files()[1].name(), "small.txt");
+    /// # })
+    /// ```
+    pub fn set_sort_by(&mut self, sort_by: SortBy) {
+        self.sort.sort_by = sort_by;
+        self.resort();
+    }
+
+    /// Sets the direction of the sort, keeping the current selection.
+    ///
+    /// The parent directory (`../`) always stays first.
+    pub fn set_sort_order(&mut self, sort_order: SortOrder) {
+        self.sort.order = sort_order;
+        self.resort();
+    }
+
+    /// Sets whether directories are listed before files, whatever the sort order.
+    ///
+    /// This is enabled by default.
+    pub fn set_dirs_first(&mut self, dirs_first: bool) {
+        self.sort.dirs_first = dirs_first;
+        self.resort();
+    }
+
+    /// Sets whether names are compared naturally, that is with digit sequences compared
+    /// by their numeric value.
+    ///
+    /// This is disabled by default, in which case names are compared character by character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// let fs = InMemoryFileSystem::from_tree("/\n├── build10\n├── build2\n└── build9");
+    /// let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string()).await.unwrap();
+    ///
+    /// file_explorer.set_natural_sort(true);
+    /// let names: Vec<_> = file_explorer.files().iter().map(|file| file.name()).collect();
+    /// assert_eq!(names, ["build2", "build9", "build10"]);
+    /// # })
+    /// ```
+    pub fn set_natural_sort(&mut self, natural: bool) {
+        self.sort.natural = natural;
+        self.resort();
+    }
+
+    /// Sets whether names and extensions are compared regardless of their case.
+    ///
+    /// This is disabled by default, in which case `Zeta` comes before `alpha`.
+    pub fn set_case_insensitive_sort(&mut self, case_insensitive: bool) {
+        self.sort.case_insensitive = case_insensitive;
+        self.resort();
+    }
+
+    /// Sets the theme of the file explorer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::{FileExplorer, Theme};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut file_explorer = FileExplorer::new().await.unwrap();
+    ///
+    /// file_explorer.set_theme(Theme::default().add_default_title());
+    /// # })
+    /// ```
+    #[inline]
+    pub fn set_theme(&mut self, theme: Theme<F>) {
+        self.theme = theme;
+    }
+
+    /// Sets the search filter to filter files and directories by name.
+    ///
+    /// When a search filter is set, only files and directories whose names contain
+    /// the filter string (case-insensitive) will be displayed. Set to `None` to clear
+    /// the filter and show all files.
+    ///
+    /// The search filter is the [`FilterStack::QUERY`] layer of the
+    /// [filter stack](#method.add_filter_layer), so it combines with the other layers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut file_explorer = FileExplorer::new().await.unwrap();
+    ///
+    /// // Filter to show only files containing "test"
+    /// file_explorer.set_search_filter(Some("test".to_string()));
+    ///
+    /// // Clear the filter
+    /// file_explorer.set_search_filter(None);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn set_search_filter(&mut self, filter: Option<String>) {
+        match filter {
+            Some(query) => self
+                .filters
+                .insert(FilterStack::QUERY, FilterLayer::Query(query)),
+            None => {
+                self.filters.remove(FilterStack::QUERY);
+            }
+        }
+        self.filtered_files = self.compute_filtered_files();
+    }
+
+    /// Sets the fuzzy filter, like the fuzzy finder of [fzf](https://github.com/junegunn/fzf).
+    ///
+    /// When a fuzzy filter is set, only the entries whose names contain the characters of
+    /// the query in order are displayed, the best matches first, and the best match is
+    /// selected. The case is ignored unless the query contains an uppercase letter. The
+    /// matched characters of every entry of [`filtered_files`](#method.filtered_files) are
+    /// available with [`File::match_positions`] and highlighted by the widget. Set to `None` to clear the filter.
+    ///
+    /// The fuzzy filter applies on top of the [filter stack](#method.add_filter_layer).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// let fs = InMemoryFileSystem::from_tree("/\n├── safe.rs\n├── file_explorer.rs\n└── lib.rs");
+    /// let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string()).await.unwrap();
+    ///
+    /// file_explorer.set_fuzzy_filter(Some("fe".to_string()));
+    /// let names: Vec<_> = file_explorer.filtered_files().iter().map(|file| file.name()).collect();
+    /// assert_eq!(names, ["file_explorer.rs", "safe.rs"]);
+    /// assert_eq!(file_explorer.current().name(), "file_explorer.rs");
+    /// assert_eq!(file_explorer.filtered_files()[0].match_positions(), [0, 3]);
+    /// # })
+    /// ```
+    pub fn set_fuzzy_filter(&mut self, query: Option<String>) {
+        self.fuzzy_filter = query.filter(|query| !query.is_empty());
+        self.filtered_files = self.compute_filtered_files();
+
+        if self.fuzzy_filter.is_some() {
+            if let Some(best) = self.filtered_files.first() {
+                self.selected = best.idx();
+            }
+        }
+    }
+
+    /// Sets a custom closure-based filter with filter_map semantics.
+    ///
+    /// The closure receives a reference to a [`File`] and returns `Some(File)` to include it
+    /// (possibly transformed), or `None` to exclude it.
+    ///
+    /// The custom filter is the [`FilterStack::CUSTOM`] layer of the
+    /// [filter stack](#method.add_filter_layer), so it combines with the
+    /// [search filter](#method.set_search_filter) and the other layers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut file_explorer = FileExplorer::new().await.unwrap();
+    ///
+    /// // Filter to show only directories
+    /// file_explorer.set_filter(Some(|file: &ratatui_async_explorer::File| {
+    ///     if file.is_dir() {
+    ///         Some(file.clone())
+    ///     } else {
+    ///         None
+    ///     }
+    /// }));
+    ///
+    /// // Clear the filter
+    /// file_explorer.clear_filter();
+    /// # })
+    /// ```
+    pub fn set_filter<P>(&mut self, filter: Option<P>)
+    where
+        P: Fn(&File) -> Option<File> + Send + Sync + 'static,
+    {
+        match filter {
+            Some(filter) => self.filters.insert(
+                FilterStack::CUSTOM,
+                FilterLayer::Custom(Arc::new(filter) as FileFilter),
+            ),
+            None => {
+                self.filters.remove(FilterStack::CUSTOM);
+            }
+        }
+        self.filtered_files = self.compute_filtered_files();
+    }
+
+    /// Clears every layer of the filter stack, including the closure-based filter and the
+    /// search filter, and the fuzzy filter, showing all files.
+    pub fn clear_filter(&mut self) {
+        self.filters.clear();
+        self.fuzzy_filter = None;
+        self.filtered_files = self.compute_filtered_files();
+    }
+
+    /// Adds a layer to the filter stack under the given name, enabled, replacing the layer
+    /// having the same name, if any.
+    ///
+    /// An entry is displayed only if every enabled layer keeps it, so the layers narrow each
+    /// other down: glob [include](FilterLayer::include) and [exclude](FilterLayer::exclude)
+    /// patterns, an [extension](FilterLayer::extensions) allow-list, a [kind](FilterLayer::Kind)
+    /// filter, the [search filter](#method.set_search_filter) and the
+    /// [custom filter](#method.set_filter).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::{EntryKind, FileExplorer, FilterLayer};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// let fs = InMemoryFileSystem::from_tree("/\n├── src/\n├── build.rs\n├── Cargo.toml\n└── README.md");
+    /// let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string()).await.unwrap();
+    ///
+    /// file_explorer.add_filter_layer("rust", FilterLayer::include(["*.rs", "*.toml"]).unwrap());
+    /// file_explorer.add_filter_layer("files", FilterLayer::Kind(EntryKind::File));
+    /// file_explorer.set_search_filter(Some("CARGO".to_string()));
+    /// let names: Vec<_> = file_explorer.filtered_files().iter().map(|file| file.name()).collect();
+    /// assert_eq!(names, ["Cargo.toml"]);
+    ///
+    /// file_explorer.set_search_filter(None);
+    /// file_explorer.toggle_filter_layer("files");
+    /// let names: Vec<_> = file_explorer.filtered_files().iter().map(|file| file.name()).collect();
+    /// assert_eq!(names, ["src/", "Cargo.toml", "build.rs"]);
+    /// # })
+    /// ```
+    pub fn add_filter_layer(&mut self, name: impl Into<String>, layer: FilterLayer) {
+        self.filters.insert(name, layer);
+        self.filtered_files = self.compute_filtered_files();
+    }
+
+    /// Removes the layer having the given name from the filter stack, returning it.
+    pub fn remove_filter_layer(&mut self, name: &str) -> Option<FilterLayer> {
+        let layer = self.filters.remove(name);
+        self.filtered_files = self.compute_filtered_files();
+        layer
+    }
+
+    /// Switches the layer having the given name on or off, returning `false` if there is no
+    /// such layer. A disabled layer keeps its settings but no longer hides anything.
+    pub fn set_filter_layer_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        let found = self.filters.set_enabled(name, enabled);
+        self.filtered_files = self.compute_filtered_files();
+        found
+    }
+
+    /// Switches the layer having the given name off if it is enabled, or on otherwise,
+    /// returning `false` if there is no such layer.
+    pub fn toggle_filter_layer(&mut self, name: &str) -> bool {
+        let enabled = self.filters.is_enabled(name);
+        self.set_filter_layer_enabled(name, !enabled)
+    }
+
+    /// Returns the filter stack.
+    #[inline]
+    #[must_use]
+    pub const fn filters(&self) -> &FilterStack {
+        &self.filters
+    }
+
+    /// Returns the key used to sort the entries.
+    #[inline]
+    #[must_use]
+    pub const fn sort_by(&self) -> SortBy {
+        self.sort.sort_by
+    }
+
+    /// Returns the direction of the sort.
+    #[inline]
+    #[must_use]
+    pub const fn sort_order(&self) -> SortOrder {
+        self.sort.order
+    }
+
+    /// Returns whether directories are listed before files.
+    #[inline]
+    #[must_use]
+    pub const fn dirs_first(&self) -> bool {
+        self.sort.dirs_first
+    }
+
+    /// Returns whether names are compared naturally.
+    #[inline]
+    #[must_use]
+    pub const fn natural_sort(&self) -> bool {
+        self.sort.natural
+    }
+
+    /// Returns whether names are compared regardless of their case.
+    #[inline]
+    #[must_use]
+    pub const fn case_insensitive_sort(&self) -> bool {
+        self.sort.case_insensitive
+    }
+
+    /// Returns the current search filter, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut file_explorer = FileExplorer::new().await.unwrap();
+    /// assert_eq!(file_explorer.search_filter(), None);
+    ///
+    /// file_explorer.set_search_filter(Some("test".to_string()));
+    /// assert_eq!(file_explorer.search_filter(), Some("test"));
+    /// # })
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn search_filter(&self) -> Option<&str> {
+        if !self.filters.is_enabled(FilterStack::QUERY) {
+            return None;
+        }
+        match self.filters.get(FilterStack::QUERY) {
+            Some(FilterLayer::Query(query)) => Some(query),
+            _ => None,
+        }
+    }
+
+    /// Returns the query of the fuzzy filter, if any.
+    #[inline]
+    #[must_use]
+    pub fn fuzzy_filter(&self) -> Option<&str> {
+        self.fuzzy_filter.as_deref()
+    }
+
+    //
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let file_explorer = FileExplorer::new().await.unwrap();
+    /// let filesystem = file_explorer.filesystem();
+    /// # })
+    /// ```
+    pub fn filesystem(&self) -> &F {
+        &self.filesystem
+    }
+
+    /// Returns the cached filtered files with their original indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # async fn example() -> std::io::Result<()> {
+    /// let mut file_explorer = FileExplorer::new().await?;
+    /// file_explorer.set_search_filter(Some("test".to_string()));
+    /// let filtered_files = file_explorer.filtered_files();
+    /// println!("Found {} filtered files", filtered_files.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn filtered_files(&self) -> &[File] {
+        &self.filtered_files
+    }
+
+    /// Sets the selected file or directory index in the filtered view.
+    /// When no search filter is active, this works the same as before.
+    /// When a search filter is active, this accepts an index within the filtered list
+    /// and converts it to the corresponding original index.
+    ///
+    /// The file explorer add the parent directory at the beginning of the
+    /// [`Vec`](https://doc.rust-lang.org/stable/std/vec/struct.Vec.html) of files, so setting the selected index to 0 will select the parent directory
+    /// (if the current working directory not the root directory).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `selected` is greater or equal to the number of files in the current view
+    /// (filtered files if a filter is active, or all files if no filter).
+    ///
+    /// # Examples
+    ///
+    /// Suppose you have this tree file, with `passport.png` selected inside `file_explorer`:
+    /// ```plaintext
+    /// /
+    /// ├── .git
+    /// └── Documents
+    ///     ├── passport.png  <- selected (index 1)
+    ///     └── resume.pdf
+    /// ```
+    /// You can set the selected index like this:
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// # file_explorer.select_file("passport.png");
+    ///
+    /// // Because the file explorer add the parent directory at the beginning
+    /// // of the [`Vec`](https://doc.rust-lang.org/stable/std/vec/struct.Vec.html) of files, index 0 is indeed the parent directory.
+    /// file_explorer.set_selected_idx(0);
+    /// assert_eq!(file_explorer.current().path().display().to_string(), "/");
+    ///
+    /// file_explorer.set_selected_idx(1);
+    /// assert_eq!(file_explorer.current().path().display().to_string(), "/Documents/passport.png");
+    /// # })
+    /// ```
+    ///
+    /// Note: Attempting to set an index >= the number of files will cause a panic.
+    #[inline]
+    pub fn set_selected_idx(&mut self, selected: usize) {
+        assert!(selected < self.filtered_files.len());
+
+        if let Some(file) = self.filtered_files.get(selected) {
+            self.selected = file.idx();
+        }
+    }
+
+    /// Returns the current file or directory selected.
+    ///
+    /// # Examples
+    ///
+    /// Suppose you have this tree file, with `passport.png` selected inside `file_explorer`:
+    /// ```plaintext
+    /// /
+    /// ├── .git
+    /// └── Documents
+    ///     ├── passport.png  <- selected
+    ///     └── resume.pdf
+    /// ```
+    /// You can get the current file like this:
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// # file_explorer.select_file("passport.png");
+    ///
+    /// let file = file_explorer.current();
+    /// assert_eq!(file.name(), "passport.png");
+    /// # })
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current directory has no entry at all, which only happens when it has
+    /// no parent either, e.g. at the root of an empty [`Chroot`](crate::filesystem::Chroot).
+    #[inline]
+    #[must_use]
+    pub fn current(&self) -> &File {
+        &self.files[self.selected]
+    }
+
+    /// Returns the selected entry, or `None` if the current directory has no entry at all.
+    #[inline]
+    pub(crate) fn current_entry(&self) -> Option<&File> {
+        self.files.get(self.selected)
+    }
+
+    /// Select a file by name in the current directory.
+    ///
+    /// Returns true if the file was found and selected, false otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # async fn example() -> std::io::Result<()> {
+    /// let mut file_explorer = FileExplorer::new().await?;
+    ///
+    /// // Select a specific file
+    /// if file_explorer.select_file("myfile.txt") {
+    ///     println!("Selected myfile.txt");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn select_file(&mut self, filename: &str) -> bool {
+        if let Some(index) = self.files.iter().position(|f| f.name == filename) {
+            self.selected = index;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the current working directory of the file explorer.
+    ///
+    /// # Examples
+    ///
+    /// Suppose you have this tree file, with `passport.png` selected inside `file_explorer`:
+    /// ```plaintext
+    /// /
+    /// ├── .git
+    /// └── Documents
+    ///     ├── passport.png  <- selected
+    ///     └── resume.pdf
+    /// ```
+    /// You can get the current working directory like this:
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// # file_explorer.select_file("passport.png");
+    ///
+    /// let cwd = file_explorer.cwd();
+    /// assert_eq!(cwd.display().to_string(), "/Documents");
+    /// # })
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn cwd(&self) -> &PathBuf {
+        &self.cwd
+    }
+
+    /// Indicates whether hidden files are currently visible in the file explorer.
+    /// # Examples
+    ///
+    ///
+    /// You can get the current value like this:
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut file_explorer = FileExplorer::new().await.unwrap();
+    ///
+    /// // By default, hidden files are not shown.
+    /// assert_eq!(file_explorer.show_hidden(), false);
+    ///
+    /// file_explorer.set_show_hidden(true).await.unwrap();
+    /// assert_eq!(file_explorer.show_hidden(), true);
+    /// # })
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn show_hidden(&self) -> bool {
+        self.show_hidden
+    }
+
+    /// Indicates whether the [git status](File::git_status) of the entries is computed, see
+    /// [`set_git_status`](#method.set_git_status).
+    #[inline]
+    #[must_use]
+    pub const fn git_status_enabled(&self) -> bool {
+        self.git_status
+    }
+
+    /// Indicates whether the entries ignored by the ignore files are currently visible, which
+    /// is the case by default, see [`set_show_ignored`](#method.set_show_ignored).
+    #[inline]
+    #[must_use]
+    pub const fn show_ignored(&self) -> bool {
+        self.show_ignored
+    }
+
+    /// Returns the a [`Vec`](https://doc.rust-lang.org/stable/std/vec/struct.Vec.html) of files and directories in the current working directory
+    /// of the file explorer, plus the parent directory if it exist.
+    /// When a search filter is active, returns only the filtered files.
+    ///
+    /// # Examples
+    ///
+    /// Suppose you have this tree file, with `passport.png` selected inside `file_explorer`:
+    /// ```plaintext
+    /// /
+    /// ├── .git
+    /// └── Documents
+    ///     ├── passport.png  <- selected
+    ///     └── resume.pdf
+    /// ```
+    /// You can get the [`Vec`](https://doc.rust-lang.org/stable/std/vec/struct.Vec.html) of files and directories like this:
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// # file_explorer.select_file("passport.png");
+    ///
+    /// let files = file_explorer.files();
+    /// assert_eq!(files.len(), 3); // 2 files and the parent directory
+    /// # })
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn files(&self) -> &[File] {
+        &self.files
+    }
+
+    /// Returns all files and directories in the current working directory
+    /// without any filtering applied. This is useful when you need access
+    /// to the complete file list regardless of search filters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut file_explorer = FileExplorer::new().await.unwrap();
+    /// file_explorer.set_search_filter(Some("test".to_string()));
+    ///
+    /// let all_files = file_explorer.all_files();
+    /// let filtered_files = file_explorer.files();
+    /// // all_files.len() >= filtered_files.len()
+    /// # })
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn all_files(&self) -> &Vec<File> {
+        &self.files
+    }
+
+    /// Returns the index of the selected file or directory in the filtered view.
+    /// When no search filter is active, this returns the same as the original index.
+    /// When a search filter is active, this returns the index within the filtered list.
+    ///
+    /// # Examples
+    ///
+    /// Suppose you have this tree file, with `passport.png` selected inside `file_explorer`:
+    /// ```plaintext
+    /// /
+    /// ├── .git
+    /// └── Documents
+    ///     ├── passport.png  <- selected
+    ///     └── resume.pdf
+    /// ```
+    /// You can get the selected index like this:
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// # file_explorer.select_file("passport.png");
+    ///
+    /// let selected_idx = file_explorer.selected_idx();
+    ///
+    /// // Because the file explorer add the parent directory at the beginning
+    /// // of the [`Vec`](https://doc.rust-lang.org/stable/std/vec/struct.Vec.html) of files, the selected index will be 1.
+    /// assert_eq!(selected_idx, 1);
+    /// # })
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn selected_idx(&self) -> usize {
+        self.filtered_selected_idx().unwrap_or(0)
+    }
+
+    /// Returns the original index of the selected file in the complete file list.
+    /// This is useful when you need to know the actual position in the unfiltered list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut file_explorer = FileExplorer::new().await.unwrap();
+    /// file_explorer.set_search_filter(Some("test".to_string()));
+    ///
+    /// let filtered_idx = file_explorer.selected_idx();
+    /// let original_idx = file_explorer.original_selected_idx();
+    /// // original_idx >= filtered_idx
+    /// # })
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn original_selected_idx(&self) -> usize {
+        self.selected
+    }
+
+    /// Returns the theme of the file explorer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::{FileExplorer, Theme};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let file_explorer = FileExplorer::new().await.unwrap();
+    ///
+    /// assert_eq!(file_explorer.theme(), &Theme::default());
+    /// # })
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn theme(&self) -> &Theme<F> {
+        &self.theme
+    }
+
+    /// Returns the current scroll offset of the file explorer.
+    ///
+    /// This represents the index of the first visible item in the list.
+    #[inline]
+    #[must_use]
+    pub const fn scroll_offset(&self) -> usize {
+        self.scroll_offset
+    }
+
+    /// Sets the scroll offset for the file explorer.
+    ///
+    /// This is used internally by the widget renderer to maintain scroll state.
+    #[inline]
+    pub(crate) fn set_scroll_offset(&mut self, offset: usize) {
+        self.scroll_offset = offset;
+    }
+
+    /// Sets the paths of files that should be displayed as selected.
+    ///
+    /// This allows external state management of file selection, useful for
+    /// multi-file operations like batch copying or deleting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use std::path::PathBuf;
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # async fn example() -> std::io::Result<()> {
+    /// let mut file_explorer = FileExplorer::new().await?;
+    ///
+    /// let mut selected = HashSet::new();
+    /// selected.insert(PathBuf::from("/home/user/file1.txt"));
+    /// selected.insert(PathBuf::from("/home/user/file2.txt"));
+    ///
+    /// file_explorer.set_selected_paths(selected);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn set_selected_paths(&mut self, paths: HashSet<PathBuf>) {
+        self.selected_paths = paths;
+    }
+
+    /// Returns a reference to the set of selected file paths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # async fn example() -> std::io::Result<()> {
+    /// let file_explorer = FileExplorer::new().await?;
+    /// let selected = file_explorer.selected_paths();
+    /// println!("Number of selected files: {}", selected.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The selection can also be changed with inputs, which only act on the entries
+    /// passing the current filter:
+    ///
+    /// ```
+    /// use ratatui_async_explorer::{FileExplorer, Input};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// let fs = InMemoryFileSystem::from_tree("/\n├── a.png\n├── b.pdf\n├── c.png\n└── d.png");
+    /// let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string()).await.unwrap();
+    ///
+    /// file_explorer.set_search_filter(Some(".png".to_string()));
+    /// file_explorer.handle(Input::SelectAll).await.unwrap();
+    /// assert_eq!(file_explorer.selected_paths().len(), 3);
+    ///
+    /// file_explorer.handle(Input::ClearSelection).await.unwrap();
+    /// file_explorer.handle(Input::ToggleSelection).await.unwrap(); // a.png
+    /// file_explorer.handle(Input::End).await.unwrap();
+    /// file_explorer.handle(Input::SelectRange).await.unwrap(); // up to d.png, skipping b.pdf
+    /// assert_eq!(file_explorer.selected_paths().len(), 3);
+    ///
+    /// file_explorer.set_search_filter(None);
+    /// file_explorer.handle(Input::InvertSelection).await.unwrap();
+    /// assert_eq!(file_explorer.current().name(), "d.png");
+    /// assert!(!file_explorer.is_file_selected(file_explorer.current()));
+    /// assert_eq!(file_explorer.selected_paths().len(), 1); // b.pdf
+    /// # })
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn selected_paths(&self) -> &HashSet<PathBuf> {
+        &self.selected_paths
+    }
+
+    /// Checks if a specific file is marked as selected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::FileExplorer;
+    ///
+    /// # async fn example() -> std::io::Result<()> {
+    /// let file_explorer = FileExplorer::new().await?;
+    /// let current_file = file_explorer.current();
+    ///
+    /// if file_explorer.is_file_selected(current_file) {
+    ///     println!("Current file is selected");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_file_selected(&self, file: &File) -> bool {
+        self.selected_paths.contains(&file.path)
+    }
+
+    /// Returns the entries captured by [`Input::Yank`] or [`Input::Cut`], if any.
+    #[inline]
+    #[must_use]
+    pub const fn clipboard(&self) -> Option<&Clipboard> {
+        self.clipboard.as_ref()
+    }
+
+    /// Returns what happens when pasting an entry whose name is already taken.
+    #[inline]
+    #[must_use]
+    pub const fn conflict_policy(&self) -> ConflictPolicy {
+        self.conflict_policy
+    }
+
+    /// Returns the entry awaiting deletion, if [`Input::Delete`] was received and has not
+    /// been confirmed or cancelled yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::{FileExplorer, Input};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// # file_explorer.select_file("passport.png");
+    ///
+    /// file_explorer.handle(Input::Delete).await.unwrap();
+    /// assert!(file_explorer.pending_delete().is_some());
+    ///
+    /// file_explorer.handle(Input::Cancel).await.unwrap();
+    /// assert!(file_explorer.pending_delete().is_none());
+    /// # })
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn pending_delete(&self) -> Option<&File> {
+        self.pending_delete.as_ref()
+    }
+
+    /// Select a file by path in the current directory.
+    ///
+    /// Returns true if the file was found and selected, false otherwise.
+    fn select_path(&mut self, path: &Path) -> bool {
+        if let Some(index) = self.files.iter().position(|f| f.path == path) {
+            self.selected = index;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Select the entry of the current directory that contains the freshly created `name`.
+    fn select_created(&mut self, name: &str) {
+        let top_level = Path::new(name)
+            .components()
+            .next()
+            .map(|component| self.cwd.join(component));
+
+        if !top_level.is_some_and(|path| self.select_path(&path)) {
+            self.clamp_selected();
+        }
+    }
+
+    /// Keep the selected index in bounds after entries were removed from the listing.
+    fn clamp_selected(&mut self) {
+        if self.selected >= self.files.len() && !self.files.is_empty() {
+            self.selected = self.files.len() - 1;
+        }
+    }
+
+    /// Sort the entries again after a change of the sort settings, keeping the selection.
+    fn resort(&mut self) {
+        let selected = self.files.get(self.selected).map(|file| file.path.clone());
+
+        let start = usize::from(self.files.first().is_some_and(File::is_navigation_entry));
+        self.sort.sort(&mut self.files[start..]);
+
+        for (i, file) in self.files.iter_mut().enumerate() {
+            file.idx = i;
+        }
+        self.filtered_files = self.compute_filtered_files();
+
+        if !selected.is_some_and(|path| self.select_path(&path)) {
+            self.clamp_selected();
+        }
+    }
+
+    /// Put the selected paths, or the current entry if nothing is selected, in the clipboard.
+    fn fill_clipboard(&mut self, mode: ClipboardMode) {
+        let paths = if self.selected_paths.is_empty() {
+            self.operable_current().into_iter().collect()
+        } else {
+            self.sorted_selection()
+        };
+
+        if !paths.is_empty() {
+            self.clipboard = Some(Clipboard { paths, mode });
+        }
+    }
+
+    /// Copy or move `from` into the current working directory, applying the conflict policy,
+    /// and add what was done to `operations`.
+    async fn paste_entry(
+        &self,
+        from: &Path,
+        mode: ClipboardMode,
+        operations: &mut Vec<Operation<F>>,
+    ) -> Result<()> {
+        let mut to = Self::destination(from, &self.cwd)?;
+
+        if to == from {
+            // Pasting an entry where it already is
+            match mode {
+                ClipboardMode::Copy => to = self.numbered_destination(from).await?,
+                ClipboardMode::Cut => return Ok(()),
+            }
+        } else if to.starts_with(from) || from.starts_with(&to) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Cannot paste {} into itself", from.display()),
+            ));
+        } else if self.filesystem.exists(&to.to_string_lossy()).await? {
+            match self.conflict_policy {
+                ConflictPolicy::Skip => return Ok(()),
+                ConflictPolicy::Overwrite => {
+                    if let Some(trash) = &self.trash {
+                        // The replaced entry can be restored by undoing the paste
+                        let item = trash.trash(&to.to_string_lossy()).await?;
+                        operations.push(Operation::Trash {
+                            trash: trash.clone(),
+                            item,
+                        });
+                    } else {
+                        return self.paste_over(from, &to, mode, operations).await;
+                    }
+                }
+                ConflictPolicy::AutoRename => to = self.numbered_destination(&to).await?,
+            }
+        }
+
+        self.transfer(from, &to, mode).await?;
+        operations.push(Self::transfer_operation(from, to, mode));
+
+        Ok(())
+    }
+
+    /// Copy or move `from` over the existing entry `to`, which is only removed once `from`
+    /// is in place: the entry is pasted under a temporary name first, then swapped with `to`.
+    async fn paste_over(
+        &self,
+        from: &Path,
+        to: &Path,
+        mode: ClipboardMode,
+        operations: &mut Vec<Operation<F>>,
+    ) -> Result<()> {
+        let staged = self.vacant_sibling(to, "paste").await?;
+        if let Err(e) = self.transfer(from, &staged, mode).await {
+            // Leave nothing half copied behind
+            let _ = self.remove(&staged).await;
+            return Err(e);
+        }
+
+        let backup = self.vacant_sibling(to, "old").await?;
+        let (to_str, staged_str, backup_str) = (
+            to.to_string_lossy(),
+            staged.to_string_lossy(),
+            backup.to_string_lossy(),
+        );
+        let swapped = match self.filesystem.rename(&to_str, &backup_str).await {
+            Ok(()) => match self.filesystem.rename(&staged_str, &to_str).await {
+                Ok(()) => Ok(()),
+                Err(e) => {
+                    let _ = self.filesystem.rename(&backup_str, &to_str).await;
+                    Err(e)
+                }
+            },
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = swapped {
+            // Put the pasted entry back where it came from
+            let _ = match mode {
+                ClipboardMode::Copy => self.remove(&staged).await,
+                ClipboardMode::Cut => self.transfer(&staged, from, mode).await,
+            };
+            return Err(e);
+        }
+
+        operations.push(Self::transfer_operation(from, to.to_path_buf(), mode));
+        self.remove(&backup).await
+    }
+
+    /// Copy or move `from` to `to`.
+    async fn transfer(&self, from: &Path, to: &Path, mode: ClipboardMode) -> Result<()> {
+        let (from, to) = (from.to_string_lossy(), to.to_string_lossy());
+
+        match mode {
+            ClipboardMode::Copy => self.filesystem.copy(&from, &to).await,
+            ClipboardMode::Cut => self.filesystem.move_to(&from, &to).await,
+        }
+    }
+
+    /// Returns the operation recording that `from` was copied or moved to `to`.
+    fn transfer_operation(from: &Path, to: PathBuf, mode: ClipboardMode) -> Operation<F> {
+        let from = from.to_path_buf();
+
+        match mode {
+            ClipboardMode::Copy => Operation::Copy { from, to },
+            ClipboardMode::Cut => Operation::Move { from, to },
+        }
+    }
+
+    /// Remove the file or directory at `path`.
+    async fn remove(&self, path: &Path) -> Result<()> {
+        let path = path.to_string_lossy();
+
+        if self.filesystem.is_dir(&path).await? {
+            self.filesystem.delete_dir_all(&path).await
+        } else {
+            self.filesystem.delete(&path).await
+        }
+    }
+
+    /// Returns a vacant hidden name next to `path`, like `.name.tag` or `.name.tag.2`.
+    async fn vacant_sibling(&self, path: &Path, tag: &str) -> Result<PathBuf> {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "Entry has no file name"))?;
+
+        for n in 1.. {
+            let candidate = if n == 1 {
+                path.with_file_name(format!(".{}.{}", name, tag))
+            } else {
+                path.with_file_name(format!(".{}.{}.{}", name, tag, n))
+            };
+            if !self.filesystem.exists(&candidate.to_string_lossy()).await? {
+                return Ok(candidate);
+            }
+        }
+
+        unreachable!("there is always a vacant name")
+    }
+
+    /// Returns the first vacant `name (n).ext` variant of `path`.
+    async fn numbered_destination(&self, path: &Path) -> Result<PathBuf> {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "Entry has no file name"))?;
+        let is_dir = self.filesystem.is_dir(&path.to_string_lossy()).await?;
+
+        for n in 1.. {
+            let candidate = path.with_file_name(numbered_name(&name, is_dir, n));
+            if !self.filesystem.exists(&candidate.to_string_lossy()).await? {
+                return Ok(candidate);
+            }
+        }
+
+        unreachable!("there is always a vacant name")
+    }
+
+    /// Returns the current location, to remember it in the history.
+    fn location(&self) -> HistoryEntry {
+        HistoryEntry {
+            cwd: self.cwd.clone(),
+            selected: self.files.get(self.selected).map(|file| file.path.clone()),
+            scroll_offset: self.scroll_offset,
+        }
+    }
+
+    /// Read the completions of the path typed in the "go to path" prompt from its parent
+    /// directory. A directory which cannot be read offers no completion.
+    async fn refresh_path_completions(&mut self) {
+        let Some(entry) = &self.path_entry else {
+            return;
+        };
+
+        let (dir, typed_dir, prefix) = path_entry::completion_parts(&entry.input, &self.cwd);
+        let show_hidden = self.show_hidden || prefix.starts_with('.');
+
+        let mut completions: Vec<String> = self
+            .filesystem
+            .read_dir(&dir.to_string_lossy())
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|entry| entry.name.starts_with(prefix) && (show_hidden || !entry.is_hidden))
+            .map(|entry| format!("{}{}", typed_dir, entry.name))
+            .collect();
+        completions.sort();
+
+        if let Some(entry) = &mut self.path_entry {
+            entry.completions = completions;
+            entry.selected_completion = None;
+        }
+    }
+
+    /// Saves the bookmarks to the bookmarks file, if any.
+    async fn save_bookmarks(&self) -> Result<()> {
+        match &self.bookmarks_file {
+            Some(path) => self.bookmarks.save(path).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Record the current location in the history and remember the position in `cwd`,
+    /// before going to another directory.
+    fn leave_cwd(&mut self) {
+        self.history.visit(self.location());
+        self.remember_position();
+    }
+
+    /// Remember the selected entry and scroll offset of `cwd`, to restore them when coming back.
+    fn remember_position(&mut self) {
+        if let Some(file) = self.files.get(self.selected) {
+            self.positions.insert(
+                self.cwd.clone(),
+                Position {
+                    selected: file.name.clone(),
+                    scroll_offset: self.scroll_offset,
+                },
+            );
+        }
+    }
+
+    /// Restore the position the user left `cwd` at, or start at the top if it is new. Returns
+    /// whether a position was restored.
+    fn restore_position(&mut self) -> bool {
+        self.selected = 0;
+        self.scroll_offset = 0;
+
+        if let Some(position) = self.positions.get(&self.cwd) {
+            if self.select_file(&position.selected) {
+                self.scroll_offset = position.scroll_offset;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Go to a location from the history, leaving everything as it was if it can not be listed.
+    async fn restore_location(&mut self, entry: &HistoryEntry) -> Result<()> {
+        self.remember_position();
+        let previous = std::mem::replace(&mut self.cwd, entry.cwd.clone());
+        if let Err(e) = self.get_and_set_files().await {
+            self.cwd = previous;
+            return Err(e);
+        }
+
+        self.selected = 0;
+        if let Some(selected) = &entry.selected {
+            self.select_path(selected);
+        }
+        self.scroll_offset = entry.scroll_offset;
+        self.frecency.visit(self.cwd.clone());
+
+        Ok(())
+    }
+
+    /// Returns the selected paths in a stable order, for batch operations.
+    fn sorted_selection(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self.selected_paths.iter().cloned().collect();
+        paths.sort();
+        paths
+    }
+
+    /// Record the `operations` of a batch once it is done, refresh the listing while keeping
+    /// the cursor on the same entry if it still exists, and forget the selected paths that no
+    /// longer exist.
+    async fn finish_batch(&mut self, operations: Vec<Operation<F>>) -> Result<()> {
+        for path in operations.iter().flat_map(Operation::paths) {
+            self.ignore_rules.invalidate(path);
+        }
+        self.journal.record(operations);
+
+        let current = self.files.get(self.selected).map(|file| file.path.clone());
+
+        let mut stale = Vec::new();
+        for path in &self.selected_paths {
+            if !self.filesystem.exists(&path.to_string_lossy()).await? {
+                stale.push(path.clone());
+            }
+        }
+        for path in stale {
+            self.selected_paths.remove(&path);
+        }
+
+        self.get_and_set_files().await?;
+        if !current.is_some_and(|path| self.select_path(&path)) {
+            self.clamp_selected();
+        }
+
+        Ok(())
+    }
+
+    /// Returns the paths of the entries that pass the filter, in display order, except
+    /// for the parent directory (`../`) or searched directory (`./`), which can never be
+    /// selected.
+    fn visible_paths(&self) -> Vec<PathBuf> {
+        self.filtered_files
+            .iter()
+            .map(|file| &self.files[file.idx()])
+            .filter(|file| !file.is_navigation_entry())
+            .map(|file| file.path.clone())
+            .collect()
+    }
+
+    /// Returns the path of the selected entry, unless it is the parent directory (`../`) or
+    /// searched directory (`./`), which file operations must never act on.
+    fn operable_current(&self) -> Option<PathBuf> {
+        self.current_entry()
+            .filter(|current| !current.is_navigation_entry())
+            .map(|current| current.path.clone())
     }
 
-    /// Returns the theme of the file explorer.
-    ///
-    /// # Examples
-    ///
-    /// ```no_run
-    /// use ratatui_async_explorer::{FileExplorer, Theme};
-    ///
-    /// # tokio_test::block_on(async {
-    /// let file_explorer = FileExplorer::new().await.unwrap();
-    ///
-    /// assert_eq!(file_explorer.theme(), &Theme::default());
-    /// # })
-    /// ```
-    #[inline]
-    #[must_use]
-    pub const fn theme(&self) -> &Theme<F> {
-        &self.theme
+    /// Returns whether `file` is an archive the filesystem can list like a directory, see
+    /// [`LocalFileSystem`].
+    async fn is_browsable_archive(&self, file: &File) -> bool {
+        #[cfg(feature = "archive")]
+        if crate::filesystem::ArchiveFormat::from_path(&file.path).is_some() {
+            return self
+                .filesystem
+                .read_dir(&file.path.to_string_lossy())
+                .await
+                .is_ok();
+        }
+
+        let _ = file;
+        false
     }
 
-    /// Returns the current scroll offset of the file explorer.
-    ///
-    /// This represents the index of the first visible item in the list.
-    #[inline]
-    #[must_use]
-    pub const fn scroll_offset(&self) -> usize {
-        self.scroll_offset
+    /// Returns the path `from` would have once copied or moved into `dir`.
+    fn destination(from: &Path, dir: &Path) -> Result<PathBuf> {
+        from.file_name()
+            .map(|name| dir.join(name))
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "Entry has no file name"))
     }
 
-    /// Sets the scroll offset for the file explorer.
-    ///
-    /// This is used internally by the widget renderer to maintain scroll state.
-    #[inline]
-    pub(crate) fn set_scroll_offset(&mut self, offset: usize) {
-        self.scroll_offset = offset;
+    /// Fails with [`ErrorKind::InvalidInput`] unless `name` is a plain file name, so that a
+    /// rename cannot move the entry out of its directory.
+    fn validate_name(name: &str) -> Result<()> {
+        let mut components = Path::new(name).components();
+        let plain = matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(_)), None)
+        );
+
+        if !plain || name.contains('/') {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid file name: {name:?}"),
+            ));
+        }
+
+        Ok(())
     }
 
-    /// Sets the paths of files that should be displayed as selected.
-    ///
-    /// This allows external state management of file selection, useful for
-    /// multi-file operations like batch copying or deleting.
-    ///
-    /// # Examples
-    ///
-    /// ```no_run
-    /// use std::collections::HashSet;
-    /// use std::path::PathBuf;
-    /// use ratatui_async_explorer::FileExplorer;
-    ///
-    /// # async fn example() -> std::io::Result<()> {
-    /// let mut file_explorer = FileExplorer::new().await?;
-    ///
-    /// let mut selected = HashSet::new();
-    /// selected.insert(PathBuf::from("/home/user/file1.txt"));
-    /// selected.insert(PathBuf::from("/home/user/file2.txt"));
-    ///
-    /// file_explorer.set_selected_paths(selected);
-    /// # Ok(())
-    /// # }
-    /// ```
-    #[inline]
-    pub fn set_selected_paths(&mut self, paths: HashSet<PathBuf>) {
-        self.selected_paths = paths;
+    /// Returns the first ancestor of `path` below `cwd` (or `path` itself) that does not
+    /// exist yet, that is the top-level entry that creating `path` would add to `cwd`.
+    async fn first_missing(&self, path: &Path) -> Result<PathBuf> {
+        let mut current = self.cwd.clone();
+
+        for component in path.strip_prefix(&self.cwd).unwrap_or(path).components() {
+            current.push(component);
+            if !self.filesystem.exists(&current.to_string_lossy()).await? {
+                break;
+            }
+        }
+
+        Ok(current)
     }
 
-    /// Returns a reference to the set of selected file paths.
-    ///
-    /// # Examples
-    ///
-    /// ```no_run
-    /// use ratatui_async_explorer::FileExplorer;
-    ///
-    /// # async fn example() -> std::io::Result<()> {
-    /// let file_explorer = FileExplorer::new().await?;
-    /// let selected = file_explorer.selected_paths();
-    /// println!("Number of selected files: {}", selected.len());
-    /// # Ok(())
-    /// # }
-    /// ```
-    #[inline]
-    #[must_use]
-    pub const fn selected_paths(&self) -> &HashSet<PathBuf> {
-        &self.selected_paths
+    /// Returns the permissions of the entries at `paths` which can be read. They are taken
+    /// from the loaded entries when possible, and every other parent is listed only once.
+    async fn permissions_of(&self, paths: &[PathBuf]) -> HashMap<PathBuf, FilePermissions> {
+        let mut permissions: HashMap<PathBuf, FilePermissions> = self
+            .files
+            .iter()
+            .filter_map(|file| Some((file.path.clone(), file.permissions?)))
+            .collect();
+        let mut listed = HashSet::new();
+
+        for path in paths {
+            if permissions.contains_key(path) {
+                continue;
+            }
+            let Some(parent) = self.filesystem.parent(&path.to_string_lossy()) else {
+                continue;
+            };
+            if !listed.insert(parent.clone()) {
+                continue;
+            }
+
+            if let Ok(entries) = self.filesystem.read_dir(&parent).await {
+                permissions.extend(
+                    entries
+                        .into_iter()
+                        .filter_map(|entry| Some((PathBuf::from(entry.path), entry.permissions?))),
+                );
+            }
+        }
+
+        permissions
     }
 
-    /// Checks if a specific file is marked as selected.
-    ///
-    /// # Examples
-    ///
-    /// ```no_run
-    /// use ratatui_async_explorer::FileExplorer;
-    ///
-    /// # async fn example() -> std::io::Result<()> {
-    /// let file_explorer = FileExplorer::new().await?;
-    /// let current_file = file_explorer.current();
-    ///
-    /// if file_explorer.is_file_selected(current_file) {
-    ///     println!("Current file is selected");
-    /// }
-    /// # Ok(())
-    /// # }
-    /// ```
-    #[inline]
-    #[must_use]
-    pub fn is_file_selected(&self, file: &File) -> bool {
-        self.selected_paths.contains(&file.path)
+    /// Fails with [`ErrorKind::AlreadyExists`] if something already exists at `path`.
+    async fn ensure_vacant(&self, path: &Path) -> Result<()> {
+        if self.filesystem.exists(&path.to_string_lossy()).await? {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("{} already exists", path.display()),
+            ));
+        }
+
+        Ok(())
     }
 
     /// Compute filtered files with their original indices, returning owned File objects.
     /// This method clones the files to cache them in the filtered_files field.
     fn compute_filtered_files(&self) -> Vec<File> {
-        match &self.filter {
-            Some(predicate) => self
-                .files
+        let files = if self.filters.is_empty() {
+            self.files.clone()
+        } else {
+            self.files
                 .iter()
-                .filter_map(|file| predicate(file))
-                .collect(),
-            None => self.files.clone(),
-        }
+                .filter_map(|file| self.filters.apply(file))
+                .collect()
+        };
+
+        let Some(query) = &self.fuzzy_filter else {
+            return files;
+        };
+
+        // Rank the matches by score, the stable sort keeping the sort order among equals
+        let mut matches: Vec<(i64, File)> = files
+            .into_iter()
+            .filter_map(|mut file| {
+                let (score, positions) = fuzzy_match(query, &file.name)?;
+                file.match_positions = positions;
+                Some((score, file))
+            })
+            .collect();
+        matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+        matches.into_iter().map(|(_, file)| file).collect()
     }
 
     /// Convert the current selected index (which is stored as original index) to filtered index.
@@ -1012,32 +3532,61 @@ impl<F: FileSystem> FileExplorer<F> {
             .read_dir(&self.cwd.to_string_lossy())
             .await?;
 
+        let no_rules = IgnoreRules::default();
+        let ignore_rules = if self.show_ignored {
+            &no_rules
+        } else {
+            self.ignore_rules
+                .get_or_load(&*self.filesystem, &self.cwd)
+                .await
+        };
+
+        // Listing a directory leaves the results of a search
+        if let Some(search) = self.search.take() {
+            search.cancel();
+        }
+        // and reloads the preview, since the entries may have changed
+        if let Some(preview) = self.preview.take() {
+            preview.cancel();
+        }
+
         // Convert FileEntry to File
         let mut files: Vec<File> = entries
             .into_iter()
             .filter(|entry| self.show_hidden || !entry.is_hidden)
-            .map(|entry| File {
-                idx: 0,
-                name: entry.name,
-                path: PathBuf::from(entry.path),
-                is_dir: entry.is_dir,
-                is_file: entry.is_file,
-                is_hidden: entry.is_hidden,
-                size: entry.size,
-                modified: entry.modified,
-                permissions: entry.permissions,
-                symlink_target: entry.symlink_target,
-            })
+            .filter(|entry| !ignore_rules.is_ignored(Path::new(&entry.path), entry.is_dir))
+            .map(File::from_entry)
             .collect();
 
+        if self.git_status {
+            if !self.git_prefixes.contains_key(&self.cwd) {
+                // git can only run in directories on the local disk
+                let prefix = if self.filesystem.is_local(&self.cwd.to_string_lossy()).await {
+                    GitStatuses::prefix(&self.cwd).await
+                } else {
+                    None
+                };
+                self.git_prefixes.insert(self.cwd.clone(), prefix);
+            }
+            if let Some(prefix) = &self.git_prefixes[&self.cwd] {
+                if let Some(statuses) = GitStatuses::load(&self.cwd, prefix).await {
+                    for file in &mut files {
+                        file.git_status = statuses.get(&file.path);
+                    }
+                }
+            }
+        }
+
+        self.sort.sort(&mut files);
+
         // Add parent directory if it exists
-        if let Some(parent) = self.cwd.parent() {
+        if let Some(parent) = self.filesystem.parent(&self.cwd.to_string_lossy()) {
             files.insert(
                 0,
                 File {
                     idx: 0,
                     name: "../".to_owned(),
-                    path: parent.to_path_buf(),
+                    path: PathBuf::from(parent),
                     is_dir: true,
                     is_file: false,
                     is_hidden: false,
@@ -1045,6 +3594,8 @@ impl<F: FileSystem> FileExplorer<F> {
                     modified: None,
                     permissions: None,
                     symlink_target: None,
+                    match_positions: Vec::new(),
+                    git_status: None,
                 },
             );
         }
@@ -1081,7 +3632,7 @@ impl FileExplorer<LocalFileSystem> {
     /// ```
     pub async fn new() -> Result<Self> {
         let cwd = std::env::current_dir()?;
-        let filesystem = Arc::new(LocalFileSystem);
+        let filesystem = Arc::new(LocalFileSystem::new());
 
         Self::with_fs(filesystem, cwd.to_string_lossy().to_string()).await
     }
@@ -1094,7 +3645,7 @@ impl FileExplorer<LocalFileSystem> {
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```
     /// use ratatui_async_explorer::{FileExplorer, Theme};
     ///
     /// # async fn example() -> std::io::Result<()> {
@@ -1123,6 +3674,8 @@ pub struct File {
     modified: Option<std::time::SystemTime>,
     permissions: Option<crate::filesystem::FilePermissions>,
     symlink_target: Option<String>,
+    match_positions: Vec<usize>,
+    git_status: Option<GitStatus>,
 }
 
 impl File {
@@ -1145,13 +3698,15 @@ impl File {
     ///     └── resume.pdf
     /// ```
     /// You can get the name of the selected file like this:
-    /// ```no_run
+    /// ```
     /// use ratatui_async_explorer::FileExplorer;
     ///
     /// # tokio_test::block_on(async {
-    /// let file_explorer = FileExplorer::new().await.unwrap();
-    ///
-    /// /* user select `password.png` */
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// # file_explorer.select_file("passport.png");
     ///
     /// let file = file_explorer.current();
     /// assert_eq!(file.name(), "passport.png");
@@ -1175,13 +3730,15 @@ impl File {
     ///     └── resume.pdf
     /// ```
     /// You can get the path of the selected file like this:
-    /// ```no_run
+    /// ```
     /// use ratatui_async_explorer::FileExplorer;
     ///
     /// # tokio_test::block_on(async {
-    /// let file_explorer = FileExplorer::new().await.unwrap();
-    ///
-    /// /* user select `password.png` */
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// # file_explorer.select_file("passport.png");
     ///
     /// let file = file_explorer.current();
     /// assert_eq!(file.path().display().to_string(), "/Documents/passport.png");
@@ -1205,18 +3762,22 @@ impl File {
     ///     └── resume.pdf
     /// ```
     /// You can know if the selected file is a directory like this:
-    /// ```no_run
+    /// ```
     /// use ratatui_async_explorer::FileExplorer;
     ///
     /// # tokio_test::block_on(async {
-    /// let file_explorer = FileExplorer::new().await.unwrap();
-    ///
-    /// /* user select `password.png` */
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// # file_explorer.select_file("passport.png");
     ///
     /// let file = file_explorer.current();
     /// assert_eq!(file.is_dir(), false);
     ///
     /// /* user select `Documents` */
+    /// # file_explorer.set_cwd("/").await.unwrap();
+    /// # file_explorer.select_file("Documents/");
     ///
     /// let file = file_explorer.current();
     /// assert_eq!(file.is_dir(), true);
@@ -1247,18 +3808,22 @@ impl File {
     ///     └── resume.pdf
     /// ```
     /// You can know if the selected file is a regular file like this:
-    /// ```no_run
+    /// ```
     /// use ratatui_async_explorer::FileExplorer;
     ///
     /// # tokio_test::block_on(async {
-    /// let file_explorer = FileExplorer::new().await.unwrap();
-    ///
-    /// /* user select `password.png` */
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// # file_explorer.select_file("passport.png");
     ///
     /// let file = file_explorer.current();
     /// assert_eq!(file.is_file(), true);
     ///
     /// /* user select `Documents` */
+    /// # file_explorer.set_cwd("/").await.unwrap();
+    /// # file_explorer.select_file("Documents/");
     ///
     /// let file = file_explorer.current();
     /// assert_eq!(file.is_file(), false);
@@ -1282,18 +3847,23 @@ impl File {
     ///     └── resume.pdf
     /// ```
     /// You can know if the selected file or directory is hidden like this:
-    /// ```no_run
+    /// ```
     /// use ratatui_async_explorer::FileExplorer;
     ///
     /// # tokio_test::block_on(async {
-    /// let file_explorer = FileExplorer::new().await.unwrap();
-    ///
-    /// /* user select `password.png` */
+    /// # use std::sync::Arc;
+    /// # use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+    /// # let fs = InMemoryFileSystem::from_tree("/\n├── .git/\n└── Documents/\n    ├── passport.png\n    └── resume.pdf");
+    /// # let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/Documents".to_string()).await.unwrap();
+    /// # file_explorer.select_file("passport.png");
     ///
     /// let file = file_explorer.current();
     /// assert_eq!(file.is_hidden(), false);
     ///
     /// /* user select `.git` */
+    /// # file_explorer.set_cwd("/").await.unwrap();
+    /// # file_explorer.set_show_hidden(true).await.unwrap();
+    /// # file_explorer.select_file(".git/");
     ///
     /// let file = file_explorer.current();
     /// assert_eq!(file.is_hidden(), true);
@@ -1328,6 +3898,50 @@ impl File {
         self.permissions
     }
 
+    /// Returns the indices of the characters of the name matched by the
+    /// [fuzzy filter](FileExplorer::set_fuzzy_filter), in increasing order.
+    ///
+    /// Returns an empty slice when no fuzzy filter is set.
+    #[inline]
+    #[must_use]
+    pub fn match_positions(&self) -> &[usize] {
+        &self.match_positions
+    }
+
+    /// Returns the status of the file or directory in its git repository, if it has one and
+    /// the git status is [enabled](FileExplorer::set_git_status). A directory has the most
+    /// important status of its children.
+    #[inline]
+    #[must_use]
+    pub const fn git_status(&self) -> Option<GitStatus> {
+        self.git_status
+    }
+
+    /// Converts an entry read from the [`FileSystem`], leaving its index to be set.
+    pub(crate) fn from_entry(entry: crate::FileEntry) -> Self {
+        Self {
+            idx: 0,
+            name: entry.name,
+            path: PathBuf::from(entry.path),
+            is_dir: entry.is_dir,
+            is_file: entry.is_file,
+            is_hidden: entry.is_hidden,
+            size: entry.size,
+            modified: entry.modified,
+            permissions: entry.permissions,
+            symlink_target: entry.symlink_target,
+            match_positions: Vec::new(),
+            git_status: None,
+        }
+    }
+
+    /// Returns `true` if this is the synthetic `../` entry pointing to the parent directory,
+    /// or the synthetic `./` entry heading the results of a search.
+    #[inline]
+    pub(crate) fn is_navigation_entry(&self) -> bool {
+        self.name == "../" || self.name == "./"
+    }
+
     #[cfg(test)]
     pub(crate) fn new_for_test(
         name: &str,
@@ -1346,6 +3960,384 @@ impl File {
             modified: None,
             permissions,
             symlink_target: symlink_target.map(String::from),
+            match_positions: Vec::new(),
+            git_status: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::filesystem::InMemoryFileSystem;
+
+    use super::*;
+
+    async fn explorer(fs: InMemoryFileSystem) -> FileExplorer<InMemoryFileSystem> {
+        FileExplorer::with_fs(Arc::new(fs), "/work".to_string())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn rename_rejects_paths() {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/work/notes.txt", "")
+            .with_dir("/work/docs");
+        let mut file_explorer = explorer(fs).await;
+        file_explorer.select_file("notes.txt");
+
+        for name in [
+            "",
+            ".",
+            "..",
+            "../escaped.txt",
+            "/escaped.txt",
+            "docs/notes.txt",
+            "x/",
+        ] {
+            let error = file_explorer
+                .handle(Input::Rename(name.to_string()))
+                .await
+                .unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidInput, "{name:?}");
+        }
+        assert_eq!(file_explorer.current().name(), "notes.txt");
+
+        file_explorer
+            .handle(Input::Rename("todo.txt".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(file_explorer.current().name(), "todo.txt");
+    }
+
+    #[tokio::test]
+    async fn target_exists() {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/work/notes.txt", "notes")
+            .with_file("/work/todo.txt", "todo")
+            .with_file("/work/docs/notes.txt", "old");
+        let mut file_explorer = explorer(fs).await;
+        file_explorer.select_file("notes.txt");
+
+        for input in [
+            Input::Rename("todo.txt".to_string()),
+            Input::CopyTo(PathBuf::from("/work/docs")),
+            Input::MoveTo(PathBuf::from("/work/docs")),
+        ] {
+            let error = file_explorer.handle(input).await.unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::AlreadyExists);
+        }
+
+        let fs = file_explorer.filesystem();
+        assert_eq!(fs.contents("/work/notes.txt").unwrap(), b"notes");
+        assert_eq!(fs.contents("/work/todo.txt").unwrap(), b"todo");
+        assert_eq!(fs.contents("/work/docs/notes.txt").unwrap(), b"old");
+    }
+
+    #[tokio::test]
+    async fn create_existing() {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/work/notes.txt", "notes")
+            .with_file("/work/src/main.rs", "fn main() {}");
+        let mut file_explorer = explorer(fs).await;
+
+        for name in ["notes.txt", "src", "src/main.rs"] {
+            let error = file_explorer.create_dir(name).await.unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::AlreadyExists, "{name}");
+            let error = file_explorer.create_file(name).await.unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::AlreadyExists, "{name}");
+        }
+
+        let fs = file_explorer.filesystem();
+        assert_eq!(fs.contents("/work/notes.txt").unwrap(), b"notes");
+        assert_eq!(fs.contents("/work/src/main.rs").unwrap(), b"fn main() {}");
+        assert!(!file_explorer.undo().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn create_nested() {
+        let fs = InMemoryFileSystem::new().with_file("/work/src/main.rs", "");
+        let mut file_explorer = explorer(fs).await;
+
+        file_explorer.create_dir("docs/guide/intro").await.unwrap();
+        assert_eq!(file_explorer.current().name(), "docs/");
+        assert!(file_explorer
+            .filesystem()
+            .is_dir("/work/docs/guide/intro")
+            .await
+            .unwrap());
+
+        file_explorer.create_file("src/bin/tool.rs").await.unwrap();
+        assert_eq!(file_explorer.current().name(), "src/");
+        assert!(file_explorer
+            .filesystem()
+            .exists("/work/src/bin/tool.rs")
+            .await
+            .unwrap());
+
+        // Undoing removes what was created, and only that
+        assert!(file_explorer.undo().await.unwrap());
+        assert!(!file_explorer
+            .filesystem()
+            .exists("/work/src/bin")
+            .await
+            .unwrap());
+        assert!(file_explorer
+            .filesystem()
+            .exists("/work/src/main.rs")
+            .await
+            .unwrap());
+        assert!(file_explorer.undo().await.unwrap());
+        assert!(!file_explorer
+            .filesystem()
+            .exists("/work/docs")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn delete_prompt_is_abandoned() {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/work/notes.txt", "")
+            .with_file("/work/todo.txt", "");
+        let mut file_explorer = explorer(fs).await;
+
+        let mut inputs = vec![Input::Up, Input::ToggleShowHidden, Input::Cancel];
+        #[cfg(feature = "crossterm")]
+        inputs.push(Input::from(&ratatui::crossterm::event::Event::Key(
+            ratatui::crossterm::event::KeyEvent::from(ratatui::crossterm::event::KeyCode::Char(
+                'j',
+            )),
+        )));
+
+        for input in inputs {
+            file_explorer.select_file("notes.txt");
+            file_explorer.handle(Input::Delete).await.unwrap();
+            assert!(file_explorer.pending_delete().is_some());
+
+            file_explorer.handle(input.clone()).await.unwrap();
+            assert!(file_explorer.pending_delete().is_none(), "{input:?}");
+
+            file_explorer.select_file("notes.txt");
+            file_explorer.handle(Input::Confirm).await.unwrap();
+            assert!(file_explorer
+                .filesystem()
+                .exists("/work/notes.txt")
+                .await
+                .unwrap());
+        }
+    }
+
+    #[cfg(feature = "crossterm")]
+    #[tokio::test]
+    async fn dirs_first_key() {
+        use ratatui::crossterm::event::{Event, KeyCode, KeyEvent};
+
+        let fs = InMemoryFileSystem::new()
+            .with_file("/work/a.txt", "")
+            .with_dir("/work/b");
+        let mut file_explorer = explorer(fs).await;
+        let names = |file_explorer: &FileExplorer<InMemoryFileSystem>| {
+            file_explorer
+                .files()
+                .iter()
+                .map(|file| file.name().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&file_explorer), ["../", "b/", "a.txt"]);
+
+        let key = Event::Key(KeyEvent::from(KeyCode::Char('D')));
+        file_explorer.handle(&key).await.unwrap();
+        assert!(!file_explorer.dirs_first());
+        assert_eq!(names(&file_explorer), ["../", "a.txt", "b/"]);
+    }
+
+    #[cfg(feature = "crossterm")]
+    #[tokio::test]
+    async fn path_entry_keys() {
+        use ratatui::crossterm::event::{Event, KeyCode, KeyEvent};
+
+        let fs = InMemoryFileSystem::new()
+            .with_file("/work/notes.txt", "")
+            .with_file("/work/docs/guide.md", "")
+            .with_dir("/work/lib");
+        let mut file_explorer = explorer(fs).await;
+        let key = |code| Event::Key(KeyEvent::from(code));
+
+        file_explorer
+            .handle_event(&key(KeyCode::Char('g')))
+            .await
+            .unwrap();
+        assert_eq!(file_explorer.path_entry().unwrap().input(), "/work/");
+
+        // The keys bound to actions type into the prompt instead
+        for c in "lib/dy".chars() {
+            file_explorer
+                .handle_event(&key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        for _ in 0..2 {
+            file_explorer
+                .handle_event(&key(KeyCode::Backspace))
+                .await
+                .unwrap();
+        }
+        assert_eq!(file_explorer.path_entry().unwrap().input(), "/work/lib/");
+        assert_eq!(file_explorer.cwd(), Path::new("/work"));
+        assert!(file_explorer.pending_delete.is_none());
+
+        // Enter goes to the typed path
+        file_explorer
+            .handle_event(&key(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert!(file_explorer.path_entry().is_none());
+        assert_eq!(file_explorer.cwd(), Path::new("/work/lib"));
+
+        // And so does Right, selecting a file in its directory
+        file_explorer
+            .handle_event(&key(KeyCode::Char('g')))
+            .await
+            .unwrap();
+        for _ in "lib/".chars() {
+            file_explorer
+                .handle_event(&key(KeyCode::Backspace))
+                .await
+                .unwrap();
+        }
+        for c in "docs/guide.md".chars() {
+            file_explorer
+                .handle_event(&key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        file_explorer
+            .handle_event(&key(KeyCode::Right))
+            .await
+            .unwrap();
+        assert_eq!(file_explorer.cwd(), Path::new("/work/docs"));
+        assert_eq!(file_explorer.current().name(), "guide.md");
+
+        // Once the prompt is closed, the keys trigger their bindings again
+        file_explorer
+            .handle_event(&key(KeyCode::Char('k')))
+            .await
+            .unwrap();
+        assert_eq!(file_explorer.current().name(), "../");
+    }
+
+    async fn mode(fs: &InMemoryFileSystem, path: &str) -> Option<u32> {
+        let parent = fs.parent(path).unwrap();
+
+        fs.read_dir(&parent)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|entry| entry.path == path)
+            .and_then(|entry| entry.permissions)
+            .map(|permissions| permissions.mode())
+    }
+
+    #[tokio::test]
+    async fn chmod_selected_is_undone() {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/work/a.txt", "")
+            .with_permissions("/work/a.txt", FilePermissions::from_mode(0o600))
+            .with_file("/work/b.txt", "")
+            .with_file("/work/docs/c.txt", "")
+            .with_permissions("/work/docs/c.txt", FilePermissions::from_mode(0o640));
+        let mut file_explorer = explorer(fs).await;
+
+        for name in ["a.txt", "b.txt"] {
+            file_explorer.select_file(name);
+            file_explorer.handle(Input::ToggleSelection).await.unwrap();
+        }
+        file_explorer.set_cwd("/work/docs").await.unwrap();
+        file_explorer.select_file("c.txt");
+        file_explorer.handle(Input::ToggleSelection).await.unwrap();
+        assert_eq!(file_explorer.selected_paths().len(), 3);
+
+        let results = file_explorer
+            .chmod_selected(FilePermissions::from_mode(0o444))
+            .await
+            .unwrap();
+        assert!(results.iter().all(|(_, result)| result.is_ok()));
+
+        assert!(file_explorer.undo().await.unwrap());
+        let fs = file_explorer.filesystem();
+        assert_eq!(mode(fs, "/work/a.txt").await, Some(0o600));
+        assert_eq!(mode(fs, "/work/b.txt").await, Some(0o644));
+        assert_eq!(mode(fs, "/work/docs/c.txt").await, Some(0o640));
+    }
+
+    #[tokio::test]
+    async fn overwrite() {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/work/notes.txt", "new")
+            .with_file("/work/docs/notes.txt", "old")
+            .with_file("/work/src/main.rs", "fn main() {}")
+            .with_file("/work/docs/src/lib.rs", "");
+        let mut file_explorer = explorer(fs).await;
+        file_explorer.set_conflict_policy(ConflictPolicy::Overwrite);
+
+        for (name, input) in [("notes.txt", Input::Yank), ("src/", Input::Cut)] {
+            file_explorer.set_cwd("/work").await.unwrap();
+            file_explorer.select_file(name);
+            file_explorer.handle(input).await.unwrap();
+            file_explorer.set_cwd("/work/docs").await.unwrap();
+            file_explorer.handle(Input::Paste).await.unwrap();
         }
+
+        let fs = file_explorer.filesystem();
+        assert_eq!(fs.contents("/work/docs/notes.txt").unwrap(), b"new");
+        assert!(fs.exists("/work/docs/src/main.rs").await.unwrap());
+        assert!(!fs.exists("/work/docs/src/lib.rs").await.unwrap());
+        assert!(!fs.exists("/work/src").await.unwrap());
+        let names: Vec<_> = file_explorer
+            .files()
+            .iter()
+            .map(|file| file.name())
+            .collect();
+        assert_eq!(names.len(), 3, "{names:?}");
+    }
+
+    #[tokio::test]
+    async fn overwrite_keeps_the_target_on_failure() {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/work/notes.txt", "new")
+            .with_file("/work/todo.txt", "new")
+            .with_file("/work/docs/notes.txt", "old")
+            .with_file("/work/docs/todo.txt", "old");
+        let mut file_explorer = explorer(fs).await;
+        file_explorer.set_conflict_policy(ConflictPolicy::Overwrite);
+
+        file_explorer.select_file("notes.txt");
+        file_explorer.handle(Input::ToggleSelection).await.unwrap();
+        file_explorer.select_file("todo.txt");
+        file_explorer.handle(Input::ToggleSelection).await.unwrap();
+        file_explorer.handle(Input::Yank).await.unwrap();
+
+        // The copies fail, as the sources are gone
+        let fs = file_explorer.filesystem();
+        fs.delete("/work/notes.txt").await.unwrap();
+        fs.delete("/work/todo.txt").await.unwrap();
+        file_explorer.set_cwd("/work/docs").await.unwrap();
+        let error = file_explorer.handle(Input::Paste).await.unwrap_err();
+
+        // Every failure is reported
+        let message = error.to_string();
+        assert!(message.contains("/work/notes.txt"), "{message}");
+        assert!(message.contains("/work/todo.txt"), "{message}");
+
+        let fs = file_explorer.filesystem();
+        assert_eq!(fs.contents("/work/docs/notes.txt").unwrap(), b"old");
+        assert_eq!(fs.contents("/work/docs/todo.txt").unwrap(), b"old");
+        let names: Vec<_> = file_explorer
+            .files()
+            .iter()
+            .map(|file| file.name())
+            .collect();
+        assert_eq!(names, ["../", "notes.txt", "todo.txt"]);
     }
 }
@@ -0,0 +1,751 @@
+//! Read-only filesystem implementation over tar and zip archives.
+
+use super::{FileEntry, FilePermissions, FileSystem};
+use std::collections::BTreeMap;
+use std::io::{Cursor, Error, ErrorKind, Read, Result};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+/// The archive formats understood by [`ArchiveFileSystem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArchiveFormat {
+    /// An uncompressed tarball (`.tar`)
+    Tar,
+    /// A gzip-compressed tarball (`.tar.gz` or `.tgz`)
+    TarGz,
+    /// A zip archive (`.zip`)
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Detect the archive format from the extension of `path`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::filesystem::ArchiveFormat;
+    ///
+    /// assert_eq!(ArchiveFormat::from_path("release-1.0.tar.gz"), Some(ArchiveFormat::TarGz));
+    /// assert_eq!(ArchiveFormat::from_path("notes.txt"), None);
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let name = path.as_ref().file_name()?.to_string_lossy().to_lowercase();
+
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// A read-only filesystem exposing the content of a `.tar`, `.tar.gz` or `.zip` file.
+///
+/// The archive file itself acts as the root directory: an entry `docs/README.md`
+/// of `/tmp/release.tar.gz` is found at `/tmp/release.tar.gz/docs/README.md`. The
+/// archive index is read once when opening it, every mutation fails with
+/// [`ErrorKind::ReadOnlyFilesystem`]. The content of the last file read is kept, so
+/// reading it chunk by chunk extracts it only once.
+///
+/// With the `archive` feature enabled, [`LocalFileSystem`](super::LocalFileSystem)
+/// uses it to browse archives transparently, so it is rarely needed directly.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use ratatui_async_explorer::{filesystem::ArchiveFileSystem, FileExplorer};
+///
+/// # async fn example() -> std::io::Result<()> {
+/// let archive = ArchiveFileSystem::open("/tmp/release.tar.gz").await?;
+/// let file_explorer =
+///     FileExplorer::with_fs(Arc::new(archive), "/tmp/release.tar.gz".to_string()).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, educe::Educe)]
+#[educe(Debug)]
+pub struct ArchiveFileSystem {
+    root: String,
+    format: ArchiveFormat,
+    entries: BTreeMap<String, ArchiveEntry>,
+    /// The relative path and the content of the last file extracted.
+    #[educe(Debug(ignore))]
+    extracted: Arc<Mutex<Option<Extracted>>>,
+}
+
+/// The relative path of a file of an archive, and its content.
+type Extracted = (String, Arc<Vec<u8>>);
+
+#[derive(Debug, Clone)]
+struct ArchiveEntry {
+    is_dir: bool,
+    size: u64,
+    modified: Option<SystemTime>,
+    permissions: Option<FilePermissions>,
+    symlink_target: Option<String>,
+}
+
+impl ArchiveEntry {
+    fn implicit_dir() -> Self {
+        Self {
+            is_dir: true,
+            size: 0,
+            modified: None,
+            permissions: Some(FilePermissions::from_mode(0o755)),
+            symlink_target: None,
+        }
+    }
+}
+
+impl ArchiveFileSystem {
+    /// Open the archive at `path`, detecting its format from its extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the extension is not recognized, or if the archive cannot
+    /// be read or is corrupted.
+    pub async fn open(path: &str) -> Result<Self> {
+        let format = ArchiveFormat::from_path(path).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("Unsupported archive: {}", path),
+            )
+        })?;
+
+        Self::open_as(path, format).await
+    }
+
+    /// Open the archive at `path` as the given format, whatever its extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive cannot be read or is corrupted.
+    pub async fn open_as(path: &str, format: ArchiveFormat) -> Result<Self> {
+        let bytes = tokio::fs::read(path).await?;
+
+        // Decompressing and indexing are CPU bound, keep them off the async workers
+        let entries = tokio::task::spawn_blocking(move || match format {
+            ArchiveFormat::Tar => index_tar(Cursor::new(bytes)),
+            ArchiveFormat::TarGz => index_tar(flate2::read::GzDecoder::new(Cursor::new(bytes))),
+            ArchiveFormat::Zip => index_zip(bytes),
+        })
+        .await
+        .map_err(Error::other)??;
+
+        Ok(Self {
+            root: path.trim_end_matches('/').to_string(),
+            format,
+            entries,
+            extracted: Arc::default(),
+        })
+    }
+
+    /// Returns the path of the archive file, which is also the root of this filesystem.
+    #[inline]
+    #[must_use]
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+
+    /// Returns the format of the archive.
+    #[inline]
+    #[must_use]
+    pub const fn format(&self) -> ArchiveFormat {
+        self.format
+    }
+
+    /// Returns the path of `path` relative to the root of the archive, `""` being the root.
+    fn relative(&self, path: &str) -> Result<String> {
+        let rest = path
+            .strip_prefix(self.root.as_str())
+            .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+            .ok_or_else(|| not_found(path))?;
+
+        normalize(rest).ok_or_else(|| not_found(path))
+    }
+
+    fn entry(&self, path: &str) -> Result<(String, Option<&ArchiveEntry>)> {
+        let relative = self.relative(path)?;
+        if relative.is_empty() {
+            return Ok((relative, None));
+        }
+
+        match self.entries.get(&relative) {
+            Some(entry) => Ok((relative, Some(entry))),
+            None => Err(not_found(path)),
+        }
+    }
+
+    /// Returns the content of the file `relative`, extracting it unless it was the last one.
+    async fn extract(&self, relative: String) -> Result<Arc<Vec<u8>>> {
+        if let Some((_, content)) = self
+            .extracted()
+            .as_ref()
+            .filter(|(cached, _)| *cached == relative)
+        {
+            return Ok(Arc::clone(content));
+        }
+
+        // Archives are not indexed by offset, so the whole entry is extracted
+        let bytes = tokio::fs::read(&self.root).await?;
+        let format = self.format;
+        let name = relative.clone();
+
+        let content = tokio::task::spawn_blocking(move || match format {
+            ArchiveFormat::Tar => extract_tar(Cursor::new(bytes), &name),
+            ArchiveFormat::TarGz => {
+                extract_tar(flate2::read::GzDecoder::new(Cursor::new(bytes)), &name)
+            }
+            ArchiveFormat::Zip => extract_zip(bytes, &name),
+        })
+        .await
+        .map_err(Error::other)??;
+
+        let content = Arc::new(content);
+        *self.extracted() = Some((relative, Arc::clone(&content)));
+        Ok(content)
+    }
+
+    fn extracted(&self) -> MutexGuard<'_, Option<Extracted>> {
+        self.extracted.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn absolute(&self, relative: &str) -> String {
+        if relative.is_empty() {
+            self.root.clone()
+        } else {
+            format!("{}/{}", self.root, relative)
+        }
+    }
+}
+
+impl FileSystem for ArchiveFileSystem {
+    async fn read_dir(&self, path: &str) -> Result<Vec<FileEntry>> {
+        let (dir, entry) = self.entry(path)?;
+        if entry.is_some_and(|entry| !entry.is_dir) {
+            return Err(Error::new(
+                ErrorKind::NotADirectory,
+                format!("Not a directory: {}", path),
+            ));
+        }
+
+        let prefix = if dir.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", dir)
+        };
+
+        let entries = self
+            .entries
+            .range(prefix.clone()..)
+            .take_while(|(child, _)| child.starts_with(&prefix))
+            .filter(|(child, _)| !child[prefix.len()..].contains('/'))
+            .map(|(child, entry)| {
+                let name = child[prefix.len()..].to_string();
+
+                FileEntry {
+                    is_hidden: name.starts_with('.'),
+                    name: if entry.is_dir {
+                        format!("{}/", name)
+                    } else {
+                        name
+                    },
+                    path: self.absolute(child),
+                    is_dir: entry.is_dir,
+                    is_file: !entry.is_dir && entry.symlink_target.is_none(),
+                    size: (!entry.is_dir).then_some(entry.size),
+                    modified: entry.modified,
+                    permissions: entry.permissions,
+                    is_symlink: entry.symlink_target.is_some(),
+                    symlink_target: entry.symlink_target.clone(),
+                }
+            })
+            .collect();
+
+        Ok(entries)
+    }
+
+    async fn exists(&self, path: &str) -> Result<bool> {
+        Ok(self.entry(path).is_ok())
+    }
+
+    async fn is_dir(&self, path: &str) -> Result<bool> {
+        let (_, entry) = self.entry(path)?;
+        Ok(entry.is_none_or(|entry| entry.is_dir))
+    }
+
+    async fn canonicalize(&self, path: &str) -> Result<String> {
+        let (relative, _) = self.entry(path)?;
+        Ok(self.absolute(&relative))
+    }
+
+    fn parent(&self, path: &str) -> Option<String> {
+        let relative = self.relative(path).ok()?;
+        if relative.is_empty() {
+            return None;
+        }
+
+        Some(match relative.rfind('/') {
+            Some(idx) => self.absolute(&relative[..idx]),
+            None => self.root.clone(),
+        })
+    }
+
+    async fn read_chunk(&self, path: &str, offset: u64, len: usize) -> Result<Vec<u8>> {
+        let (relative, entry) = self.entry(path)?;
+        match entry {
+            Some(entry) if !entry.is_dir => {}
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::IsADirectory,
+                    format!("Is a directory: {}", path),
+                ))
+            }
+        }
+
+        let content = self.extract(relative).await?;
+
+        let start =
+            usize::try_from(offset).map_or(content.len(), |offset| offset.min(content.len()));
+        let end = start.saturating_add(len).min(content.len());
+        Ok(content[start..end].to_vec())
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        Err(read_only(path))
+    }
+
+    async fn delete_dir_all(&self, path: &str) -> Result<()> {
+        Err(read_only(path))
+    }
+
+    async fn rename(&self, from: &str, _to: &str) -> Result<()> {
+        Err(read_only(from))
+    }
+
+    async fn copy(&self, from: &str, _to: &str) -> Result<()> {
+        Err(read_only(from))
+    }
+
+    async fn move_to(&self, from: &str, _to: &str) -> Result<()> {
+        Err(read_only(from))
+    }
+
+    async fn create_dir(&self, path: &str) -> Result<()> {
+        Err(read_only(path))
+    }
+
+    async fn create_dir_all(&self, path: &str) -> Result<()> {
+        Err(read_only(path))
+    }
+
+    async fn create_file(&self, path: &str) -> Result<()> {
+        Err(read_only(path))
+    }
+
+    async fn write_file(&self, path: &str, _contents: &[u8]) -> Result<()> {
+        Err(read_only(path))
+    }
+
+    async fn set_permissions(&self, path: &str, _permissions: FilePermissions) -> Result<()> {
+        Err(read_only(path))
+    }
+}
+
+/// Normalize an entry name to `a/b/c`, returning `None` if it escapes the archive root.
+fn normalize(name: &str) -> Option<String> {
+    let mut components = Vec::new();
+
+    for component in name.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop()?;
+            }
+            component => components.push(component),
+        }
+    }
+
+    Some(components.join("/"))
+}
+
+/// Insert an entry, along with the directories implied by its path.
+fn insert(entries: &mut BTreeMap<String, ArchiveEntry>, name: &str, entry: ArchiveEntry) {
+    let Some(name) = normalize(name).filter(|name| !name.is_empty()) else {
+        return;
+    };
+
+    let mut parent = name.as_str();
+    while let Some(idx) = parent.rfind('/') {
+        parent = &parent[..idx];
+        entries
+            .entry(parent.to_string())
+            .or_insert_with(ArchiveEntry::implicit_dir);
+    }
+
+    entries.insert(name, entry);
+}
+
+fn index_tar<R: Read>(reader: R) -> Result<BTreeMap<String, ArchiveEntry>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = BTreeMap::new();
+
+    for entry in archive.entries()? {
+        let entry = entry?;
+        let header = entry.header();
+        let entry_type = header.entry_type();
+
+        if !(entry_type.is_file() || entry_type.is_dir() || entry_type.is_symlink()) {
+            continue;
+        }
+
+        let name = entry.path()?.to_string_lossy().to_string();
+        let symlink_target = if entry_type.is_symlink() {
+            entry
+                .link_name()?
+                .map(|target| target.to_string_lossy().to_string())
+        } else {
+            None
+        };
+
+        insert(
+            &mut entries,
+            &name,
+            ArchiveEntry {
+                is_dir: entry_type.is_dir(),
+                size: entry.size(),
+                modified: header
+                    .mtime()
+                    .ok()
+                    .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs)),
+                permissions: header.mode().ok().map(FilePermissions::from_mode),
+                symlink_target,
+            },
+        );
+    }
+
+    Ok(entries)
+}
+
+fn index_zip(bytes: Vec<u8>) -> Result<BTreeMap<String, ArchiveEntry>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(zip_error)?;
+    let mut entries = BTreeMap::new();
+
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).map_err(zip_error)?;
+        let name = file.name().to_string();
+
+        let symlink_target = if file.is_symlink() {
+            let mut target = String::new();
+            file.read_to_string(&mut target)?;
+            Some(target)
+        } else {
+            None
+        };
+
+        insert(
+            &mut entries,
+            &name,
+            ArchiveEntry {
+                is_dir: file.is_dir(),
+                size: file.size(),
+                modified: file.last_modified().and_then(zip_time),
+                permissions: file.unix_mode().map(FilePermissions::from_mode),
+                symlink_target,
+            },
+        );
+    }
+
+    Ok(entries)
+}
+
+/// Extract the content of the tar entry whose normalized name is `name`.
+fn extract_tar<R: Read>(reader: R, name: &str) -> Result<Vec<u8>> {
+    let mut archive = tar::Archive::new(reader);
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if normalize(&entry.path()?.to_string_lossy()).as_deref() == Some(name) {
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content)?;
+            return Ok(content);
+        }
+    }
+
+    Err(not_found(name))
+}
+
+/// Extract the content of the zip entry whose normalized name is `name`.
+fn extract_zip(bytes: Vec<u8>, name: &str) -> Result<Vec<u8>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(zip_error)?;
+
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).map_err(zip_error)?;
+        if normalize(file.name()).as_deref() == Some(name) {
+            let mut content = Vec::new();
+            file.read_to_end(&mut content)?;
+            return Ok(content);
+        }
+    }
+
+    Err(not_found(name))
+}
+
+/// Zip timestamps have no time zone, they are interpreted as local time.
+fn zip_time(time: zip::DateTime) -> Option<SystemTime> {
+    use chrono::TimeZone;
+
+    let date = chrono::NaiveDate::from_ymd_opt(
+        time.year().into(),
+        time.month().into(),
+        time.day().into(),
+    )?
+    .and_hms_opt(
+        time.hour().into(),
+        time.minute().into(),
+        time.second().into(),
+    )?;
+
+    chrono::Local
+        .from_local_datetime(&date)
+        .earliest()
+        .map(SystemTime::from)
+}
+
+fn zip_error(e: zip::result::ZipError) -> Error {
+    match e {
+        zip::result::ZipError::Io(e) => e,
+        e => Error::new(ErrorKind::InvalidData, e),
+    }
+}
+
+fn not_found(path: &str) -> Error {
+    Error::new(
+        ErrorKind::NotFound,
+        format!("No such file or directory: {}", path),
+    )
+}
+
+fn read_only(path: &str) -> Error {
+    Error::new(
+        ErrorKind::ReadOnlyFilesystem,
+        format!("Archives are read-only: {}", path),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn names(entries: &[FileEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.name.as_str()).collect()
+    }
+
+    fn tar_bytes() -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+
+        let mut header = tar::Header::new_gnu();
+        header.set_size(5);
+        header.set_mode(0o755);
+        header.set_mtime(1_000);
+        builder
+            .append_data(&mut header, "release/bin/tool", &b"hello"[..])
+            .unwrap();
+
+        let mut header = tar::Header::new_gnu();
+        header.set_size(2);
+        header.set_mode(0o644);
+        builder
+            .append_data(&mut header, "./release/README.md", &b"hi"[..])
+            .unwrap();
+
+        builder.into_inner().unwrap()
+    }
+
+    #[tokio::test]
+    async fn browse_tar_gz() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("release.tar.gz");
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&tar_bytes()).unwrap();
+        std::fs::write(&path, encoder.finish().unwrap()).unwrap();
+
+        let root = path.to_string_lossy().to_string();
+        let fs = ArchiveFileSystem::open(&root).await.unwrap();
+        assert_eq!(fs.format(), ArchiveFormat::TarGz);
+
+        assert_eq!(names(&fs.read_dir(&root).await.unwrap()), ["release/"]);
+
+        let mut release = fs.read_dir(&format!("{}/release", root)).await.unwrap();
+        release.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(names(&release), ["README.md", "bin/"]);
+        assert_eq!(release[0].path, format!("{}/release/README.md", root));
+
+        let bin = fs.read_dir(&format!("{}/release/bin", root)).await.unwrap();
+        assert_eq!(bin[0].size, Some(5));
+        assert_eq!(
+            bin[0].modified,
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_000))
+        );
+        assert_eq!(bin[0].permissions, Some(FilePermissions::from_mode(0o755)));
+
+        assert_eq!(fs.parent(&root), None);
+        assert_eq!(fs.parent(&format!("{}/release", root)), Some(root.clone()));
+        assert!(fs.is_dir(&format!("{}/release/bin", root)).await.unwrap());
+        assert!(!fs.exists(&format!("{}/../outside", root)).await.unwrap());
+
+        let tool = format!("{}/release/bin/tool", root);
+        assert_eq!(fs.read_chunk(&tool, 1, 3).await.unwrap(), b"ell");
+        assert_eq!(fs.read_chunk(&tool, 3, 100).await.unwrap(), b"lo");
+
+        // The following chunks come from the extracted content, not the archive file
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(fs.read_chunk(&tool, 0, 2).await.unwrap(), b"he");
+        assert!(fs.read_chunk(&root, 0, 1).await.is_err());
+
+        let err = fs.delete(&format!("{}/release/README.md", root)).await;
+        assert_eq!(err.unwrap_err().kind(), ErrorKind::ReadOnlyFilesystem);
+    }
+
+    #[tokio::test]
+    async fn browse_zip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bundle.zip");
+
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default().unix_permissions(0o600);
+        writer.add_directory("empty/", options).unwrap();
+        writer.start_file("docs/guide.txt", options).unwrap();
+        writer.write_all(b"guide").unwrap();
+        std::fs::write(&path, writer.finish().unwrap().into_inner()).unwrap();
+
+        let root = path.to_string_lossy().to_string();
+        let fs = ArchiveFileSystem::open(&root).await.unwrap();
+
+        assert_eq!(
+            names(&fs.read_dir(&root).await.unwrap()),
+            ["docs/", "empty/"]
+        );
+
+        let docs = fs.read_dir(&format!("{}/docs", root)).await.unwrap();
+        assert_eq!(names(&docs), ["guide.txt"]);
+        assert_eq!(docs[0].size, Some(5));
+        assert_eq!(docs[0].permissions, Some(FilePermissions::from_mode(0o600)));
+        assert!(docs[0].modified.is_some());
+    }
+
+    #[tokio::test]
+    async fn explorer_descends_into_archives() {
+        use crate::{FileExplorer, Input};
+        use std::sync::Arc;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("release.tar"), tar_bytes()).unwrap();
+
+        // Browse through a symbolic link, the entries must keep its prefix
+        #[cfg(unix)]
+        let cwd = {
+            let link = dir.path().join("link");
+            std::os::unix::fs::symlink(dir.path(), &link).unwrap();
+            link
+        };
+        #[cfg(not(unix))]
+        let cwd = dir.path().to_path_buf();
+
+        let mut file_explorer = FileExplorer::with_fs(
+            Arc::new(crate::filesystem::LocalFileSystem::new()),
+            cwd.to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert!(file_explorer.select_file("release.tar"));
+        file_explorer.handle(Input::Right).await.unwrap();
+        assert_eq!(file_explorer.cwd(), &cwd.join("release.tar"));
+        assert_eq!(file_explorer.files()[1].name(), "release/");
+        assert_eq!(
+            file_explorer.files()[1].path(),
+            &cwd.join("release.tar/release")
+        );
+
+        assert!(file_explorer.select_file("release/"));
+        file_explorer.handle(Input::Right).await.unwrap();
+        assert_eq!(file_explorer.cwd(), &cwd.join("release.tar/release"));
+
+        file_explorer.handle(Input::Left).await.unwrap();
+        file_explorer.handle(Input::Left).await.unwrap();
+        assert_eq!(file_explorer.cwd(), &cwd);
+        assert_eq!(file_explorer.current().name(), "release.tar");
+    }
+
+    #[tokio::test]
+    async fn archive_files_remain_files() {
+        use crate::{filesystem::LocalFileSystem, FileExplorer, Input, PreviewContent};
+        use std::sync::Arc;
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("release.tar"), tar_bytes()).unwrap();
+        std::fs::write(root.join("notes.zip"), "not an archive").unwrap();
+        let path = |name: &str| root.join(name).to_string_lossy().to_string();
+
+        let fs = LocalFileSystem::new();
+        for name in ["release.tar", "notes.zip"] {
+            assert!(!fs.is_dir(&path(name)).await.unwrap(), "{name}");
+            assert!(fs.canonicalize(&path(name)).await.is_ok(), "{name}");
+        }
+        assert_eq!(
+            fs.read_chunk(&path("release.tar"), 0, 7).await.unwrap(),
+            b"release"
+        );
+        assert_eq!(
+            fs.read_chunk(&path("notes.zip"), 0, 3).await.unwrap(),
+            b"not"
+        );
+        assert!(fs.is_dir(&path("release.tar/release")).await.unwrap());
+        assert!(fs.read_dir(&path("notes.zip")).await.is_err());
+        assert!(fs.is_local(&path("")).await);
+        assert!(!fs.is_local(&path("release.tar/release")).await);
+
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), path("")).await.unwrap();
+
+        // A misnamed archive can not be entered
+        assert!(file_explorer.select_file("notes.zip"));
+        file_explorer.handle(Input::Right).await.unwrap();
+        assert_eq!(file_explorer.cwd(), root);
+
+        // A real archive is previewed as a file
+        file_explorer.set_preview(Some(crate::PreviewOptions::new()));
+        assert!(file_explorer.select_file("release.tar"));
+        file_explorer.poll_preview();
+        while file_explorer.preview().unwrap().is_loading() {
+            tokio::task::yield_now().await;
+            file_explorer.poll_preview();
+        }
+        assert_eq!(
+            file_explorer.preview().unwrap().content(),
+            &PreviewContent::Binary
+        );
+
+        // Both are deleted as files
+        for name in ["release.tar", "notes.zip"] {
+            assert!(file_explorer.select_file(name));
+            file_explorer.handle(Input::ToggleSelection).await.unwrap();
+        }
+        let results = file_explorer.delete_selected().await.unwrap();
+        assert!(
+            results.iter().all(|(_, result)| result.is_ok()),
+            "{results:?}"
+        );
+        assert!(!root.join("release.tar").exists());
+        assert!(!root.join("notes.zip").exists());
+    }
+}
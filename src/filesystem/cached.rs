@@ -0,0 +1,266 @@
+//! Filesystem wrapper caching directory listings.
+
+use super::{FileEntry, FilePermissions, FileSystem};
+use std::collections::HashMap;
+use std::io::Result;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// A wrapper that memoizes the [`read_dir`](FileSystem::read_dir) results of the
+/// wrapped filesystem.
+///
+/// Listings are reused for `ttl`, which saves round trips on slow or remote
+/// filesystems. Mutations made through the wrapper invalidate the listings they
+/// affect; changes made behind its back only show up once the TTL expires or after
+/// an explicit [`invalidate`](Cached::invalidate).
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use ratatui_async_explorer::filesystem::{Cached, FileSystem, InMemoryFileSystem};
+///
+/// # tokio_test::block_on(async {
+/// let fs = Cached::new(InMemoryFileSystem::new(), Duration::from_secs(30));
+/// assert!(fs.read_dir("/").await.unwrap().is_empty());
+///
+/// // Changes made behind the cache are not visible until it is invalidated
+/// fs.inner().create_file("/notes.txt").await.unwrap();
+/// assert!(fs.read_dir("/").await.unwrap().is_empty());
+///
+/// fs.invalidate("/");
+/// assert_eq!(fs.read_dir("/").await.unwrap().len(), 1);
+/// # })
+/// ```
+#[derive(Debug)]
+pub struct Cached<F> {
+    inner: F,
+    ttl: Duration,
+    listings: Mutex<HashMap<String, (Instant, Vec<FileEntry>)>>,
+}
+
+impl<F: FileSystem> Cached<F> {
+    /// Cache the directory listings of `inner` for `ttl`.
+    #[must_use]
+    pub fn new(inner: F, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            listings: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns how long a listing is reused.
+    #[inline]
+    #[must_use]
+    pub const fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns a reference to the wrapped filesystem.
+    ///
+    /// Changes made through it are not tracked by the cache.
+    #[inline]
+    #[must_use]
+    pub const fn inner(&self) -> &F {
+        &self.inner
+    }
+
+    /// Consumes the wrapper, returning the wrapped filesystem.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> F {
+        self.inner
+    }
+
+    /// Forget the cached listing of the directory `path`.
+    pub fn invalidate(&self, path: &str) {
+        self.listings().remove(path);
+    }
+
+    /// Forget every cached listing.
+    pub fn invalidate_all(&self) {
+        self.listings().clear();
+    }
+
+    /// Forget the listings of `path`, of everything below it and of its parent.
+    fn invalidate_tree(&self, path: &str) {
+        let parent = self.inner.parent(path);
+
+        self.listings().retain(|dir, _| {
+            !Path::new(dir).starts_with(path) && Some(dir.as_str()) != parent.as_deref()
+        });
+    }
+
+    fn listings(&self) -> MutexGuard<'_, HashMap<String, (Instant, Vec<FileEntry>)>> {
+        self.listings.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<F: FileSystem> FileSystem for Cached<F> {
+    async fn read_dir(&self, path: &str) -> Result<Vec<FileEntry>> {
+        if let Some((at, entries)) = self.listings().get(path) {
+            if at.elapsed() < self.ttl {
+                return Ok(entries.clone());
+            }
+        }
+
+        let entries = self.inner.read_dir(path).await?;
+        self.listings()
+            .insert(path.to_string(), (Instant::now(), entries.clone()));
+
+        Ok(entries)
+    }
+
+    async fn exists(&self, path: &str) -> Result<bool> {
+        self.inner.exists(path).await
+    }
+
+    async fn is_dir(&self, path: &str) -> Result<bool> {
+        self.inner.is_dir(path).await
+    }
+
+    async fn canonicalize(&self, path: &str) -> Result<String> {
+        self.inner.canonicalize(path).await
+    }
+
+    fn parent(&self, path: &str) -> Option<String> {
+        self.inner.parent(path)
+    }
+
+    async fn read_chunk(&self, path: &str, offset: u64, len: usize) -> Result<Vec<u8>> {
+        // Only directory listings are cached
+        self.inner.read_chunk(path, offset, len).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        let result = self.inner.delete(path).await;
+        self.invalidate_tree(path);
+        result
+    }
+
+    async fn delete_dir_all(&self, path: &str) -> Result<()> {
+        let result = self.inner.delete_dir_all(path).await;
+        self.invalidate_tree(path);
+        result
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> Result<()> {
+        let result = self.inner.rename(from, to).await;
+        self.invalidate_tree(from);
+        self.invalidate_tree(to);
+        result
+    }
+
+    async fn copy(&self, from: &str, to: &str) -> Result<()> {
+        let result = self.inner.copy(from, to).await;
+        self.invalidate_tree(to);
+        result
+    }
+
+    async fn move_to(&self, from: &str, to: &str) -> Result<()> {
+        let result = self.inner.move_to(from, to).await;
+        self.invalidate_tree(from);
+        self.invalidate_tree(to);
+        result
+    }
+
+    async fn create_dir(&self, path: &str) -> Result<()> {
+        let result = self.inner.create_dir(path).await;
+        self.invalidate_tree(path);
+        result
+    }
+
+    async fn create_dir_all(&self, path: &str) -> Result<()> {
+        let result = self.inner.create_dir_all(path).await;
+
+        // Every missing ancestor may have been created
+        let mut dir = Some(path.to_string());
+        while let Some(path) = dir {
+            self.invalidate_tree(&path);
+            dir = self.inner.parent(&path);
+        }
+
+        result
+    }
+
+    async fn create_file(&self, path: &str) -> Result<()> {
+        let result = self.inner.create_file(path).await;
+        self.invalidate_tree(path);
+        result
+    }
+
+    async fn write_file(&self, path: &str, contents: &[u8]) -> Result<()> {
+        let result = self.inner.write_file(path, contents).await;
+        self.invalidate_tree(path);
+        result
+    }
+
+    async fn set_permissions(&self, path: &str, permissions: FilePermissions) -> Result<()> {
+        let result = self.inner.set_permissions(path, permissions).await;
+        // Listings carry the permissions of their entries
+        if let Some(parent) = self.inner.parent(path) {
+            self.invalidate(&parent);
+        }
+        result
+    }
+
+    async fn is_local(&self, path: &str) -> bool {
+        self.inner.is_local(path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filesystem::InMemoryFileSystem;
+
+    fn names(entries: &[FileEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.name.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn mutations_invalidate_affected_listings() {
+        let fs = Cached::new(
+            InMemoryFileSystem::new().with_file("/a/old.txt", ""),
+            Duration::from_secs(60),
+        );
+
+        assert_eq!(names(&fs.read_dir("/").await.unwrap()), ["a/"]);
+        assert_eq!(names(&fs.read_dir("/a").await.unwrap()), ["old.txt"]);
+
+        fs.rename("/a/old.txt", "/new.txt").await.unwrap();
+        assert_eq!(names(&fs.read_dir("/").await.unwrap()), ["a/", "new.txt"]);
+        assert!(fs.read_dir("/a").await.unwrap().is_empty());
+
+        fs.create_dir_all("/a/b/c").await.unwrap();
+        assert_eq!(names(&fs.read_dir("/a").await.unwrap()), ["b/"]);
+
+        fs.read_dir("/a/b/c").await.unwrap();
+        fs.delete_dir_all("/a").await.unwrap();
+        assert!(fs.read_dir("/a/b/c").await.is_err());
+        assert_eq!(names(&fs.read_dir("/").await.unwrap()), ["new.txt"]);
+    }
+
+    #[tokio::test]
+    async fn listings_expire() {
+        let fs = Cached::new(InMemoryFileSystem::new(), Duration::ZERO);
+
+        fs.read_dir("/").await.unwrap();
+        fs.inner().create_file("/notes.txt").await.unwrap();
+        assert_eq!(fs.read_dir("/").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn invalidate_all_forgets_every_listing() {
+        let fs = Cached::new(InMemoryFileSystem::new(), Duration::from_secs(60));
+
+        fs.read_dir("/").await.unwrap();
+        fs.inner().create_file("/notes.txt").await.unwrap();
+        assert!(fs.read_dir("/").await.unwrap().is_empty());
+
+        fs.invalidate_all();
+        assert_eq!(fs.read_dir("/").await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,357 @@
+//! Filesystem wrapper confining every path to a root directory.
+
+use super::{FileEntry, FilePermissions, FileSystem};
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Component, Path, PathBuf};
+
+/// A wrapper that confines every operation to a root directory of the wrapped filesystem.
+///
+/// Paths keep their meaning from the inner filesystem, they are just not allowed to
+/// leave `root`. Paths are first normalized lexically, so that `..` components cannot
+/// climb above the root, and then resolved with
+/// [`canonicalize`](FileSystem::canonicalize) so that a symbolic link pointing outside
+/// of the root cannot be followed either. Offending operations fail with
+/// [`ErrorKind::PermissionDenied`].
+///
+/// [`parent`](FileSystem::parent) returns `None` for the root itself, so a
+/// [`FileExplorer`](crate::FileExplorer) never shows a `../` entry there and
+/// [`Input::Left`](crate::Input::Left) stops at the root.
+///
+/// # Examples
+///
+/// ```
+/// use ratatui_async_explorer::filesystem::{Chroot, FileSystem, InMemoryFileSystem};
+///
+/// # tokio_test::block_on(async {
+/// let fs = Chroot::new(
+///     InMemoryFileSystem::new()
+///         .with_dir("/home/alice")
+///         .with_file("/etc/passwd", ""),
+///     "/home/alice",
+/// );
+///
+/// assert_eq!(fs.parent("/home/alice"), None);
+/// assert!(fs.read_dir("/home/alice/../../etc").await.is_err());
+/// # })
+/// ```
+#[derive(Debug, Clone)]
+pub struct Chroot<F> {
+    inner: F,
+    root: PathBuf,
+}
+
+impl<F: FileSystem> Chroot<F> {
+    /// Confine `inner` to the absolute directory `root`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `root` is not an absolute path.
+    #[must_use]
+    pub fn new<P: AsRef<Path>>(inner: F, root: P) -> Self {
+        let root = root.as_ref();
+        assert!(root.is_absolute(), "chroot must be an absolute path");
+
+        Self {
+            inner,
+            root: normalize(root),
+        }
+    }
+
+    /// Returns the directory every path is confined to.
+    #[inline]
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns a reference to the wrapped filesystem.
+    #[inline]
+    #[must_use]
+    pub const fn inner(&self) -> &F {
+        &self.inner
+    }
+
+    /// Consumes the wrapper, returning the wrapped filesystem.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> F {
+        self.inner
+    }
+
+    /// Normalize `path` and make sure it stays inside the root, even once symbolic
+    /// links are resolved.
+    ///
+    /// Relative paths are interpreted from the root.
+    async fn confine(&self, path: &str) -> Result<String> {
+        let normalized = normalize(&self.root.join(path));
+        if !normalized.starts_with(&self.root) {
+            return Err(escape(path));
+        }
+
+        // The root itself may be behind a symbolic link, compare resolved paths
+        let real_root = self
+            .inner
+            .canonicalize(&self.root.to_string_lossy())
+            .await?;
+
+        // Paths that do not exist yet (e.g. creation targets) are checked through
+        // their closest existing ancestor
+        let mut probe = normalized.as_path();
+        loop {
+            match self.inner.canonicalize(&probe.to_string_lossy()).await {
+                Ok(real) => {
+                    if !Path::new(&real).starts_with(&real_root) {
+                        return Err(escape(path));
+                    }
+                    break;
+                }
+                Err(_) => match probe.parent() {
+                    Some(parent) if parent.starts_with(&self.root) => probe = parent,
+                    _ => break,
+                },
+            }
+        }
+
+        Ok(normalized.to_string_lossy().to_string())
+    }
+}
+
+impl<F: FileSystem> FileSystem for Chroot<F> {
+    async fn read_dir(&self, path: &str) -> Result<Vec<FileEntry>> {
+        let path = self.confine(path).await?;
+        self.inner.read_dir(&path).await
+    }
+
+    async fn exists(&self, path: &str) -> Result<bool> {
+        match self.confine(path).await {
+            Ok(path) => self.inner.exists(&path).await,
+            Err(_) => Ok(false),
+        }
+    }
+
+    async fn is_dir(&self, path: &str) -> Result<bool> {
+        let path = self.confine(path).await?;
+        self.inner.is_dir(&path).await
+    }
+
+    async fn canonicalize(&self, path: &str) -> Result<String> {
+        let path = self.confine(path).await?;
+        self.inner.canonicalize(&path).await
+    }
+
+    fn parent(&self, path: &str) -> Option<String> {
+        let normalized = normalize(&self.root.join(path));
+        if normalized == self.root || !normalized.starts_with(&self.root) {
+            return None;
+        }
+
+        self.inner.parent(&normalized.to_string_lossy())
+    }
+
+    async fn read_chunk(&self, path: &str, offset: u64, len: usize) -> Result<Vec<u8>> {
+        let path = self.confine(path).await?;
+        self.inner.read_chunk(&path, offset, len).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        let path = self.confine(path).await?;
+        self.inner.delete(&path).await
+    }
+
+    async fn delete_dir_all(&self, path: &str) -> Result<()> {
+        let path = self.confine(path).await?;
+        if Path::new(&path) == self.root {
+            return Err(Error::new(
+                ErrorKind::PermissionDenied,
+                format!("Cannot delete the root directory: {}", path),
+            ));
+        }
+
+        self.inner.delete_dir_all(&path).await
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> Result<()> {
+        let from = self.confine(from).await?;
+        let to = self.confine(to).await?;
+        self.inner.rename(&from, &to).await
+    }
+
+    async fn copy(&self, from: &str, to: &str) -> Result<()> {
+        let from = self.confine(from).await?;
+        let to = self.confine(to).await?;
+        self.inner.copy(&from, &to).await
+    }
+
+    async fn move_to(&self, from: &str, to: &str) -> Result<()> {
+        let from = self.confine(from).await?;
+        let to = self.confine(to).await?;
+        self.inner.move_to(&from, &to).await
+    }
+
+    async fn create_dir(&self, path: &str) -> Result<()> {
+        let path = self.confine(path).await?;
+        self.inner.create_dir(&path).await
+    }
+
+    async fn create_dir_all(&self, path: &str) -> Result<()> {
+        let path = self.confine(path).await?;
+        self.inner.create_dir_all(&path).await
+    }
+
+    async fn create_file(&self, path: &str) -> Result<()> {
+        let path = self.confine(path).await?;
+        self.inner.create_file(&path).await
+    }
+
+    async fn write_file(&self, path: &str, contents: &[u8]) -> Result<()> {
+        let path = self.confine(path).await?;
+        self.inner.write_file(&path, contents).await
+    }
+
+    async fn set_permissions(&self, path: &str, permissions: FilePermissions) -> Result<()> {
+        let path = self.confine(path).await?;
+        self.inner.set_permissions(&path, permissions).await
+    }
+
+    async fn is_local(&self, path: &str) -> bool {
+        match self.confine(path).await {
+            Ok(path) => self.inner.is_local(&path).await,
+            Err(_) => false,
+        }
+    }
+}
+
+/// Resolve `.` and `..` components without touching the filesystem.
+///
+/// `..` never climbs above the first component, like on a real root directory.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+fn escape(path: &str) -> Error {
+    Error::new(
+        ErrorKind::PermissionDenied,
+        format!("Path escapes the root directory: {}", path),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filesystem::InMemoryFileSystem;
+    use crate::{FileExplorer, Input};
+    use std::sync::Arc;
+
+    fn fs() -> Chroot<InMemoryFileSystem> {
+        Chroot::new(
+            InMemoryFileSystem::new()
+                .with_file("/srv/tenant/docs/report.pdf", "")
+                .with_file("/srv/other/secret.txt", "")
+                .with_symlink("/srv/tenant/escape", "/srv/other")
+                .with_symlink("/srv/tenant/shortcut", "docs"),
+            "/srv/tenant",
+        )
+    }
+
+    #[tokio::test]
+    async fn refuses_escapes() {
+        let fs = fs();
+
+        for path in ["/srv/other", "/srv/tenant/../other", "/srv/tenant/escape"] {
+            let err = fs.read_dir(path).await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::PermissionDenied, "{}", path);
+        }
+
+        let err = fs.create_file("/srv/tenant/escape/new.txt").await;
+        assert_eq!(err.unwrap_err().kind(), ErrorKind::PermissionDenied);
+
+        let err = fs
+            .copy("/srv/other/secret.txt", "/srv/tenant/secret.txt")
+            .await;
+        assert_eq!(err.unwrap_err().kind(), ErrorKind::PermissionDenied);
+
+        assert!(!fs.exists("/srv/other/secret.txt").await.unwrap());
+        assert!(fs.delete_dir_all("/srv/tenant").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn allows_paths_inside_the_root() {
+        let fs = fs();
+
+        assert_eq!(fs.read_dir("/srv/tenant/shortcut").await.unwrap().len(), 1);
+        assert_eq!(fs.read_dir("docs").await.unwrap().len(), 1);
+
+        fs.create_file("/srv/tenant/docs/../notes.txt")
+            .await
+            .unwrap();
+        assert!(fs.inner().exists("/srv/tenant/notes.txt").await.unwrap());
+
+        assert_eq!(
+            fs.parent("/srv/tenant/docs"),
+            Some("/srv/tenant".to_string())
+        );
+        assert_eq!(fs.parent("/srv/tenant"), None);
+        assert_eq!(fs.parent("/srv/tenant/.."), None);
+    }
+
+    #[tokio::test]
+    async fn explorer_stops_at_the_root() {
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs()), "/srv/tenant".to_string())
+            .await
+            .unwrap();
+
+        assert!(file_explorer
+            .files()
+            .iter()
+            .all(|file| file.name() != "../"));
+
+        file_explorer.handle(Input::Left).await.unwrap();
+        assert_eq!(file_explorer.cwd(), Path::new("/srv/tenant"));
+    }
+
+    #[tokio::test]
+    async fn explorer_at_an_empty_root() {
+        use ratatui::{buffer::Buffer, layout::Rect, widgets::WidgetRef};
+
+        let fs = Chroot::new(InMemoryFileSystem::new().with_dir("/srv/t"), "/srv/t");
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/srv/t".to_string())
+            .await
+            .unwrap();
+        assert!(file_explorer.files().is_empty());
+
+        // Nothing is selected, so there is nothing to act on
+        for input in [
+            Input::Up,
+            Input::Down,
+            Input::Right,
+            Input::Delete,
+            Input::Confirm,
+            Input::ToggleSelection,
+            Input::SelectRange,
+            Input::Yank,
+            Input::Cut,
+            Input::Paste,
+        ] {
+            file_explorer.handle(input).await.unwrap();
+        }
+        assert!(file_explorer.selected_paths().is_empty());
+        assert!(file_explorer.clipboard().is_none());
+        assert_eq!(file_explorer.cwd(), Path::new("/srv/t"));
+
+        let area = Rect::new(0, 0, 20, 5);
+        let mut buf = Buffer::empty(area);
+        file_explorer.widget().render_ref(area, &mut buf);
+    }
+}
@@ -2,18 +2,52 @@
 
 use super::{FileEntry, FilePermissions, FileSystem};
 use std::io::{Error, ErrorKind, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+#[cfg(feature = "archive")]
+use super::{ArchiveFileSystem, ArchiveFormat};
+#[cfg(feature = "archive")]
+use std::sync::{Arc, Mutex, MutexGuard};
+#[cfg(feature = "archive")]
+use std::time::SystemTime;
+
 /// A filesystem implementation for local file operations using `tokio::fs`.
 ///
-/// This is a zero-sized type that implements the `FileSystem` trait for
-/// local filesystem operations.
-#[derive(Debug, Clone, Copy)]
-pub struct LocalFileSystem;
+/// With the `archive` feature enabled, `.tar`, `.tar.gz` and `.zip` files can be
+/// browsed like read-only directories: reading `/tmp/release.tar.gz/docs` lists the
+/// `docs` directory of the archive through an [`ArchiveFileSystem`](super::ArchiveFileSystem).
+/// The archive file itself remains a file, apart from being listed by
+/// [`read_dir`](FileSystem::read_dir), and a file that can not be parsed as an archive is
+/// only a file.
+/// The last archive opened is kept open, and shared by the clones of the filesystem, so
+/// that browsing it does not index it again for every directory.
+#[derive(Debug, Clone, Default)]
+pub struct LocalFileSystem {
+    /// The most recently opened archive, with the modification time it was indexed at.
+    #[cfg(feature = "archive")]
+    last_archive: Arc<Mutex<Option<OpenArchive>>>,
+}
+
+/// The root of an archive, the modification time it was indexed at and its index.
+#[cfg(feature = "archive")]
+type OpenArchive = (String, SystemTime, Arc<ArchiveFileSystem>);
+
+impl LocalFileSystem {
+    /// Create a new local filesystem.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
 
 impl FileSystem for LocalFileSystem {
     async fn read_dir(&self, path: &str) -> Result<Vec<FileEntry>> {
+        #[cfg(feature = "archive")]
+        if let Some((archive, inner)) = self.open_archive(path, true).await? {
+            return archive.read_dir(&inner).await;
+        }
+
         // Add timeout for the entire operation to prevent hanging on network mounts
         let timeout_duration = Duration::from_secs(5);
 
@@ -156,34 +190,33 @@ impl FileSystem for LocalFileSystem {
         })
         .await;
 
-        let mut entries = match read_result {
-            Ok(Ok(temp_entries)) => temp_entries,
-            Ok(Err(e)) => return Err(e),
-            Err(_) => {
-                return Err(Error::new(
-                    ErrorKind::TimedOut,
-                    format!("Timeout reading directory: {}", path),
-                ));
-            }
-        };
-
-        // Sort: directories first, then alphabetically
-        entries.sort_by(|a, b| match (a.is_dir, b.is_dir) {
-            (true, false) => std::cmp::Ordering::Less,
-            (false, true) => std::cmp::Ordering::Greater,
-            _ => a.name.cmp(&b.name),
-        });
-
-        Ok(entries)
+        match read_result {
+            Ok(Ok(entries)) => Ok(entries),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(Error::new(
+                ErrorKind::TimedOut,
+                format!("Timeout reading directory: {}", path),
+            )),
+        }
     }
 
     async fn exists(&self, path: &str) -> Result<bool> {
+        #[cfg(feature = "archive")]
+        if let Ok(Some((archive, inner))) = self.open_archive(path, false).await {
+            return archive.exists(&inner).await;
+        }
+
         let result = tokio::time::timeout(Duration::from_secs(2), tokio::fs::metadata(path)).await;
 
         Ok(matches!(result, Ok(Ok(_))))
     }
 
     async fn is_dir(&self, path: &str) -> Result<bool> {
+        #[cfg(feature = "archive")]
+        if let Some((archive, inner)) = self.open_archive(path, false).await? {
+            return archive.is_dir(&inner).await;
+        }
+
         tokio::time::timeout(Duration::from_secs(2), tokio::fs::metadata(path))
             .await
             .map_err(|_| Error::new(ErrorKind::TimedOut, "Timeout checking if path is directory"))?
@@ -191,6 +224,11 @@ impl FileSystem for LocalFileSystem {
     }
 
     async fn canonicalize(&self, path: &str) -> Result<String> {
+        #[cfg(feature = "archive")]
+        if let Some((archive, inner)) = self.open_archive(path, false).await? {
+            return archive.canonicalize(&inner).await;
+        }
+
         tokio::time::timeout(Duration::from_secs(2), tokio::fs::canonicalize(path))
             .await
             .map_err(|_| Error::new(ErrorKind::TimedOut, "Timeout canonicalizing path"))?
@@ -203,9 +241,417 @@ impl FileSystem for LocalFileSystem {
             .map(|p| p.to_string_lossy().to_string())
     }
 
+    async fn read_chunk(&self, path: &str, offset: u64, len: usize) -> Result<Vec<u8>> {
+        #[cfg(feature = "archive")]
+        if let Some((archive, inner)) = self.open_archive(path, false).await? {
+            return archive.read_chunk(&inner, offset, len).await;
+        }
+
+        let path = path.to_string();
+        let read = tokio::task::spawn_blocking(move || {
+            use std::io::{Read, Seek, SeekFrom};
+
+            let mut file = std::fs::File::open(path)?;
+            file.seek(SeekFrom::Start(offset))?;
+
+            let mut chunk = Vec::with_capacity(len);
+            file.take(len as u64).read_to_end(&mut chunk)?;
+            Ok(chunk)
+        });
+
+        tokio::time::timeout(Duration::from_secs(5), read)
+            .await
+            .map_err(|_| Error::new(ErrorKind::TimedOut, "Timeout reading file"))?
+            .map_err(Error::other)?
+    }
+
     async fn delete(&self, path: &str) -> Result<()> {
         tokio::time::timeout(Duration::from_secs(2), tokio::fs::remove_file(path))
             .await
             .map_err(|_| Error::new(ErrorKind::TimedOut, "Timeout deleting file"))?
     }
+
+    async fn delete_dir_all(&self, path: &str) -> Result<()> {
+        // No timeout here: removing a large tree legitimately takes a while
+        tokio::fs::remove_dir_all(path).await
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> Result<()> {
+        tokio::time::timeout(Duration::from_secs(2), tokio::fs::rename(from, to))
+            .await
+            .map_err(|_| Error::new(ErrorKind::TimedOut, "Timeout renaming file"))?
+    }
+
+    async fn copy(&self, from: &str, to: &str) -> Result<()> {
+        // No timeout here: copying large files legitimately takes a while
+        let metadata = tokio::fs::symlink_metadata(from).await?;
+
+        if metadata.is_dir() {
+            // Compare the canonical paths, so that a symbolic link or a `..` leading back
+            // into the directory is caught too
+            let source = tokio::fs::canonicalize(from).await?;
+            if canonicalize_target(Path::new(to))
+                .await?
+                .starts_with(&source)
+            {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Cannot copy {} into itself", from),
+                ));
+            }
+            copy_dir_all(PathBuf::from(from), PathBuf::from(to)).await
+        } else {
+            // Like for directories, never replace an existing entry
+            if tokio::fs::symlink_metadata(to).await.is_ok() {
+                return Err(Error::new(
+                    ErrorKind::AlreadyExists,
+                    format!("{} already exists", to),
+                ));
+            }
+            tokio::fs::copy(from, to).await.map(|_| ())
+        }
+    }
+
+    async fn move_to(&self, from: &str, to: &str) -> Result<()> {
+        match self.rename(from, to).await {
+            Err(e) if e.kind() == ErrorKind::CrossesDevices => {
+                copy_and_remove(self, from, to).await
+            }
+            result => result,
+        }
+    }
+
+    async fn create_dir(&self, path: &str) -> Result<()> {
+        tokio::time::timeout(Duration::from_secs(2), tokio::fs::create_dir(path))
+            .await
+            .map_err(|_| Error::new(ErrorKind::TimedOut, "Timeout creating directory"))?
+    }
+
+    async fn create_dir_all(&self, path: &str) -> Result<()> {
+        tokio::time::timeout(Duration::from_secs(2), tokio::fs::create_dir_all(path))
+            .await
+            .map_err(|_| Error::new(ErrorKind::TimedOut, "Timeout creating directory"))?
+    }
+
+    async fn create_file(&self, path: &str) -> Result<()> {
+        let mut options = tokio::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+
+        tokio::time::timeout(Duration::from_secs(2), options.open(path))
+            .await
+            .map_err(|_| Error::new(ErrorKind::TimedOut, "Timeout creating file"))?
+            .map(|_| ())
+    }
+
+    async fn write_file(&self, path: &str, contents: &[u8]) -> Result<()> {
+        // No timeout here: writing large files legitimately takes a while
+        tokio::fs::write(path, contents).await
+    }
+
+    async fn set_permissions(&self, path: &str, permissions: FilePermissions) -> Result<()> {
+        let permissions = {
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::Permissions::from_mode(permissions.mode())
+            }
+
+            #[cfg(not(unix))]
+            {
+                let mut current = tokio::fs::metadata(path).await?.permissions();
+                current.set_readonly(!permissions.user_write);
+                current
+            }
+        };
+
+        tokio::time::timeout(
+            Duration::from_secs(2),
+            tokio::fs::set_permissions(path, permissions),
+        )
+        .await
+        .map_err(|_| Error::new(ErrorKind::TimedOut, "Timeout setting permissions"))?
+    }
+
+    async fn is_local(&self, _path: &str) -> bool {
+        // The inside of an archive only exists in memory
+        #[cfg(feature = "archive")]
+        if let Ok(Some(_)) = self.open_archive(_path, true).await {
+            return false;
+        }
+
+        true
+    }
+}
+
+#[cfg(feature = "archive")]
+impl LocalFileSystem {
+    /// If `path` goes through an archive file, open it and return it along with the path
+    /// of `path` inside it. The archive file itself only counts when `include_root` is set,
+    /// and files that can not be parsed as archives are skipped.
+    ///
+    /// The archive is rooted at the path of the archive file as written in `path`, so that
+    /// its entries keep the prefix the caller uses, symbolic links included.
+    async fn open_archive(
+        &self,
+        path: &str,
+        include_root: bool,
+    ) -> Result<Option<(Arc<ArchiveFileSystem>, String)>> {
+        let skip = usize::from(!include_root);
+        for ancestor in Path::new(path).ancestors().skip(skip) {
+            let Some(format) = ArchiveFormat::from_path(ancestor) else {
+                continue;
+            };
+
+            let metadata = match tokio::fs::metadata(ancestor).await {
+                Ok(metadata) if metadata.is_file() => metadata,
+                _ => continue,
+            };
+            let modified = metadata.modified()?;
+            let root = ancestor.to_string_lossy().to_string();
+
+            let cached = self
+                .last_archive()
+                .as_ref()
+                .filter(|(cached, at, _)| *cached == root && *at == modified)
+                .map(|(_, _, archive)| Arc::clone(archive));
+
+            let archive = match cached {
+                Some(archive) => archive,
+                None => match ArchiveFileSystem::open_as(&root, format).await {
+                    Ok(archive) => {
+                        let archive = Arc::new(archive);
+                        *self.last_archive() = Some((root, modified, Arc::clone(&archive)));
+                        archive
+                    }
+                    Err(e) => {
+                        // Only named like an archive, it is a plain file
+                        tracing::debug!("Not browsing {} as an archive: {}", root, e);
+                        continue;
+                    }
+                },
+            };
+
+            return Ok(Some((archive, path.to_string())));
+        }
+
+        Ok(None)
+    }
+
+    fn last_archive(&self) -> MutexGuard<'_, Option<OpenArchive>> {
+        self.last_archive.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Move `from` to `to` by copying it and then removing the original, for when a rename
+/// cannot cross filesystems.
+async fn copy_and_remove(filesystem: &LocalFileSystem, from: &str, to: &str) -> Result<()> {
+    filesystem.copy(from, to).await?;
+
+    if tokio::fs::symlink_metadata(from).await?.is_dir() {
+        tokio::fs::remove_dir_all(from).await
+    } else {
+        tokio::fs::remove_file(from).await
+    }
+}
+
+/// Canonicalize a path which may not exist yet, by canonicalizing its parent and joining its
+/// file name.
+async fn canonicalize_target(path: &Path) -> Result<PathBuf> {
+    let Some(name) = path.file_name() else {
+        return tokio::fs::canonicalize(path).await;
+    };
+
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    Ok(tokio::fs::canonicalize(parent).await?.join(name))
+}
+
+/// Recursively copy the directory `from` to `to`, which must not exist yet.
+///
+/// Symbolic links are recreated as links on Unix and copied as regular files elsewhere.
+async fn copy_dir_all(from: PathBuf, to: PathBuf) -> Result<()> {
+    let mut pending = vec![(from, to)];
+
+    while let Some((src, dst)) = pending.pop() {
+        tokio::fs::create_dir(&dst).await?;
+
+        let mut read_dir = tokio::fs::read_dir(&src).await?;
+        while let Some(entry) = read_dir.next_entry().await? {
+            let target = dst.join(entry.file_name());
+            let file_type = entry.file_type().await?;
+
+            if file_type.is_dir() {
+                pending.push((entry.path(), target));
+            } else if file_type.is_symlink() {
+                #[cfg(unix)]
+                {
+                    let link = tokio::fs::read_link(entry.path()).await?;
+                    tokio::fs::symlink(link, target).await?;
+                }
+
+                #[cfg(not(unix))]
+                {
+                    tokio::fs::copy(entry.path(), target).await?;
+                }
+            } else {
+                tokio::fs::copy(entry.path(), target).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn path(dir: &Path, name: &str) -> String {
+        dir.join(name).to_string_lossy().to_string()
+    }
+
+    #[tokio::test]
+    async fn create() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("notes.txt"), "notes").unwrap();
+
+        let fs = LocalFileSystem::new();
+        for result in [
+            fs.create_file(&path(root, "notes.txt")).await,
+            fs.create_dir(&path(root, "notes.txt")).await,
+        ] {
+            assert_eq!(result.unwrap_err().kind(), ErrorKind::AlreadyExists);
+        }
+        assert_eq!(fs::read_to_string(root.join("notes.txt")).unwrap(), "notes");
+
+        // Nested names need their parents
+        let error = fs.create_dir(&path(root, "a/b")).await.unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+        fs.create_dir_all(&path(root, "a/b")).await.unwrap();
+        fs.create_file(&path(root, "a/b/c.txt")).await.unwrap();
+        assert!(root.join("a/b/c.txt").is_file());
+    }
+
+    #[tokio::test]
+    async fn target_exists() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/inner")).unwrap();
+        fs::write(root.join("src/inner/file.txt"), "").unwrap();
+        fs::create_dir_all(root.join("dst/inner")).unwrap();
+        fs::write(root.join("dst/inner/file.txt"), "").unwrap();
+
+        let fs = LocalFileSystem::new();
+        let error = fs
+            .copy(&path(root, "src"), &path(root, "dst"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::AlreadyExists);
+
+        // Nor can a file replace a file
+        fs::write(root.join("notes.txt"), "notes").unwrap();
+        fs::write(root.join("todo.txt"), "todo").unwrap();
+        let error = fs
+            .copy(&path(root, "notes.txt"), &path(root, "todo.txt"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::AlreadyExists);
+        assert_eq!(fs::read(root.join("todo.txt")).unwrap(), b"todo");
+
+        // A directory cannot replace a directory which is not empty
+        assert!(fs
+            .rename(&path(root, "src"), &path(root, "dst"))
+            .await
+            .is_err());
+        assert!(fs
+            .move_to(&path(root, "src"), &path(root, "dst"))
+            .await
+            .is_err());
+        assert!(root.join("src/inner/file.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn copy_into_itself() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/inner")).unwrap();
+        fs::create_dir(root.join("other")).unwrap();
+
+        let fs = LocalFileSystem::new();
+        let into_itself = |to: &str| {
+            let (fs, to) = (&fs, path(root, to));
+            let from = path(root, "src");
+            async move { fs.copy(&from, &to).await.unwrap_err().kind() }
+        };
+        assert_eq!(into_itself("src/copy").await, ErrorKind::InvalidInput);
+        assert_eq!(
+            into_itself("other/../src/copy").await,
+            ErrorKind::InvalidInput
+        );
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(root.join("src"), root.join("link")).unwrap();
+            assert_eq!(into_itself("link/copy").await, ErrorKind::InvalidInput);
+            assert_eq!(
+                into_itself("link/inner/copy").await,
+                ErrorKind::InvalidInput
+            );
+        }
+
+        assert!(!root.join("src/copy").exists());
+
+        // A sibling sharing a prefix of the name is not inside the directory
+        fs.copy(&path(root, "src"), &path(root, "src2"))
+            .await
+            .unwrap();
+        assert!(root.join("src2/inner").is_dir());
+    }
+
+    #[tokio::test]
+    async fn move_across_devices() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/inner")).unwrap();
+        fs::write(root.join("src/inner/file.txt"), "contents").unwrap();
+        fs::write(root.join("file.txt"), "file").unwrap();
+        fs::create_dir(root.join("dst")).unwrap();
+
+        let fs = LocalFileSystem::new();
+        copy_and_remove(&fs, &path(root, "src"), &path(root, "dst/src"))
+            .await
+            .unwrap();
+        assert!(!root.join("src").exists());
+        assert_eq!(
+            fs::read_to_string(root.join("dst/src/inner/file.txt")).unwrap(),
+            "contents"
+        );
+
+        copy_and_remove(&fs, &path(root, "file.txt"), &path(root, "dst/file.txt"))
+            .await
+            .unwrap();
+        assert!(!root.join("file.txt").exists());
+        assert_eq!(
+            fs::read_to_string(root.join("dst/file.txt")).unwrap(),
+            "file"
+        );
+
+        // Nothing is removed when the copy fails
+        fs::write(root.join("file.txt"), "file").unwrap();
+        let error = copy_and_remove(
+            &fs,
+            &path(root, "file.txt"),
+            &path(root, "missing/file.txt"),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+        assert!(root.join("file.txt").exists());
+    }
 }
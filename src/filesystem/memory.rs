@@ -0,0 +1,946 @@
+//! In-memory filesystem implementation.
+
+use super::{FileEntry, FilePermissions, FileSystem};
+use std::collections::{BTreeMap, VecDeque};
+use std::io::{Error, ErrorKind, Result};
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::SystemTime;
+
+/// Maximum number of symbolic links followed while resolving a single path.
+const MAX_SYMLINK_HOPS: usize = 40;
+
+/// A filesystem living entirely in memory.
+///
+/// It is meant for tests and demos: it never touches the disk, and every
+/// attribute rendered by the explorer (permissions, symbolic links, modification
+/// times and hidden flags) can be set explicitly, so the resulting listings are
+/// fully deterministic.
+///
+/// Paths are Unix-style and absolute. Permissions are enforced for the owner:
+/// listing a directory requires `user_read`, and creating or removing entries
+/// requires `user_write` on the parent directory.
+///
+/// # Examples
+///
+/// Describe the tree with a diagram:
+///
+/// ```
+/// use ratatui_async_explorer::filesystem::InMemoryFileSystem;
+///
+/// let fs = InMemoryFileSystem::from_tree(
+///     "
+///     /
+///     ├── .git/
+///     └── Documents/
+///         ├── passport.png
+///         └── resume.pdf
+///     ",
+/// );
+/// ```
+///
+/// Or build it entry by entry:
+///
+/// ```
+/// use std::time::{Duration, SystemTime};
+/// use ratatui_async_explorer::{filesystem::InMemoryFileSystem, FilePermissions};
+///
+/// let fs = InMemoryFileSystem::new()
+///     .with_file("/home/user/notes.txt", "hello")
+///     .with_modified("/home/user/notes.txt", SystemTime::UNIX_EPOCH + Duration::from_secs(60))
+///     .with_file("/home/user/run.sh", "#!/bin/sh")
+///     .with_permissions("/home/user/run.sh", FilePermissions::from_mode(0o755))
+///     .with_symlink("/home/user/latest", "notes.txt")
+///     .with_hidden("/home/user/run.sh", true);
+/// ```
+#[derive(Debug)]
+pub struct InMemoryFileSystem {
+    nodes: RwLock<BTreeMap<String, Node>>,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    kind: NodeKind,
+    permissions: FilePermissions,
+    modified: SystemTime,
+    hidden: Option<bool>,
+}
+
+#[derive(Debug, Clone)]
+enum NodeKind {
+    File(Vec<u8>),
+    Dir,
+    Symlink(String),
+}
+
+impl Node {
+    fn new(kind: NodeKind, modified: SystemTime) -> Self {
+        let mode = match kind {
+            NodeKind::File(_) => 0o644,
+            NodeKind::Dir | NodeKind::Symlink(_) => 0o755,
+        };
+
+        Self {
+            kind,
+            permissions: FilePermissions::from_mode(mode),
+            modified,
+            hidden: None,
+        }
+    }
+
+    fn is_dir(&self) -> bool {
+        matches!(self.kind, NodeKind::Dir)
+    }
+}
+
+impl Default for InMemoryFileSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryFileSystem {
+    /// Create a filesystem that only contains the root directory `/`.
+    pub fn new() -> Self {
+        let mut nodes = BTreeMap::new();
+        nodes.insert(
+            "/".to_string(),
+            Node::new(NodeKind::Dir, SystemTime::UNIX_EPOCH),
+        );
+
+        Self {
+            nodes: RwLock::new(nodes),
+        }
+    }
+
+    /// Create a filesystem from a `tree`-like diagram.
+    ///
+    /// The first non-empty line is the absolute path the diagram is rooted at. Every
+    /// following line describes an entry with the usual `├── `, `└── ` and `│   `
+    /// drawing characters, four columns per level. Entries ending with `/` or having
+    /// children are directories, other entries are empty files. Anything after two
+    /// consecutive spaces is ignored, which allows annotations like `<- selected`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the diagram is not rooted at an absolute path, or if an entry is
+    /// indented deeper than its parent allows.
+    pub fn from_tree(tree: &str) -> Self {
+        let mut lines = tree.lines().filter(|line| !line.trim().is_empty());
+
+        let root_line = lines.next().unwrap_or("/");
+        let root_indent = root_line.chars().take_while(|c| c.is_whitespace()).count();
+        let root = root_line.trim();
+        assert!(
+            root.starts_with('/'),
+            "tree must be rooted at an absolute path"
+        );
+
+        let mut fs = Self::new().with_dir(root);
+        let mut ancestors = vec![normalize(root)];
+
+        for line in lines {
+            let chars: Vec<char> = line.chars().collect();
+            let Some(connector) = chars
+                .windows(3)
+                .position(|w| matches!(w[0], '├' | '└') && w[1] == '─' && w[2] == '─')
+            else {
+                continue;
+            };
+
+            let depth = connector.saturating_sub(root_indent) / 4 + 1;
+            assert!(
+                depth <= ancestors.len(),
+                "entry `{}` is indented too deep",
+                line
+            );
+            ancestors.truncate(depth);
+
+            let entry: String = chars[connector + 3..].iter().collect();
+            let entry = entry.trim_start();
+            let entry = entry.split("  ").next().unwrap_or(entry).trim_end();
+
+            let path = join(&ancestors[depth - 1], entry.trim_end_matches('/'));
+            fs = if entry.ends_with('/') {
+                fs.with_dir(&path)
+            } else {
+                fs.with_file(&path, "")
+            };
+            ancestors.push(path);
+        }
+
+        // Entries that turned out to have children are directories
+        {
+            let mut nodes = fs.write();
+            let parents: Vec<String> = nodes.keys().filter_map(|path| parent(path)).collect();
+            for parent in parents {
+                if let Some(node) = nodes.get_mut(&parent) {
+                    if !node.is_dir() {
+                        node.kind = NodeKind::Dir;
+                        node.permissions = FilePermissions::from_mode(0o755);
+                    }
+                }
+            }
+        }
+
+        fs
+    }
+
+    /// Add a directory, creating its missing parents.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn with_dir(self, path: &str) -> Self {
+        self.insert(path, NodeKind::Dir)
+    }
+
+    /// Add a file with the given contents, creating its missing parents.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn with_file<C: Into<Vec<u8>>>(self, path: &str, contents: C) -> Self {
+        self.insert(path, NodeKind::File(contents.into()))
+    }
+
+    /// Add a symbolic link pointing to `target`, creating its missing parents.
+    ///
+    /// Relative targets are resolved from the directory containing the link.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn with_symlink(self, path: &str, target: &str) -> Self {
+        self.insert(path, NodeKind::Symlink(target.to_string()))
+    }
+
+    /// Set the permissions of an existing entry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry does not exist.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn with_permissions(self, path: &str, permissions: FilePermissions) -> Self {
+        self.update(path, |node| node.permissions = permissions)
+    }
+
+    /// Set the last modified time of an existing entry.
+    ///
+    /// Entries added with the builder methods are dated from the Unix epoch by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry does not exist.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn with_modified(self, path: &str, modified: SystemTime) -> Self {
+        self.update(path, |node| node.modified = modified)
+    }
+
+    /// Set whether an existing entry is hidden.
+    ///
+    /// By default, entries whose name starts with a dot are hidden.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry does not exist.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn with_hidden(self, path: &str, hidden: bool) -> Self {
+        self.update(path, |node| node.hidden = Some(hidden))
+    }
+
+    /// Returns the contents of the file at `path`, following symbolic links.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path does not exist or is a directory.
+    pub fn contents(&self, path: &str) -> Result<Vec<u8>> {
+        let nodes = self.read();
+        let resolved = resolve(&nodes, path)?;
+
+        match &nodes[&resolved].kind {
+            NodeKind::File(contents) => Ok(contents.clone()),
+            _ => Err(is_a_directory(path)),
+        }
+    }
+
+    fn insert(self, path: &str, kind: NodeKind) -> Self {
+        {
+            let path = normalize(path);
+            let mut nodes = self.write();
+
+            let mut ancestor = parent(&path);
+            while let Some(dir) = ancestor {
+                nodes
+                    .entry(dir.clone())
+                    .or_insert_with(|| Node::new(NodeKind::Dir, SystemTime::UNIX_EPOCH));
+                ancestor = parent(&dir);
+            }
+
+            nodes.insert(path, Node::new(kind, SystemTime::UNIX_EPOCH));
+        }
+
+        self
+    }
+
+    fn update(self, path: &str, f: impl FnOnce(&mut Node)) -> Self {
+        {
+            let mut nodes = self.write();
+            let node = nodes
+                .get_mut(&normalize(path))
+                .unwrap_or_else(|| panic!("no entry at {}", path));
+            f(node);
+        }
+
+        self
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, BTreeMap<String, Node>> {
+        self.nodes.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, BTreeMap<String, Node>> {
+        self.nodes.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl FileSystem for InMemoryFileSystem {
+    async fn read_dir(&self, path: &str) -> Result<Vec<FileEntry>> {
+        let nodes = self.read();
+        let dir = resolve(&nodes, path)?;
+
+        let node = &nodes[&dir];
+        if !node.is_dir() {
+            return Err(not_a_directory(path));
+        }
+        if !node.permissions.user_read {
+            return Err(permission_denied(path));
+        }
+
+        let requested = normalize(path);
+        let mut entries = Vec::new();
+
+        for (child, child_node) in children(&nodes, &dir) {
+            let name = file_name(child).to_string();
+            let entry_path = join(&requested, &name);
+
+            let (target, symlink_target) = match &child_node.kind {
+                NodeKind::Symlink(target) => match resolve(&nodes, child) {
+                    Ok(resolved) => (&nodes[&resolved], Some(target.clone())),
+                    Err(_) => {
+                        // Skip broken symlinks, like the local implementation does
+                        tracing::error!("Broken symlink: {}", entry_path);
+                        continue;
+                    }
+                },
+                _ => (child_node, None),
+            };
+
+            let is_dir = target.is_dir();
+            let is_hidden = child_node.hidden.unwrap_or_else(|| name.starts_with('.'));
+
+            entries.push(FileEntry {
+                name: if is_dir { format!("{}/", name) } else { name },
+                path: entry_path,
+                is_dir,
+                is_file: matches!(target.kind, NodeKind::File(_)),
+                is_hidden,
+                size: match &target.kind {
+                    NodeKind::File(contents) => Some(contents.len() as u64),
+                    _ => None,
+                },
+                modified: Some(target.modified),
+                permissions: Some(target.permissions),
+                is_symlink: symlink_target.is_some(),
+                symlink_target,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    async fn exists(&self, path: &str) -> Result<bool> {
+        Ok(resolve(&self.read(), path).is_ok())
+    }
+
+    async fn is_dir(&self, path: &str) -> Result<bool> {
+        let nodes = self.read();
+        let resolved = resolve(&nodes, path)?;
+
+        Ok(nodes[&resolved].is_dir())
+    }
+
+    async fn canonicalize(&self, path: &str) -> Result<String> {
+        resolve(&self.read(), path)
+    }
+
+    fn parent(&self, path: &str) -> Option<String> {
+        parent(&normalize(path))
+    }
+
+    async fn read_chunk(&self, path: &str, offset: u64, len: usize) -> Result<Vec<u8>> {
+        let nodes = self.read();
+        let node = &nodes[&resolve(&nodes, path)?];
+
+        match &node.kind {
+            NodeKind::File(_) if !node.permissions.user_read => Err(permission_denied(path)),
+            NodeKind::File(contents) => {
+                let start = usize::try_from(offset)
+                    .map_or(contents.len(), |offset| offset.min(contents.len()));
+                let end = start.saturating_add(len).min(contents.len());
+                Ok(contents[start..end].to_vec())
+            }
+            _ => Err(is_a_directory(path)),
+        }
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        let mut nodes = self.write();
+        let location = locate(&nodes, path)?;
+
+        if nodes[&location].is_dir() {
+            return Err(is_a_directory(path));
+        }
+        check_writable_parent(&nodes, &location)?;
+
+        nodes.remove(&location);
+        Ok(())
+    }
+
+    async fn delete_dir_all(&self, path: &str) -> Result<()> {
+        let mut nodes = self.write();
+        let location = locate(&nodes, path)?;
+
+        // Like `std::fs::remove_dir_all`, a symbolic link is removed, not followed
+        if matches!(nodes[&location].kind, NodeKind::Symlink(_)) {
+            check_writable_parent(&nodes, &location)?;
+            nodes.remove(&location);
+            return Ok(());
+        }
+        if !nodes[&location].is_dir() {
+            return Err(not_a_directory(path));
+        }
+        if parent(&location).is_none() {
+            return Err(permission_denied(path));
+        }
+        check_writable_parent(&nodes, &location)?;
+
+        for descendant in subtree(&nodes, &location) {
+            nodes.remove(&descendant);
+        }
+        Ok(())
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> Result<()> {
+        let mut nodes = self.write();
+        let source = locate(&nodes, from)?;
+        let target = prepare_target(&nodes, &source, to)?;
+        check_writable_parent(&nodes, &source)?;
+
+        for old in subtree(&nodes, &source) {
+            let node = nodes.remove(&old).expect("subtree entries exist");
+            nodes.insert(format!("{}{}", target, &old[source.len()..]), node);
+        }
+        Ok(())
+    }
+
+    async fn copy(&self, from: &str, to: &str) -> Result<()> {
+        let mut nodes = self.write();
+        let source = resolve(&nodes, from)?;
+        // Unlike a rename, a copy never replaces an existing file
+        if locate(&nodes, to).is_ok() {
+            return Err(already_exists(to));
+        }
+        let target = prepare_target(&nodes, &source, to)?;
+        let now = SystemTime::now();
+
+        for old in subtree(&nodes, &source) {
+            let mut node = nodes[&old].clone();
+            node.modified = now;
+            nodes.insert(format!("{}{}", target, &old[source.len()..]), node);
+        }
+        Ok(())
+    }
+
+    async fn move_to(&self, from: &str, to: &str) -> Result<()> {
+        // Everything lives on the same "device"
+        self.rename(from, to).await
+    }
+
+    async fn create_dir(&self, path: &str) -> Result<()> {
+        let mut nodes = self.write();
+        let location = locate_new(&nodes, path)?;
+
+        nodes.insert(location, Node::new(NodeKind::Dir, SystemTime::now()));
+        Ok(())
+    }
+
+    async fn create_dir_all(&self, path: &str) -> Result<()> {
+        let mut nodes = self.write();
+        let mut missing = Vec::new();
+        let mut current = normalize(path);
+
+        loop {
+            match resolve(&nodes, &current) {
+                Ok(resolved) if nodes[&resolved].is_dir() => break,
+                Ok(_) => return Err(not_a_directory(&current)),
+                Err(_) => {
+                    missing.push(current.clone());
+                    current = parent(&current).ok_or_else(|| not_found(path))?;
+                }
+            }
+        }
+
+        for dir in missing.into_iter().rev() {
+            let location = locate_new(&nodes, &dir)?;
+            nodes.insert(location, Node::new(NodeKind::Dir, SystemTime::now()));
+        }
+        Ok(())
+    }
+
+    async fn set_permissions(&self, path: &str, permissions: FilePermissions) -> Result<()> {
+        let mut nodes = self.write();
+        let resolved = resolve(&nodes, path)?;
+
+        if let Some(node) = nodes.get_mut(&resolved) {
+            node.permissions = permissions;
+        }
+        Ok(())
+    }
+
+    async fn create_file(&self, path: &str) -> Result<()> {
+        let mut nodes = self.write();
+        let location = locate_new(&nodes, path)?;
+
+        nodes.insert(
+            location,
+            Node::new(NodeKind::File(Vec::new()), SystemTime::now()),
+        );
+        Ok(())
+    }
+
+    async fn write_file(&self, path: &str, contents: &[u8]) -> Result<()> {
+        let mut nodes = self.write();
+
+        let Ok(location) = resolve(&nodes, path) else {
+            let location = locate_new(&nodes, path)?;
+            nodes.insert(
+                location,
+                Node::new(NodeKind::File(contents.to_vec()), SystemTime::now()),
+            );
+            return Ok(());
+        };
+
+        let node = nodes.get_mut(&location).expect("resolved paths exist");
+        if !node.permissions.user_write {
+            return Err(permission_denied(path));
+        }
+        match &mut node.kind {
+            NodeKind::File(existing) => *existing = contents.to_vec(),
+            _ => return Err(is_a_directory(path)),
+        }
+        node.modified = SystemTime::now();
+
+        Ok(())
+    }
+}
+
+/// Normalize a path to an absolute path without `.`, `..`, empty or trailing components.
+fn normalize(path: &str) -> String {
+    let mut components: Vec<&str> = Vec::new();
+
+    for component in path.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop();
+            }
+            component => components.push(component),
+        }
+    }
+
+    format!("/{}", components.join("/"))
+}
+
+fn join(dir: &str, name: &str) -> String {
+    normalize(&format!("{}/{}", dir, name))
+}
+
+fn parent(path: &str) -> Option<String> {
+    if path == "/" {
+        return None;
+    }
+
+    path.rfind('/').map(|idx| match idx {
+        0 => "/".to_string(),
+        idx => path[..idx].to_string(),
+    })
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Resolve every symbolic link of `path`, returning the canonical path of an existing entry.
+fn resolve(nodes: &BTreeMap<String, Node>, path: &str) -> Result<String> {
+    let mut pending: VecDeque<String> = components(&normalize(path)).collect();
+    let mut resolved = "/".to_string();
+    let mut hops = 0;
+
+    while let Some(component) = pending.pop_front() {
+        let candidate = join(&resolved, &component);
+
+        match nodes.get(&candidate).map(|node| &node.kind) {
+            Some(NodeKind::Symlink(target)) => {
+                hops += 1;
+                if hops > MAX_SYMLINK_HOPS {
+                    return Err(Error::other(format!(
+                        "Too many levels of symbolic links: {}",
+                        path
+                    )));
+                }
+
+                let target = if target.starts_with('/') {
+                    normalize(target)
+                } else {
+                    join(&resolved, target)
+                };
+
+                let mut rest: VecDeque<String> = components(&target).collect();
+                rest.extend(pending.drain(..));
+                pending = rest;
+                resolved = "/".to_string();
+            }
+            Some(NodeKind::Dir) => resolved = candidate,
+            Some(NodeKind::File(_)) if pending.is_empty() => resolved = candidate,
+            Some(NodeKind::File(_)) => return Err(not_a_directory(path)),
+            None => return Err(not_found(path)),
+        }
+    }
+
+    Ok(resolved)
+}
+
+fn components(path: &str) -> impl Iterator<Item = String> + '_ {
+    path.split('/')
+        .filter(|component| !component.is_empty())
+        .map(String::from)
+}
+
+/// Returns the location of the entry at `path` itself, following symbolic links
+/// in its parents only, so links can be removed or renamed rather than their targets.
+fn locate(nodes: &BTreeMap<String, Node>, path: &str) -> Result<String> {
+    let path = normalize(path);
+    let Some(dir) = parent(&path) else {
+        return Ok(path);
+    };
+
+    let location = join(&resolve(nodes, &dir)?, file_name(&path));
+    if nodes.contains_key(&location) {
+        Ok(location)
+    } else {
+        Err(not_found(&path))
+    }
+}
+
+/// Returns where a new entry at `path` would be stored, checking that its parent
+/// is a writable directory and that nothing exists there yet.
+fn locate_new(nodes: &BTreeMap<String, Node>, path: &str) -> Result<String> {
+    let path = normalize(path);
+    let dir = parent(&path).ok_or_else(|| already_exists(&path))?;
+    let location = join(&resolve(nodes, &dir)?, file_name(&path));
+
+    if !nodes[&parent(&location).expect("location has a parent")].is_dir() {
+        return Err(not_a_directory(&dir));
+    }
+    if nodes.contains_key(&location) {
+        return Err(already_exists(&path));
+    }
+    check_writable_parent(nodes, &location)?;
+
+    Ok(location)
+}
+
+/// Returns where `source` would be stored once renamed or copied to `to`. An existing
+/// file at `to` is replaced, like on the local filesystem.
+fn prepare_target(nodes: &BTreeMap<String, Node>, source: &str, to: &str) -> Result<String> {
+    let target = match locate(nodes, to) {
+        Ok(existing) if nodes[&existing].is_dir() => return Err(already_exists(to)),
+        Ok(existing) => {
+            check_writable_parent(nodes, &existing)?;
+            existing
+        }
+        Err(_) => locate_new(nodes, to)?,
+    };
+
+    if target == source || target.starts_with(&format!("{}/", source)) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("Cannot move or copy {} into itself", source),
+        ));
+    }
+
+    Ok(target)
+}
+
+fn check_writable_parent(nodes: &BTreeMap<String, Node>, location: &str) -> Result<()> {
+    match parent(location).and_then(|dir| nodes.get(&dir)) {
+        Some(dir) if !dir.permissions.user_write => Err(permission_denied(location)),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the direct children of the directory `dir`.
+fn children<'a>(nodes: &'a BTreeMap<String, Node>, dir: &str) -> Vec<(&'a String, &'a Node)> {
+    let prefix = if dir == "/" {
+        "/".to_string()
+    } else {
+        format!("{}/", dir)
+    };
+
+    nodes
+        .range(prefix.clone()..)
+        .take_while(|(path, _)| path.starts_with(&prefix))
+        .filter(|(path, _)| path.len() > prefix.len() && !path[prefix.len()..].contains('/'))
+        .collect()
+}
+
+/// Returns `path` and all its descendants.
+fn subtree(nodes: &BTreeMap<String, Node>, path: &str) -> Vec<String> {
+    let prefix = format!("{}/", path);
+
+    std::iter::once(path.to_string())
+        .chain(
+            nodes
+                .range(prefix.clone()..)
+                .take_while(|(child, _)| child.starts_with(&prefix))
+                .map(|(child, _)| child.clone()),
+        )
+        .collect()
+}
+
+fn not_found(path: &str) -> Error {
+    Error::new(
+        ErrorKind::NotFound,
+        format!("No such file or directory: {}", path),
+    )
+}
+
+fn not_a_directory(path: &str) -> Error {
+    Error::new(
+        ErrorKind::NotADirectory,
+        format!("Not a directory: {}", path),
+    )
+}
+
+fn is_a_directory(path: &str) -> Error {
+    Error::new(ErrorKind::IsADirectory, format!("Is a directory: {}", path))
+}
+
+fn already_exists(path: &str) -> Error {
+    Error::new(ErrorKind::AlreadyExists, format!("File exists: {}", path))
+}
+
+fn permission_denied(path: &str) -> Error {
+    Error::new(
+        ErrorKind::PermissionDenied,
+        format!("Permission denied: {}", path),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(entries: &[FileEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.name.as_str()).collect()
+    }
+
+    #[test]
+    fn tree_diagram() {
+        let fs = InMemoryFileSystem::from_tree(
+            "
+            /home/user
+            ├── .config/
+            ├── projects
+            │   └── demo
+            │       └── Cargo.toml  <- manifest
+            └── notes.txt
+            ",
+        );
+
+        let nodes = fs.read();
+        assert!(nodes["/home/user/.config"].is_dir());
+        assert!(nodes["/home/user/projects"].is_dir());
+        assert!(nodes["/home/user/projects/demo"].is_dir());
+        assert!(!nodes["/home/user/projects/demo/Cargo.toml"].is_dir());
+        assert!(!nodes["/home/user/notes.txt"].is_dir());
+    }
+
+    #[tokio::test]
+    async fn read_dir_attributes() {
+        let modified = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000);
+        let fs = InMemoryFileSystem::new()
+            .with_file("/data/b.txt", "hello")
+            .with_modified("/data/b.txt", modified)
+            .with_file("/data/.env", "")
+            .with_dir("/data/a")
+            .with_hidden("/data/a", true)
+            .with_symlink("/data/link", "a")
+            .with_symlink("/data/broken", "missing");
+
+        let mut entries = fs.read_dir("/data").await.unwrap();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(names(&entries), [".env", "a/", "b.txt", "link/"]);
+
+        assert!(entries[0].is_hidden);
+        assert!(entries[1].is_hidden);
+        assert!(!entries[2].is_hidden);
+
+        assert!(entries[3].is_symlink);
+        assert_eq!(entries[3].symlink_target.as_deref(), Some("a"));
+        assert_eq!(entries[3].path, "/data/link");
+
+        assert_eq!(entries[2].size, Some(5));
+        assert_eq!(entries[2].modified, Some(modified));
+        assert_eq!(
+            entries[2].permissions,
+            Some(FilePermissions::from_mode(0o644))
+        );
+    }
+
+    #[tokio::test]
+    async fn symlinks_are_followed() {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/real/file.txt", "content")
+            .with_symlink("/link", "/real")
+            .with_symlink("/loop", "/loop");
+
+        assert_eq!(names(&fs.read_dir("/link").await.unwrap()), ["file.txt"]);
+        assert_eq!(
+            fs.canonicalize("/link/file.txt").await.unwrap(),
+            "/real/file.txt"
+        );
+        assert!(fs.is_dir("/link").await.unwrap());
+        assert!(fs.read_dir("/loop").await.is_err());
+
+        assert_eq!(fs.read_chunk("/link/file.txt", 3, 2).await.unwrap(), b"te");
+        assert_eq!(fs.read_chunk("/link/file.txt", 5, 10).await.unwrap(), b"nt");
+        assert!(fs
+            .read_chunk("/link/file.txt", 50, 10)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(fs.read_chunk("/link", 0, 10).await.is_err());
+
+        // Deleting a link removes the link, not its target
+        fs.delete("/link").await.unwrap();
+        assert!(fs.exists("/real/file.txt").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn mutations() {
+        let fs = InMemoryFileSystem::new().with_file("/src/main.rs", "fn main() {}");
+
+        fs.create_dir_all("/dst/nested").await.unwrap();
+        fs.copy("/src", "/dst/nested/src").await.unwrap();
+        assert_eq!(
+            fs.contents("/dst/nested/src/main.rs").unwrap(),
+            b"fn main() {}"
+        );
+
+        fs.rename("/src/main.rs", "/src/lib.rs").await.unwrap();
+        assert_eq!(names(&fs.read_dir("/src").await.unwrap()), ["lib.rs"]);
+
+        fs.create_file("/src/mod.rs").await.unwrap();
+        assert_eq!(
+            fs.create_file("/src/mod.rs").await.unwrap_err().kind(),
+            ErrorKind::AlreadyExists
+        );
+        assert_eq!(
+            fs.copy("/src", "/src/inner").await.unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+
+        fs.write_file("/src/mod.rs", b"mod lib;").await.unwrap();
+        fs.write_file("/src/new.rs", b"").await.unwrap();
+        assert_eq!(fs.contents("/src/mod.rs").unwrap(), b"mod lib;");
+        assert_eq!(
+            fs.write_file("/src", b"").await.unwrap_err().kind(),
+            ErrorKind::IsADirectory
+        );
+
+        fs.delete_dir_all("/dst").await.unwrap();
+        assert!(!fs.exists("/dst/nested/src/main.rs").await.unwrap());
+        assert_eq!(names(&fs.read_dir("/").await.unwrap()), ["src/"]);
+    }
+
+    #[tokio::test]
+    async fn rename_copy_move_errors() {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/src/main.rs", "fn main() {}")
+            .with_file("/dst/src/lib.rs", "")
+            .with_file("/notes.txt", "notes")
+            .with_symlink("/link", "/src");
+
+        // A directory is never replaced, and a copy never replaces anything
+        for result in [
+            fs.rename("/src", "/dst/src").await,
+            fs.copy("/src", "/dst/src").await,
+            fs.move_to("/src", "/dst/src").await,
+            fs.copy("/notes.txt", "/dst").await,
+            fs.copy("/notes.txt", "/src/main.rs").await,
+        ] {
+            assert_eq!(result.unwrap_err().kind(), ErrorKind::AlreadyExists);
+        }
+        assert!(fs.exists("/src/main.rs").await.unwrap());
+        assert_eq!(fs.contents("/src/main.rs").unwrap(), b"fn main() {}");
+
+        // The guard sees through symbolic links and `..`
+        for to in ["/src/copy", "/link/copy", "/dst/../src/copy"] {
+            assert_eq!(
+                fs.copy("/src", to).await.unwrap_err().kind(),
+                ErrorKind::InvalidInput,
+                "{to}"
+            );
+            assert_eq!(
+                fs.move_to("/src", to).await.unwrap_err().kind(),
+                ErrorKind::InvalidInput,
+                "{to}"
+            );
+        }
+        assert!(!fs.exists("/src/copy").await.unwrap());
+
+        // The in-memory filesystem has no devices: a move is always a rename
+        fs.move_to("/src", "/moved").await.unwrap();
+        assert!(!fs.exists("/src").await.unwrap());
+        assert_eq!(fs.contents("/moved/main.rs").unwrap(), b"fn main() {}");
+    }
+
+    #[tokio::test]
+    async fn permissions_are_enforced() {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/locked/secret.txt", "")
+            .with_permissions("/locked", FilePermissions::from_mode(0o500))
+            .with_dir("/private")
+            .with_permissions("/private", FilePermissions::from_mode(0o300));
+
+        let err = fs.read_dir("/private").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        let err = fs.delete("/locked/secret.txt").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        let err = fs.create_file("/locked/new.txt").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        fs.set_permissions("/locked", FilePermissions::from_mode(0o700))
+            .await
+            .unwrap();
+        fs.create_file("/locked/new.txt").await.unwrap();
+
+        let entries = fs.read_dir("/").await.unwrap();
+        assert_eq!(entries[0].permissions.unwrap().mode(), 0o700);
+    }
+
+    #[tokio::test]
+    async fn delete_dir_all_removes_symlinks_without_following_them() {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/data/keep.txt", "")
+            .with_symlink("/link", "/data");
+
+        fs.delete_dir_all("/link").await.unwrap();
+        assert!(!fs.exists("/link").await.unwrap());
+        assert!(fs.exists("/data/keep.txt").await.unwrap());
+    }
+}
@@ -3,12 +3,37 @@
 //! This module provides a trait-based abstraction for filesystem operations,
 //! allowing the file explorer to work with both local filesystems and remote
 //! filesystems (like SFTP) through a common interface.
+//!
+//! [`InMemoryFileSystem`] never touches the disk, which makes it convenient to
+//! test an explorer integration or to build demos.
+//!
+//! [`ReadOnly`], [`Chroot`] and [`Cached`] wrap any other implementation to
+//! respectively forbid modifications, confine paths to a directory and cache
+//! directory listings. They can be stacked, e.g.
+//! `Cached<ReadOnly<Chroot<LocalFileSystem>>>`.
 
-use std::{future::Future, io::Result};
+use std::{
+    future::Future,
+    io::{Error, ErrorKind, Result},
+};
 
+#[cfg(feature = "archive")]
+mod archive;
+mod cached;
+mod chroot;
 mod local;
+mod memory;
+mod read_only;
+mod trash;
 
+#[cfg(feature = "archive")]
+pub use archive::{ArchiveFileSystem, ArchiveFormat};
+pub use cached::Cached;
+pub use chroot::Chroot;
 pub use local::LocalFileSystem;
+pub use memory::InMemoryFileSystem;
+pub use read_only::ReadOnly;
+pub use trash::{Trash, TrashItem};
 
 /// Unix-style file permissions representation.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -35,7 +60,6 @@ pub struct FilePermissions {
 
 impl FilePermissions {
     /// Create permissions from a Unix mode value (e.g., 0o755)
-    #[cfg(unix)]
     pub fn from_mode(mode: u32) -> Self {
         Self {
             user_read: mode & 0o400 != 0,
@@ -50,6 +74,24 @@ impl FilePermissions {
         }
     }
 
+    /// Convert permissions back to a Unix mode value (e.g., 0o755)
+    pub fn mode(&self) -> u32 {
+        [
+            (self.user_read, 0o400),
+            (self.user_write, 0o200),
+            (self.user_execute, 0o100),
+            (self.group_read, 0o040),
+            (self.group_write, 0o020),
+            (self.group_execute, 0o010),
+            (self.others_read, 0o004),
+            (self.others_write, 0o002),
+            (self.others_execute, 0o001),
+        ]
+        .into_iter()
+        .filter(|(set, _)| *set)
+        .fold(0, |mode, (_, bit)| mode | bit)
+    }
+
     /// Format permissions as a Unix-style string (e.g., "rwxr-xr-x")
     pub fn to_string(&self, _is_dir: bool) -> String {
         format!(
@@ -98,12 +140,57 @@ pub struct FileEntry {
 /// implementations (local, SFTP, etc.) through a common interface.
 ///
 /// All methods are async to support both local and remote filesystem operations.
+///
+/// Only listing the directories is required: [`read_chunk`](FileSystem::read_chunk) and the
+/// methods modifying the filesystem, apart from [`delete`](FileSystem::delete), fail with
+/// [`ErrorKind::Unsupported`] unless they are implemented.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::{ErrorKind, Result};
+/// use ratatui_async_explorer::{FileEntry, FileSystem};
+///
+/// /// A filesystem with nothing but an empty root directory.
+/// struct Empty;
+///
+/// impl FileSystem for Empty {
+///     async fn read_dir(&self, _path: &str) -> Result<Vec<FileEntry>> {
+///         Ok(Vec::new())
+///     }
+///
+///     async fn exists(&self, path: &str) -> Result<bool> {
+///         Ok(path == "/")
+///     }
+///
+///     async fn is_dir(&self, path: &str) -> Result<bool> {
+///         Ok(path == "/")
+///     }
+///
+///     async fn canonicalize(&self, path: &str) -> Result<String> {
+///         Ok(path.to_string())
+///     }
+///
+///     fn parent(&self, _path: &str) -> Option<String> {
+///         None
+///     }
+///
+///     async fn delete(&self, _path: &str) -> Result<()> {
+///         Err(ErrorKind::NotFound.into())
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let error = Empty.create_dir("/docs").await.unwrap_err();
+/// assert_eq!(error.kind(), ErrorKind::Unsupported);
+/// # })
+/// ```
 pub trait FileSystem: Send + Sync {
     /// Read the contents of a directory at the given path.
     ///
     /// Returns a vector of `FileEntry` objects representing the files and
-    /// directories in the specified path. The entries should be sorted with
-    /// directories first, then alphabetically.
+    /// directories in the specified path. The entries may be returned in any
+    /// order, [`FileExplorer`](crate::FileExplorer) sorts them itself.
     ///
     /// # Errors
     ///
@@ -137,6 +224,168 @@ pub trait FileSystem: Send + Sync {
     /// Returns `None` if the path is the root directory or has no parent.
     fn parent(&self, path: &str) -> Option<String>;
 
+    /// Read up to `len` bytes of the file at the given path, starting at byte `offset`.
+    ///
+    /// Fewer bytes are returned when the end of the file is reached, and none past it, so
+    /// large or remote files can be read chunk by chunk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path does not exist, is a directory, or cannot be read.
+    fn read_chunk(
+        &self,
+        _path: &str,
+        _offset: u64,
+        _len: usize,
+    ) -> impl Future<Output = Result<Vec<u8>>> + Send {
+        async { Err(unsupported("read_chunk")) }
+    }
+
     /// Delete a file at the given path.
     fn delete(&self, path: &str) -> impl Future<Output = Result<()>> + Send;
+
+    /// Recursively delete a directory and everything it contains.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is not a directory or one of its entries
+    /// cannot be removed.
+    fn delete_dir_all(&self, _path: &str) -> impl Future<Output = Result<()>> + Send {
+        async { Err(unsupported("delete_dir_all")) }
+    }
+
+    /// Rename the file or directory at `from` to `to`.
+    ///
+    /// Both paths are expected to be on the same device. Use
+    /// [`move_to`](FileSystem::move_to) when that is not guaranteed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `from` does not exist or `to` cannot be written.
+    fn rename(&self, _from: &str, _to: &str) -> impl Future<Output = Result<()>> + Send {
+        async { Err(unsupported("rename")) }
+    }
+
+    /// Copy the file or directory at `from` to `to`.
+    ///
+    /// Directories are copied recursively.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `from` cannot be read, `to` already exists or cannot be
+    /// written, or `to` is located inside `from`.
+    fn copy(&self, _from: &str, _to: &str) -> impl Future<Output = Result<()>> + Send {
+        async { Err(unsupported("copy")) }
+    }
+
+    /// Move the file or directory at `from` to `to`.
+    ///
+    /// Unlike [`rename`](FileSystem::rename), this must also work across devices,
+    /// typically by falling back to a copy followed by a delete.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `from` does not exist or `to` cannot be written.
+    fn move_to(&self, _from: &str, _to: &str) -> impl Future<Output = Result<()>> + Send {
+        async { Err(unsupported("move_to")) }
+    }
+
+    /// Create a new, empty directory at the given path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path already exists or its parent does not exist.
+    fn create_dir(&self, _path: &str) -> impl Future<Output = Result<()>> + Send {
+        async { Err(unsupported("create_dir")) }
+    }
+
+    /// Create a directory and all of its missing parents.
+    ///
+    /// Succeeds without doing anything if the directory already exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if one of the components exists and is not a directory,
+    /// or cannot be created.
+    fn create_dir_all(&self, _path: &str) -> impl Future<Output = Result<()>> + Send {
+        async { Err(unsupported("create_dir_all")) }
+    }
+
+    /// Create a new, empty file at the given path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path already exists or its parent does not exist.
+    fn create_file(&self, _path: &str) -> impl Future<Output = Result<()>> + Send {
+        async { Err(unsupported("create_file")) }
+    }
+
+    /// Write `contents` to the file at the given path, creating it if it does not exist
+    /// and replacing its contents otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is a directory, its parent does not exist, or the
+    /// file cannot be written.
+    fn write_file(&self, _path: &str, _contents: &[u8]) -> impl Future<Output = Result<()>> + Send {
+        async { Err(unsupported("write_file")) }
+    }
+
+    /// Change the permissions of the file or directory at the given path.
+    ///
+    /// Symbolic links are followed. Filesystems without Unix permissions may only
+    /// honor part of them, e.g. the write permissions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path does not exist or its permissions cannot be changed.
+    fn set_permissions(
+        &self,
+        _path: &str,
+        _permissions: FilePermissions,
+    ) -> impl Future<Output = Result<()>> + Send {
+        async { Err(unsupported("set_permissions")) }
+    }
+
+    /// Returns whether the directory at the given path lives on the local disk, so that
+    /// programs such as `git` can be run in it.
+    ///
+    /// Defaults to `false`, e.g. for in-memory filesystems or the inside of archives.
+    fn is_local(&self, _path: &str) -> impl Future<Output = bool> + Send {
+        async { false }
+    }
+}
+
+/// The error of the [`FileSystem`] methods left unimplemented.
+fn unsupported(operation: &str) -> Error {
+    Error::new(
+        ErrorKind::Unsupported,
+        format!("{} is not supported by this filesystem", operation),
+    )
+}
+
+/// Reads the text of the file at `path` chunk by chunk, stopping after `limit` bytes.
+///
+/// Invalid UTF-8 sequences are replaced.
+pub(crate) async fn read_to_string<F: FileSystem>(
+    filesystem: &F,
+    path: &str,
+    limit: usize,
+) -> Result<String> {
+    const CHUNK_SIZE: usize = 64 * 1024;
+
+    let mut contents = Vec::new();
+    while contents.len() < limit {
+        let len = CHUNK_SIZE.min(limit - contents.len());
+        let chunk = filesystem
+            .read_chunk(path, contents.len() as u64, len)
+            .await?;
+        let done = chunk.len() < len;
+        contents.extend(chunk);
+        if done {
+            break;
+        }
+    }
+
+    Ok(String::from_utf8_lossy(&contents).into_owned())
 }
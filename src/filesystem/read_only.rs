@@ -0,0 +1,167 @@
+//! Read-only filesystem wrapper.
+
+use super::{FileEntry, FilePermissions, FileSystem};
+use std::io::{Error, ErrorKind, Result};
+
+/// A wrapper that forbids any modification of the wrapped filesystem.
+///
+/// Reads are forwarded to the inner filesystem, while every mutation (deleting,
+/// renaming, copying, moving or creating entries) fails with
+/// [`ErrorKind::PermissionDenied`] without reaching it.
+///
+/// # Examples
+///
+/// ```
+/// use ratatui_async_explorer::filesystem::{FileSystem, InMemoryFileSystem, ReadOnly};
+///
+/// # tokio_test::block_on(async {
+/// let fs = ReadOnly::new(InMemoryFileSystem::new().with_file("/notes.txt", "hello"));
+///
+/// assert!(fs.exists("/notes.txt").await.unwrap());
+/// assert_eq!(
+///     fs.delete("/notes.txt").await.unwrap_err().kind(),
+///     std::io::ErrorKind::PermissionDenied
+/// );
+/// # })
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ReadOnly<F> {
+    inner: F,
+}
+
+impl<F: FileSystem> ReadOnly<F> {
+    /// Wrap `inner` so that it can no longer be modified.
+    #[inline]
+    #[must_use]
+    pub const fn new(inner: F) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the wrapped filesystem.
+    #[inline]
+    #[must_use]
+    pub const fn inner(&self) -> &F {
+        &self.inner
+    }
+
+    /// Consumes the wrapper, returning the wrapped filesystem.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> F {
+        self.inner
+    }
+}
+
+impl<F: FileSystem> FileSystem for ReadOnly<F> {
+    async fn read_dir(&self, path: &str) -> Result<Vec<FileEntry>> {
+        self.inner.read_dir(path).await
+    }
+
+    async fn exists(&self, path: &str) -> Result<bool> {
+        self.inner.exists(path).await
+    }
+
+    async fn is_dir(&self, path: &str) -> Result<bool> {
+        self.inner.is_dir(path).await
+    }
+
+    async fn canonicalize(&self, path: &str) -> Result<String> {
+        self.inner.canonicalize(path).await
+    }
+
+    fn parent(&self, path: &str) -> Option<String> {
+        self.inner.parent(path)
+    }
+
+    async fn read_chunk(&self, path: &str, offset: u64, len: usize) -> Result<Vec<u8>> {
+        self.inner.read_chunk(path, offset, len).await
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        Err(denied(path))
+    }
+
+    async fn delete_dir_all(&self, path: &str) -> Result<()> {
+        Err(denied(path))
+    }
+
+    async fn rename(&self, from: &str, _to: &str) -> Result<()> {
+        Err(denied(from))
+    }
+
+    async fn copy(&self, _from: &str, to: &str) -> Result<()> {
+        Err(denied(to))
+    }
+
+    async fn move_to(&self, from: &str, _to: &str) -> Result<()> {
+        Err(denied(from))
+    }
+
+    async fn create_dir(&self, path: &str) -> Result<()> {
+        Err(denied(path))
+    }
+
+    async fn create_dir_all(&self, path: &str) -> Result<()> {
+        Err(denied(path))
+    }
+
+    async fn create_file(&self, path: &str) -> Result<()> {
+        Err(denied(path))
+    }
+
+    async fn write_file(&self, path: &str, _contents: &[u8]) -> Result<()> {
+        Err(denied(path))
+    }
+
+    async fn set_permissions(&self, path: &str, _permissions: FilePermissions) -> Result<()> {
+        Err(denied(path))
+    }
+
+    async fn is_local(&self, path: &str) -> bool {
+        self.inner.is_local(path).await
+    }
+}
+
+fn denied(path: &str) -> Error {
+    Error::new(
+        ErrorKind::PermissionDenied,
+        format!("Read-only filesystem: {}", path),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filesystem::InMemoryFileSystem;
+
+    #[tokio::test]
+    async fn mutations_never_reach_the_inner_filesystem() {
+        let fs = ReadOnly::new(
+            InMemoryFileSystem::new()
+                .with_dir("/docs")
+                .with_file("/docs/a.txt", "a"),
+        );
+
+        let results = [
+            fs.delete("/docs/a.txt").await,
+            fs.delete_dir_all("/docs").await,
+            fs.rename("/docs/a.txt", "/docs/b.txt").await,
+            fs.copy("/docs/a.txt", "/docs/b.txt").await,
+            fs.move_to("/docs/a.txt", "/b.txt").await,
+            fs.create_dir("/new").await,
+            fs.create_dir_all("/new/deep").await,
+            fs.create_file("/new.txt").await,
+            fs.write_file("/docs/a.txt", b"b").await,
+            fs.set_permissions("/docs", FilePermissions::from_mode(0o777))
+                .await,
+        ];
+
+        for result in results {
+            assert_eq!(result.unwrap_err().kind(), ErrorKind::PermissionDenied);
+        }
+
+        let entries = fs.read_dir("/").await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(fs.inner().contents("/docs/a.txt").unwrap(), b"a");
+    }
+}
@@ -0,0 +1,513 @@
+//! Trash can following the [FreeDesktop.org trash specification](https://specifications.freedesktop.org/trash-spec/trashspec-latest.html).
+
+use super::{read_to_string, FileSystem, LocalFileSystem};
+use chrono::{Local, NaiveDateTime, SubsecRound, TimeZone};
+use std::ffi::OsString;
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Format of the `DeletionDate` key of `.trashinfo` files.
+const DELETION_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Extension of the metadata files stored in the `info` directory of the trash.
+const TRASHINFO_EXTENSION: &str = ".trashinfo";
+
+/// The most bytes read from a `.trashinfo` file.
+const MAX_TRASHINFO_SIZE: usize = 64 * 1024;
+
+/// A trash can, on the local filesystem by default.
+///
+/// Trashed entries are moved into `<root>/files` and described by a `.trashinfo`
+/// file in `<root>/info`, so they can be listed and restored later, including by
+/// other applications implementing the specification.
+///
+/// Every operation goes through a [`FileSystem`], so a trash can also live on the
+/// same backend as the entries of a [`FileExplorer`](crate::FileExplorer), see
+/// [`Trash::with_fs`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use ratatui_async_explorer::filesystem::Trash;
+///
+/// # async fn example() -> std::io::Result<()> {
+/// let trash = Trash::home().expect("no home directory");
+///
+/// let item = trash.trash("/home/user/notes.txt").await?;
+/// assert!(trash.list().await?.contains(&item));
+///
+/// trash.restore(&item).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(educe::Educe)]
+#[educe(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Trash<F: FileSystem = LocalFileSystem> {
+    root: PathBuf,
+    #[educe(Debug(ignore), PartialEq(ignore), Hash(ignore))]
+    filesystem: Arc<F>,
+}
+
+/// An entry stored in a [`Trash`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TrashItem {
+    /// The name of the entry inside the `files` directory of the trash
+    pub name: String,
+    /// The absolute path the entry was trashed from
+    pub original_path: PathBuf,
+    /// When the entry was trashed
+    pub deletion_date: Option<SystemTime>,
+}
+
+impl Trash {
+    /// Create a trash can rooted at the given directory of the local filesystem.
+    ///
+    /// The `files` and `info` subdirectories are created on first use.
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self::with_fs(Arc::new(LocalFileSystem::new()), root)
+    }
+
+    /// Returns the home trash of the current user, `$XDG_DATA_HOME/Trash`, falling
+    /// back to `$HOME/.local/share/Trash` when `XDG_DATA_HOME` is not set.
+    ///
+    /// Returns `None` if neither variable is set.
+    pub fn home() -> Option<Self> {
+        Self::from_env_vars(std::env::var_os("XDG_DATA_HOME"), std::env::var_os("HOME"))
+    }
+
+    fn from_env_vars(xdg_data_home: Option<OsString>, home: Option<OsString>) -> Option<Self> {
+        // The specification requires XDG_DATA_HOME to be ignored unless absolute
+        let data_home = xdg_data_home
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| home.map(|home| PathBuf::from(home).join(".local/share")))?;
+
+        Some(Self::new(data_home.join("Trash")))
+    }
+}
+
+impl<F: FileSystem> Trash<F> {
+    /// Create a trash can rooted at the given directory of `filesystem`.
+    ///
+    /// The `files` and `info` subdirectories are created on first use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use ratatui_async_explorer::filesystem::{FileSystem, InMemoryFileSystem, Trash};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let fs = Arc::new(InMemoryFileSystem::new().with_file("/notes.txt", "hello"));
+    /// let trash = Trash::with_fs(Arc::clone(&fs), "/.trash");
+    ///
+    /// let item = trash.trash("/notes.txt").await.unwrap();
+    /// assert!(fs.exists("/.trash/files/notes.txt").await.unwrap());
+    ///
+    /// trash.restore(&item).await.unwrap();
+    /// assert_eq!(fs.contents("/notes.txt").unwrap(), b"hello");
+    /// # })
+    /// ```
+    pub fn with_fs<P: Into<PathBuf>>(filesystem: Arc<F>, root: P) -> Self {
+        Self {
+            root: root.into(),
+            filesystem,
+        }
+    }
+
+    /// Returns the filesystem the trash can lives on.
+    #[inline]
+    #[must_use]
+    pub fn filesystem(&self) -> &F {
+        &self.filesystem
+    }
+
+    /// Returns the root directory of the trash can.
+    #[inline]
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Move the file or directory at `path` to the trash.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` has no file name or cannot be moved into the trash.
+    pub async fn trash(&self, path: &str) -> Result<TrashItem> {
+        let original_path = std::path::absolute(path)?;
+        let file_name = original_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "Cannot trash a root directory"))?;
+
+        let fs = &*self.filesystem;
+        fs.create_dir_all(&lossy(&self.files_dir())).await?;
+        fs.create_dir_all(&lossy(&self.info_dir())).await?;
+
+        // The specification stores the date with a precision of one second
+        let deletion_date = Local::now().naive_local().trunc_subsecs(0);
+        let info = format!(
+            "[Trash Info]\nPath={}\nDeletionDate={}\n",
+            encode_path(&original_path.to_string_lossy()),
+            deletion_date.format(DELETION_DATE_FORMAT),
+        );
+
+        // Creating the info file first atomically reserves the name, as
+        // recommended by the specification.
+        let mut suffix = 1;
+        let name = loop {
+            let name = if suffix == 1 {
+                file_name.clone()
+            } else {
+                format!("{}.{}", file_name, suffix)
+            };
+            suffix += 1;
+
+            if fs.exists(&lossy(&self.files_dir().join(&name))).await? {
+                continue;
+            }
+
+            let info_path = lossy(&self.info_path(&name));
+            match fs.create_file(&info_path).await {
+                Ok(()) => {
+                    fs.write_file(&info_path, info.as_bytes()).await?;
+                    break name;
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        };
+
+        let trashed_path = self.files_dir().join(&name);
+        if let Err(e) = fs
+            .move_to(&lossy(&original_path), &lossy(&trashed_path))
+            .await
+        {
+            let _ = fs.delete(&lossy(&self.info_path(&name))).await;
+            return Err(e);
+        }
+
+        Ok(TrashItem {
+            name,
+            original_path,
+            deletion_date: to_system_time(deletion_date),
+        })
+    }
+
+    /// List the entries currently in the trash, oldest first.
+    ///
+    /// Entries whose metadata is missing or malformed are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `info` directory exists but cannot be read.
+    pub async fn list(&self) -> Result<Vec<TrashItem>> {
+        let fs = &*self.filesystem;
+        let entries = match fs.read_dir(&lossy(&self.info_dir())).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut items = Vec::new();
+        for entry in entries.into_iter().filter(|entry| !entry.is_dir) {
+            let info_name = entry.name;
+            let Some(name) = info_name.strip_suffix(TRASHINFO_EXTENSION) else {
+                continue;
+            };
+
+            if !fs.exists(&lossy(&self.files_dir().join(name))).await? {
+                tracing::error!("Orphaned trash info: {}", info_name);
+                continue;
+            }
+
+            let Ok(content) = read_to_string(fs, &entry.path, MAX_TRASHINFO_SIZE).await else {
+                continue;
+            };
+
+            match parse_trash_info(name, &content) {
+                Some(item) => items.push(item),
+                None => tracing::error!("Malformed trash info: {}", info_name),
+            }
+        }
+
+        items.sort_by(|a, b| {
+            a.deletion_date
+                .cmp(&b.deletion_date)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        Ok(items)
+    }
+
+    /// Move a trashed entry back to its original location.
+    ///
+    /// Missing parent directories of the original location are recreated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with [`ErrorKind::AlreadyExists`] if something now exists at
+    /// the original location, or if the entry cannot be moved back.
+    pub async fn restore(&self, item: &TrashItem) -> Result<()> {
+        let fs = &*self.filesystem;
+        if fs.exists(&lossy(&item.original_path)).await? {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("{} already exists", item.original_path.display()),
+            ));
+        }
+
+        if let Some(parent) = item.original_path.parent() {
+            fs.create_dir_all(&lossy(parent)).await?;
+        }
+
+        fs.move_to(
+            &lossy(&self.files_dir().join(&item.name)),
+            &lossy(&item.original_path),
+        )
+        .await?;
+
+        fs.delete(&lossy(&self.info_path(&item.name))).await
+    }
+
+    /// Permanently delete a trashed entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be removed.
+    pub async fn purge(&self, item: &TrashItem) -> Result<()> {
+        let fs = &*self.filesystem;
+        let trashed_path = lossy(&self.files_dir().join(&item.name));
+
+        // A broken symbolic link has no metadata to follow, but is no directory either
+        if fs.is_dir(&trashed_path).await.unwrap_or(false) {
+            fs.delete_dir_all(&trashed_path).await?;
+        } else {
+            fs.delete(&trashed_path).await?;
+        }
+
+        fs.delete(&lossy(&self.info_path(&item.name))).await
+    }
+
+    fn files_dir(&self) -> PathBuf {
+        self.root.join("files")
+    }
+
+    fn info_dir(&self) -> PathBuf {
+        self.root.join("info")
+    }
+
+    fn info_path(&self, name: &str) -> PathBuf {
+        self.info_dir()
+            .join(format!("{}{}", name, TRASHINFO_EXTENSION))
+    }
+}
+
+fn lossy(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+/// Parse the content of a `.trashinfo` file describing the trashed entry `name`.
+fn parse_trash_info(name: &str, content: &str) -> Option<TrashItem> {
+    let mut lines = content.lines().map(str::trim);
+    if lines.next()? != "[Trash Info]" {
+        return None;
+    }
+
+    let mut original_path = None;
+    let mut deletion_date = None;
+
+    for line in lines {
+        if line.starts_with('[') {
+            // Only the first group is defined by the specification
+            break;
+        }
+
+        match line.split_once('=') {
+            Some(("Path", value)) => original_path = Some(PathBuf::from(decode_path(value)?)),
+            Some(("DeletionDate", value)) => {
+                deletion_date = NaiveDateTime::parse_from_str(value, DELETION_DATE_FORMAT)
+                    .ok()
+                    .and_then(to_system_time);
+            }
+            _ => {}
+        }
+    }
+
+    Some(TrashItem {
+        name: name.to_string(),
+        original_path: original_path?,
+        deletion_date,
+    })
+}
+
+fn to_system_time(date: NaiveDateTime) -> Option<SystemTime> {
+    Local
+        .from_local_datetime(&date)
+        .earliest()
+        .map(SystemTime::from)
+}
+
+/// Percent-encode a path as required for the `Path` key, keeping `/` as is.
+fn encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-_.~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+
+    encoded
+}
+
+/// Decode a percent-encoded `Path` value. Returns `None` on malformed escapes.
+fn decode_path(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    Some(String::from_utf8_lossy(&decoded).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn write(path: &Path, content: &str) {
+        tokio::fs::write(path, content).await.unwrap();
+    }
+
+    #[test]
+    fn home_trash_location() {
+        let trash = Trash::from_env_vars(None, Some("/home/user".into())).unwrap();
+        assert_eq!(trash.root(), Path::new("/home/user/.local/share/Trash"));
+
+        let trash = Trash::from_env_vars(Some("/data".into()), Some("/home/user".into())).unwrap();
+        assert_eq!(trash.root(), Path::new("/data/Trash"));
+
+        let trash =
+            Trash::from_env_vars(Some("relative".into()), Some("/home/user".into())).unwrap();
+        assert_eq!(trash.root(), Path::new("/home/user/.local/share/Trash"));
+
+        assert_eq!(Trash::from_env_vars(None, None), None);
+    }
+
+    #[test]
+    fn path_encoding_round_trip() {
+        let path = "/home/user/My Files/100%_done.txt";
+        let encoded = encode_path(path);
+
+        assert_eq!(encoded, "/home/user/My%20Files/100%25_done.txt");
+        assert_eq!(decode_path(&encoded).as_deref(), Some(path));
+        assert_eq!(decode_path("/bad%2"), None);
+    }
+
+    #[tokio::test]
+    async fn trash_list_and_restore() {
+        let home = tempfile::tempdir().unwrap();
+        let trash = Trash::from_env_vars(None, Some(home.path().into())).unwrap();
+
+        let file = home.path().join("notes.txt");
+        write(&file, "hello").await;
+
+        let item = trash.trash(&file.to_string_lossy()).await.unwrap();
+        assert_eq!(item.name, "notes.txt");
+        assert_eq!(item.original_path, file);
+        assert!(!file.exists());
+
+        let info = tokio::fs::read_to_string(trash.info_path("notes.txt"))
+            .await
+            .unwrap();
+        assert!(info.starts_with("[Trash Info]\nPath="));
+        assert!(info.contains("DeletionDate="));
+
+        assert_eq!(trash.list().await.unwrap(), vec![item.clone()]);
+
+        trash.restore(&item).await.unwrap();
+        assert_eq!(tokio::fs::read_to_string(&file).await.unwrap(), "hello");
+        assert!(trash.list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn trash_same_name_twice() {
+        let home = tempfile::tempdir().unwrap();
+        let trash = Trash::new(home.path().join("Trash"));
+
+        let dir = home.path().join("build");
+        tokio::fs::create_dir(&dir).await.unwrap();
+        write(&dir.join("output.log"), "first").await;
+        let first = trash.trash(&dir.to_string_lossy()).await.unwrap();
+
+        tokio::fs::create_dir(&dir).await.unwrap();
+        let second = trash.trash(&dir.to_string_lossy()).await.unwrap();
+
+        assert_eq!(first.name, "build");
+        assert_eq!(second.name, "build.2");
+        assert_eq!(trash.list().await.unwrap().len(), 2);
+
+        // The original location is free again, so the first one can come back
+        trash.restore(&first).await.unwrap();
+        assert_eq!(
+            tokio::fs::read_to_string(dir.join("output.log"))
+                .await
+                .unwrap(),
+            "first"
+        );
+
+        let err = trash.restore(&second).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+
+        trash.purge(&second).await.unwrap();
+        assert!(trash.list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn trash_on_another_filesystem() {
+        let fs = Arc::new(
+            crate::filesystem::InMemoryFileSystem::new()
+                .with_file("/work/notes.txt", "hello")
+                .with_file("/work/build/output.log", "log"),
+        );
+        let trash = Trash::with_fs(Arc::clone(&fs), "/.trash");
+
+        let notes = trash.trash("/work/notes.txt").await.unwrap();
+        let build = trash.trash("/work/build").await.unwrap();
+        assert!(!fs.exists("/work/notes.txt").await.unwrap());
+        assert_eq!(
+            fs.contents("/.trash/files/build/output.log").unwrap(),
+            b"log"
+        );
+
+        let info =
+            String::from_utf8(fs.contents("/.trash/info/notes.txt.trashinfo").unwrap()).unwrap();
+        assert!(info.starts_with("[Trash Info]\nPath=/work/notes.txt\n"));
+
+        let mut items = trash.list().await.unwrap();
+        items.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(items, vec![build.clone(), notes.clone()]);
+
+        trash.restore(&notes).await.unwrap();
+        assert_eq!(fs.contents("/work/notes.txt").unwrap(), b"hello");
+
+        trash.purge(&build).await.unwrap();
+        assert!(!fs.exists("/.trash/files/build").await.unwrap());
+        assert!(trash.list().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,388 @@
+use std::io::{Error, ErrorKind, Result};
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use crate::{File, FileFilter};
+
+/// The kind of entries kept by a [`FilterLayer::Kind`] layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntryKind {
+    /// Keep the directories, including the symbolic links to directories.
+    Dir,
+    /// Keep the regular files, including the symbolic links to regular files.
+    File,
+    /// Keep the symbolic links.
+    Symlink,
+}
+
+/// A compiled list of glob patterns, like `*.rs` or `test_*`, see [`FilterLayer::include`] and
+/// [`FilterLayer::exclude`].
+///
+/// The patterns are matched against the name of the entries, without the trailing `/` of the
+/// directories.
+#[derive(Clone)]
+pub struct GlobPatterns {
+    patterns: Vec<String>,
+    set: GlobSet,
+}
+
+impl GlobPatterns {
+    /// Compiles the given patterns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::InvalidInput`] error if a pattern is not a valid glob.
+    pub fn new<I, S>(patterns: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let patterns: Vec<String> = patterns.into_iter().map(Into::into).collect();
+
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &patterns {
+            let glob = Glob::new(pattern).map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+            builder.add(glob);
+        }
+        let set = builder
+            .build()
+            .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+
+        Ok(Self { patterns, set })
+    }
+
+    /// Returns the patterns, as given.
+    #[inline]
+    #[must_use]
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Returns `true` if any pattern matches the given name.
+    #[inline]
+    #[must_use]
+    pub fn is_match(&self, name: &str) -> bool {
+        self.set.is_match(name)
+    }
+}
+
+impl std::fmt::Debug for GlobPatterns {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("GlobPatterns").field(&self.patterns).finish()
+    }
+}
+
+/// A layer of a [`FilterStack`], keeping the entries it matches.
+///
+/// The `../` entry is never hidden by the [`Include`](FilterLayer::Include),
+/// [`Exclude`](FilterLayer::Exclude), [`Extensions`](FilterLayer::Extensions) and
+/// [`Kind`](FilterLayer::Kind) layers, so that the parent directory stays reachable, and the
+/// directories are never hidden by the `Include` and `Extensions` layers, so that "only `*.rs`"
+/// still lets you walk the tree down to the Rust files.
+#[derive(Clone)]
+pub enum FilterLayer {
+    /// Keep the files matching one of the patterns.
+    Include(GlobPatterns),
+    /// Hide the entries matching one of the patterns.
+    Exclude(GlobPatterns),
+    /// Keep the files having one of the extensions, compared regardless of their case and given
+    /// without the leading dot.
+    Extensions(Vec<String>),
+    /// Keep the entries of the given kind.
+    Kind(EntryKind),
+    /// Keep the entries whose names contain the text, regardless of its case, see
+    /// [`FileExplorer::set_search_filter`](crate::FileExplorer::set_search_filter).
+    Query(String),
+    /// Keep the entries the closure returns, with filter_map semantics, see
+    /// [`FileExplorer::set_filter`](crate::FileExplorer::set_filter).
+    Custom(FileFilter),
+}
+
+impl FilterLayer {
+    /// Creates a layer keeping the files matching one of the glob patterns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::InvalidInput`] error if a pattern is not a valid glob.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::FilterLayer;
+    ///
+    /// assert!(FilterLayer::include(["*.rs", "Cargo.*"]).is_ok());
+    /// assert!(FilterLayer::include(["[*.rs"]).is_err());
+    /// ```
+    pub fn include<I, S>(patterns: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        GlobPatterns::new(patterns).map(Self::Include)
+    }
+
+    /// Creates a layer hiding the entries matching one of the glob patterns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::InvalidInput`] error if a pattern is not a valid glob.
+    pub fn exclude<I, S>(patterns: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        GlobPatterns::new(patterns).map(Self::Exclude)
+    }
+
+    /// Creates a layer keeping the files having one of the extensions, with or without the
+    /// leading dot.
+    #[must_use]
+    pub fn extensions<I, S>(extensions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::Extensions(
+            extensions
+                .into_iter()
+                .map(|extension| extension.as_ref().trim_start_matches('.').to_lowercase())
+                .collect(),
+        )
+    }
+
+    /// Returns the file to display if the layer keeps it.
+    fn apply(&self, file: File) -> Option<File> {
+        let name = file.name().trim_end_matches('/');
+        let keep = match self {
+            Self::Include(globs) => file.is_dir() || globs.is_match(name),
+            Self::Exclude(globs) => !globs.is_match(name),
+            Self::Extensions(extensions) => {
+                file.is_dir()
+                    || file
+                        .path()
+                        .extension()
+                        .and_then(|extension| extension.to_str())
+                        .is_some_and(|extension| {
+                            extensions.iter().any(|e| e.eq_ignore_ascii_case(extension))
+                        })
+            }
+            Self::Kind(kind) => match kind {
+                EntryKind::Dir => file.is_dir(),
+                EntryKind::File => file.is_file(),
+                EntryKind::Symlink => file.symlink_target().is_some(),
+            },
+            Self::Query(query) => {
+                return file
+                    .name()
+                    .to_lowercase()
+                    .contains(&query.to_lowercase())
+                    .then_some(file);
+            }
+            Self::Custom(predicate) => return predicate(&file),
+        };
+
+        (keep || file.is_navigation_entry()).then_some(file)
+    }
+}
+
+impl std::fmt::Debug for FilterLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Include(globs) => f.debug_tuple("Include").field(globs).finish(),
+            Self::Exclude(globs) => f.debug_tuple("Exclude").field(globs).finish(),
+            Self::Extensions(extensions) => f.debug_tuple("Extensions").field(extensions).finish(),
+            Self::Kind(kind) => f.debug_tuple("Kind").field(kind).finish(),
+            Self::Query(query) => f.debug_tuple("Query").field(query).finish(),
+            Self::Custom(_) => f.debug_tuple("Custom").field(&"...").finish(),
+        }
+    }
+}
+
+/// A named layer of a [`FilterStack`].
+#[derive(Debug, Clone)]
+struct Layer {
+    name: String,
+    filter: FilterLayer,
+    enabled: bool,
+}
+
+/// The filters of a [`FileExplorer`](crate::FileExplorer): an ordered list of named
+/// [`FilterLayer`]s, each of which can be switched on or off.
+///
+/// An entry is displayed only if every enabled layer keeps it, the layers being applied in
+/// the order they were added. See
+/// [`FileExplorer::add_filter_layer`](crate::FileExplorer::add_filter_layer).
+#[derive(Debug, Clone, Default)]
+pub struct FilterStack {
+    layers: Vec<Layer>,
+}
+
+impl FilterStack {
+    /// The name of the layer set by
+    /// [`FileExplorer::set_search_filter`](crate::FileExplorer::set_search_filter).
+    pub const QUERY: &'static str = "query";
+
+    /// The name of the layer set by
+    /// [`FileExplorer::set_filter`](crate::FileExplorer::set_filter).
+    pub const CUSTOM: &'static str = "custom";
+
+    /// Adds the layer under the given name, enabled, replacing in place the layer having the
+    /// same name, if any.
+    pub(crate) fn insert(&mut self, name: impl Into<String>, filter: FilterLayer) {
+        let name = name.into();
+        match self.layers.iter_mut().find(|layer| layer.name == name) {
+            Some(layer) => {
+                layer.filter = filter;
+                layer.enabled = true;
+            }
+            None => self.layers.push(Layer {
+                name,
+                filter,
+                enabled: true,
+            }),
+        }
+    }
+
+    /// Removes the layer having the given name, returning it.
+    pub(crate) fn remove(&mut self, name: &str) -> Option<FilterLayer> {
+        let idx = self.layers.iter().position(|layer| layer.name == name)?;
+        Some(self.layers.remove(idx).filter)
+    }
+
+    /// Switches the layer having the given name on or off, returning `false` if there is no
+    /// such layer.
+    pub(crate) fn set_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        match self.layers.iter_mut().find(|layer| layer.name == name) {
+            Some(layer) => {
+                layer.enabled = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes every layer.
+    pub(crate) fn clear(&mut self) {
+        self.layers.clear();
+    }
+
+    /// Returns the layer having the given name, if any.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&FilterLayer> {
+        self.layers
+            .iter()
+            .find(|layer| layer.name == name)
+            .map(|layer| &layer.filter)
+    }
+
+    /// Returns `true` if the layer having the given name exists and is enabled.
+    #[must_use]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.layers
+            .iter()
+            .any(|layer| layer.name == name && layer.enabled)
+    }
+
+    /// Returns the name, the layer and whether it is enabled for every layer, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &FilterLayer, bool)> {
+        self.layers
+            .iter()
+            .map(|layer| (layer.name.as_str(), &layer.filter, layer.enabled))
+    }
+
+    /// Returns the number of layers, enabled or not.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns `true` if there is no layer.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Returns the file to display if every enabled layer keeps it.
+    pub(crate) fn apply(&self, file: &File) -> Option<File> {
+        self.layers
+            .iter()
+            .filter(|layer| layer.enabled)
+            .try_fold(file.clone(), |file, layer| layer.filter.apply(file))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(stack: &FilterStack, files: &[File]) -> Vec<String> {
+        files
+            .iter()
+            .filter_map(|file| stack.apply(file))
+            .map(|file| file.name().to_string())
+            .collect()
+    }
+
+    fn files() -> Vec<File> {
+        vec![
+            File::new_for_test("../", true, None, None),
+            File::new_for_test("src/", true, None, None),
+            File::new_for_test("target/", true, None, None),
+            File::new_for_test("Cargo.toml", false, None, None),
+            File::new_for_test("main.rs", false, None, None),
+            File::new_for_test("lib.RS", false, None, None),
+            File::new_for_test("link.rs", false, Some("main.rs"), None),
+        ]
+    }
+
+    #[test]
+    fn layers() {
+        let files = files();
+        let mut stack = FilterStack::default();
+        assert_eq!(names(&stack, &files).len(), files.len());
+
+        stack.insert("ext", FilterLayer::extensions([".rs"]));
+        assert_eq!(
+            names(&stack, &files),
+            ["../", "src/", "target/", "main.rs", "lib.RS", "link.rs"]
+        );
+
+        stack.insert("exclude", FilterLayer::exclude(["target", "l*"]).unwrap());
+        assert_eq!(names(&stack, &files), ["../", "src/", "main.rs"]);
+
+        stack.insert("kind", FilterLayer::Kind(EntryKind::File));
+        assert_eq!(names(&stack, &files), ["../", "main.rs"]);
+
+        stack.insert("ext", FilterLayer::include(["*.toml"]).unwrap());
+        assert_eq!(names(&stack, &files), ["../", "Cargo.toml"]);
+
+        stack.insert(FilterStack::QUERY, FilterLayer::Query("CARGO".into()));
+        assert_eq!(names(&stack, &files), ["Cargo.toml"]);
+    }
+
+    #[test]
+    fn switch() {
+        let files = files();
+        let mut stack = FilterStack::default();
+        stack.insert("kind", FilterLayer::Kind(EntryKind::Symlink));
+        stack.insert("include", FilterLayer::include(["*.rs"]).unwrap());
+        assert_eq!(names(&stack, &files), ["../", "link.rs"]);
+
+        assert!(stack.set_enabled("kind", false));
+        assert!(!stack.is_enabled("kind"));
+        assert_eq!(
+            names(&stack, &files),
+            ["../", "src/", "target/", "main.rs", "link.rs"]
+        );
+
+        assert!(!stack.set_enabled("missing", false));
+        assert!(matches!(
+            stack.remove("include"),
+            Some(FilterLayer::Include(_))
+        ));
+        assert_eq!(names(&stack, &files).len(), files.len());
+        assert_eq!(stack.len(), 1);
+    }
+}
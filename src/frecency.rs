@@ -0,0 +1,366 @@
+use std::{
+    collections::HashMap,
+    io::{Error, ErrorKind, Result},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The total rank above which the ranks of every directory are aged.
+const MAX_AGE: f64 = 10_000.0;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Entry {
+    rank: f64,
+    last_accessed: u64,
+}
+
+impl Entry {
+    /// The frecency of the directory: its rank, weighted by how long ago it was visited.
+    fn score(&self, now: u64) -> f64 {
+        let elapsed = now.saturating_sub(self.last_accessed);
+
+        if elapsed < HOUR {
+            self.rank * 4.0
+        } else if elapsed < DAY {
+            self.rank * 2.0
+        } else if elapsed < WEEK {
+            self.rank / 2.0
+        } else {
+            self.rank / 4.0
+        }
+    }
+}
+
+/// A database of the visited directories, ranked by frecency (frequency and recency), to
+/// jump to a directory from a few fragments of its path like
+/// [zoxide](https://github.com/ajeetdsouza/zoxide) does.
+///
+/// Every visit increments the rank of a directory. Once the sum of the ranks grows too
+/// large, they are all scaled down and the directories which are not visited anymore are
+/// forgotten.
+///
+/// The database can be saved to and loaded from a text file holding one
+/// `<rank>\t<last access>\t<path>` line per directory, the last access being in seconds
+/// since the Unix epoch.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use ratatui_async_explorer::Frecency;
+///
+/// let mut frecency = Frecency::new();
+/// frecency.visit("/home/user/work/api/src");
+/// frecency.visit("/home/user/work/web/src");
+/// frecency.visit("/home/user/work/web/src");
+///
+/// assert_eq!(frecency.best_match("src"), Some(Path::new("/home/user/work/web/src")));
+/// assert_eq!(frecency.best_match("api src"), Some(Path::new("/home/user/work/api/src")));
+/// assert_eq!(frecency.best_match("work"), None); // must match the last component
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Frecency {
+    entries: HashMap<PathBuf, Entry>,
+}
+
+impl Frecency {
+    /// Creates an empty database.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the database saved in the file at `path`. A missing file gives an empty database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or a line is not valid.
+    pub async fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        match tokio::fs::read_to_string(path).await {
+            Ok(contents) => contents.parse(),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Saves the database to the file at `path`, creating its parent directories.
+    ///
+    /// The file is replaced atomically, so it is never left half written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub async fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+
+        tokio::fs::write(&temporary, self.to_string()).await?;
+        tokio::fs::rename(&temporary, path).await
+    }
+
+    /// Records a visit of the directory at `path`.
+    pub fn visit<P: Into<PathBuf>>(&mut self, path: P) {
+        self.visit_at(path.into(), now());
+    }
+
+    /// Forgets the directory at `path`, e.g. because it does not exist anymore.
+    pub fn remove<P: AsRef<Path>>(&mut self, path: P) -> bool {
+        self.entries.remove(path.as_ref()).is_some()
+    }
+
+    /// Returns the frecency score of the directory at `path`, if it has been visited.
+    #[must_use]
+    pub fn score<P: AsRef<Path>>(&self, path: P) -> Option<f64> {
+        let now = now();
+        self.entries
+            .get(path.as_ref())
+            .map(|entry| entry.score(now))
+    }
+
+    /// Returns the directories matching `query`, the best one first.
+    ///
+    /// The query is split on whitespaces into keywords, which must appear in the path in
+    /// order, the last one in the last component of the path. Keywords are compared
+    /// case-insensitively, unless they contain an uppercase letter.
+    #[must_use]
+    pub fn matches(&self, query: &str) -> Vec<&Path> {
+        let keywords: Vec<&str> = query.split_whitespace().collect();
+        let now = now();
+
+        let mut matches: Vec<(&Path, f64)> = self
+            .entries
+            .iter()
+            .filter(|(path, _)| is_match(path, &keywords))
+            .map(|(path, entry)| (path.as_path(), entry.score(now)))
+            .collect();
+
+        matches.sort_by(|(a, a_score), (b, b_score)| b_score.total_cmp(a_score).then(a.cmp(b)));
+        matches.into_iter().map(|(path, _)| path).collect()
+    }
+
+    /// Returns the best directory matching `query`, see [`matches`](Self::matches).
+    #[must_use]
+    pub fn best_match(&self, query: &str) -> Option<&Path> {
+        self.matches(query).into_iter().next()
+    }
+
+    /// Returns the number of directories in the database.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether no directory has been visited.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn visit_at(&mut self, path: PathBuf, now: u64) {
+        let entry = self.entries.entry(path).or_insert(Entry {
+            rank: 0.0,
+            last_accessed: now,
+        });
+        entry.rank += 1.0;
+        entry.last_accessed = now;
+
+        self.age();
+    }
+
+    /// Scale the ranks down once their sum exceeds [`MAX_AGE`], forgetting the directories
+    /// whose rank falls below 1.
+    fn age(&mut self) {
+        let total: f64 = self.entries.values().map(|entry| entry.rank).sum();
+        if total <= MAX_AGE {
+            return;
+        }
+
+        let factor = 0.9 * MAX_AGE / total;
+        self.entries.retain(|_, entry| {
+            entry.rank *= factor;
+            entry.rank >= 1.0
+        });
+    }
+}
+
+impl std::fmt::Display for Frecency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut entries: Vec<_> = self.entries.iter().collect();
+        entries.sort_by_key(|(path, _)| *path);
+
+        for (path, entry) in entries {
+            writeln!(
+                f,
+                "{}\t{}\t{}",
+                entry.rank,
+                entry.last_accessed,
+                path.display()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Frecency {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut frecency = Self::new();
+
+        for (number, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let invalid = || {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid frecency entry on line {}: {}", number + 1, line),
+                )
+            };
+
+            let mut fields = line.splitn(3, '\t');
+            let (Some(rank), Some(last_accessed), Some(path)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(invalid());
+            };
+
+            let entry = Entry {
+                rank: rank.parse().map_err(|_| invalid())?,
+                last_accessed: last_accessed.parse().map_err(|_| invalid())?,
+            };
+            frecency.entries.insert(PathBuf::from(path), entry);
+        }
+
+        Ok(frecency)
+    }
+}
+
+/// Returns whether the `keywords` appear in `path` in order, the last one in its last component.
+fn is_match(path: &Path, keywords: &[&str]) -> bool {
+    let path: Vec<char> = path.to_string_lossy().chars().collect();
+    let last_component = path
+        .iter()
+        .rposition(|c| *c == '/')
+        .map_or(0, |idx| idx + 1);
+
+    let mut position = 0;
+    for (i, keyword) in keywords.iter().enumerate() {
+        let keyword: Vec<char> = keyword.chars().collect();
+        let from = if i == keywords.len() - 1 {
+            position.max(last_component)
+        } else {
+            position
+        };
+
+        match find(&path, from, &keyword) {
+            Some(start) => position = start + keyword.len(),
+            None => return false,
+        }
+    }
+
+    true
+}
+
+/// Returns the index of the first occurrence of `needle` in `haystack` after `from`, ignoring
+/// the case unless `needle` contains an uppercase letter.
+fn find(haystack: &[char], from: usize, needle: &[char]) -> Option<usize> {
+    let case_sensitive = needle.iter().any(|c| c.is_uppercase());
+    let eq = |a: &char, b: &char| {
+        if case_sensitive {
+            a == b
+        } else {
+            a.to_lowercase().eq(b.to_lowercase())
+        }
+    };
+
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window.iter().zip(needle).all(|(a, b)| eq(a, b)))
+        .map(|idx| idx + from)
+}
+
+/// Returns the current time in seconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matching() {
+        let path = Path::new("/home/me/Work/ratatui-explorer/src");
+
+        assert!(is_match(path, &[]));
+        assert!(is_match(path, &["src"]));
+        assert!(is_match(path, &["work", "sr"]));
+        assert!(is_match(path, &["Work", "src"]));
+        assert!(!is_match(path, &["WORK", "src"]));
+        assert!(!is_match(path, &["src", "work"]));
+        assert!(!is_match(path, &["explorer"]));
+    }
+
+    #[test]
+    fn ranking_and_aging() {
+        let now = 100 * WEEK;
+        let mut frecency = Frecency::new();
+
+        // Visited often, but a long time ago
+        for _ in 0..6 {
+            frecency.visit_at(PathBuf::from("/old/src"), now - 2 * WEEK);
+        }
+        // Visited a few times, recently
+        for _ in 0..2 {
+            frecency.visit_at(PathBuf::from("/new/src"), now - 10);
+        }
+
+        let entries = &frecency.entries;
+        assert!(
+            entries[Path::new("/new/src")].score(now) > entries[Path::new("/old/src")].score(now)
+        );
+
+        for _ in 0..50_000 {
+            frecency.visit_at(PathBuf::from("/busy"), now);
+        }
+
+        // The rarely visited directories are eventually forgotten
+        assert!(frecency.entries[Path::new("/busy")].rank < MAX_AGE);
+        assert_eq!(frecency.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("frecency");
+
+        assert!(Frecency::load(&path).await.unwrap().is_empty());
+
+        let mut frecency = Frecency::new();
+        frecency.visit("/tmp");
+        frecency.visit("/home/me/My Documents");
+        frecency.visit("/home/me/My Documents");
+        frecency.save(&path).await.unwrap();
+
+        assert_eq!(Frecency::load(&path).await.unwrap(), frecency);
+        assert!("1.5\t/tmp".parse::<Frecency>().is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! Fuzzy matching of names, in the spirit of [fzf](https://github.com/junegunn/fzf)'s
+//! original algorithm.
+
+const SCORE_MATCH: i64 = 16;
+const SCORE_GAP_START: i64 = -3;
+const SCORE_GAP_EXTENSION: i64 = -1;
+const BONUS_BOUNDARY: i64 = 8;
+const BONUS_CAMEL_CASE: i64 = 7;
+const BONUS_CONSECUTIVE: i64 = 4;
+const BONUS_FIRST_CHAR_MULTIPLIER: i64 = 2;
+
+/// Fuzzy matches `query` against `name`, returning the score of the match along with the
+/// indices of the matched characters of `name`.
+///
+/// Every character of `query` must appear in `name` in order. The case is ignored unless
+/// `query` contains an uppercase letter. Matches are scored higher when they are
+/// consecutive or start words, and lower when there are gaps between them.
+pub(crate) fn fuzzy_match(query: &str, name: &str) -> Option<(i64, Vec<usize>)> {
+    let query: Vec<char> = query.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let case_sensitive = query.iter().any(|c| c.is_uppercase());
+    let eq = |a: char, b: char| {
+        if case_sensitive {
+            a == b
+        } else {
+            a.to_lowercase().eq(b.to_lowercase())
+        }
+    };
+
+    if query.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    // Find where the first occurrence of the whole query ends...
+    let mut matched = 0;
+    let end = name.iter().position(|c| {
+        if eq(*c, query[matched]) {
+            matched += 1;
+        }
+        matched == query.len()
+    })?;
+
+    // ...then walk back from there to find the shortest window holding it
+    let mut positions = vec![end];
+    let mut remaining = query[..query.len() - 1].iter().rev().peekable();
+    for idx in (0..end).rev() {
+        match remaining.peek() {
+            Some(c) if eq(name[idx], **c) => {
+                positions.push(idx);
+                remaining.next();
+            }
+            Some(_) => {}
+            None => break,
+        }
+    }
+    positions.reverse();
+
+    let mut score = 0;
+    let mut previous: Option<usize> = None;
+    // The bonus of the first character of the current run of consecutive matches, which
+    // the whole run benefits from
+    let mut run_bonus = 0;
+    for &idx in &positions {
+        let bonus = bonus(&name, idx);
+
+        score += SCORE_MATCH
+            + match previous {
+                None => {
+                    run_bonus = bonus;
+                    bonus * BONUS_FIRST_CHAR_MULTIPLIER
+                }
+                Some(previous) if idx == previous + 1 => {
+                    bonus.max(run_bonus).max(BONUS_CONSECUTIVE)
+                }
+                Some(previous) => {
+                    run_bonus = bonus;
+                    bonus + SCORE_GAP_START + SCORE_GAP_EXTENSION * (idx - previous - 2) as i64
+                }
+            };
+        previous = Some(idx);
+    }
+
+    Some((score, positions))
+}
+
+/// The bonus of matching the character at `idx`, higher at the start of a word.
+fn bonus(name: &[char], idx: usize) -> i64 {
+    let c = name[idx];
+    let Some(previous) = idx.checked_sub(1).map(|idx| name[idx]) else {
+        return BONUS_BOUNDARY;
+    };
+
+    if !previous.is_alphanumeric() {
+        BONUS_BOUNDARY
+    } else if (previous.is_lowercase() && c.is_uppercase())
+        || (!previous.is_ascii_digit() && c.is_ascii_digit())
+    {
+        BONUS_CAMEL_CASE
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matching() {
+        assert_eq!(fuzzy_match("", "main.rs"), Some((0, vec![])));
+        assert_eq!(fuzzy_match("mrs", "main.rs").unwrap().1, [0, 5, 6]);
+        assert_eq!(fuzzy_match("MR", "main.rs"), None);
+        assert_eq!(fuzzy_match("rsm", "main.rs"), None);
+
+        // The shortest window is preferred to the first occurrence
+        assert_eq!(fuzzy_match("ab", "a-a-ab").unwrap().1, [4, 5]);
+        assert_eq!(fuzzy_match("év", "Éveil.md").unwrap().1, [0, 1]);
+    }
+
+    #[test]
+    fn scoring() {
+        let score = |query, name| fuzzy_match(query, name).unwrap().0;
+
+        // Word starts beat the middle of words
+        assert!(score("fe", "file_explorer.rs") > score("fe", "safe.rs"));
+        assert!(score("fe", "FileExplorer.rs") > score("fe", "safe.rs"));
+        // Consecutive matches beat scattered ones
+        assert!(score("mod", "mod.rs") > score("mod", "m_o_d.rs"));
+    }
+}
@@ -0,0 +1,342 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// The status of an entry in its git repository, see
+/// [`FileExplorer::set_git_status`](crate::FileExplorer::set_git_status).
+///
+/// The statuses are ordered from the least to the most important: an entry both staged and
+/// modified is [`Modified`](GitStatus::Modified), and a directory takes the most important
+/// status of its children, apart from [`Ignored`](GitStatus::Ignored).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GitStatus {
+    /// The entry is ignored by the ignore files of the repository.
+    Ignored,
+    /// The entry is not tracked by the repository.
+    Untracked,
+    /// The entry has changes added to the index.
+    Staged,
+    /// The entry has changes not added to the index, or is deleted.
+    Modified,
+    /// The entry has merge conflicts.
+    Conflicted,
+}
+
+impl GitStatus {
+    /// Returns the character displayed after the name of the entries having this status, like
+    /// in the output of `git status --short`: `!`, `?`, `S`, `M` or `U`.
+    #[inline]
+    #[must_use]
+    pub const fn symbol(self) -> char {
+        match self {
+            Self::Ignored => '!',
+            Self::Untracked => '?',
+            Self::Staged => 'S',
+            Self::Modified => 'M',
+            Self::Conflicted => 'U',
+        }
+    }
+
+    /// Parses the `XY` status code of an entry of `git status --porcelain`.
+    fn from_code(x: u8, y: u8) -> Option<Self> {
+        match (x, y) {
+            (b'!', b'!') => Some(Self::Ignored),
+            (b'?', b'?') => Some(Self::Untracked),
+            (b'U', _) | (_, b'U') | (b'A', b'A') | (b'D', b'D') => Some(Self::Conflicted),
+            (_, b' ') if x != b' ' => Some(Self::Staged),
+            (_, b' ') => None,
+            _ => Some(Self::Modified),
+        }
+    }
+}
+
+/// The git status of the entries of a directory, read by running `git status` in it.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GitStatuses {
+    /// The status of the changed entries, and the aggregated status of their parents.
+    statuses: HashMap<PathBuf, GitStatus>,
+    /// The untracked or ignored directories, whose whole content shares their status.
+    dirs: Vec<(PathBuf, GitStatus)>,
+}
+
+impl GitStatuses {
+    /// Returns the path of the local directory `dir` relative to the root of its git
+    /// repository, like `src/`, or `None` if it is not inside a git repository or if git
+    /// cannot be run.
+    pub(crate) async fn prefix(dir: &Path) -> Option<String> {
+        let dir = dir.to_path_buf();
+
+        tokio::task::spawn_blocking(move || {
+            let prefix = git(&dir, &["rev-parse", "--show-prefix"])?;
+            Some(String::from_utf8_lossy(&prefix).trim_end().to_string())
+        })
+        .await
+        .ok()
+        .flatten()
+    }
+
+    /// Runs `git status` in the local directory `dir`, which is `prefix` from the root of its
+    /// repository (see [`prefix`](Self::prefix)), returning `None` if git fails.
+    pub(crate) async fn load(dir: &Path, prefix: &str) -> Option<Self> {
+        let (dir, prefix) = (dir.to_path_buf(), prefix.to_string());
+
+        tokio::task::spawn_blocking(move || {
+            // The paths are relative to the root of the repository
+            let output = git(
+                &dir,
+                &[
+                    "status",
+                    "--porcelain=v1",
+                    "-z",
+                    "--ignored=matching",
+                    "--",
+                    ".",
+                ],
+            )?;
+
+            Some(Self::parse(&dir, &prefix, &output))
+        })
+        .await
+        .ok()
+        .flatten()
+    }
+
+    /// Parses the output of `git status --porcelain=v1 -z` run in `dir`.
+    fn parse(dir: &Path, prefix: &str, output: &[u8]) -> Self {
+        let mut statuses = Self::default();
+
+        let mut records = output.split(|&b| b == 0);
+        while let Some(record) = records.next() {
+            let [x, y, b' ', path @ ..] = record else {
+                continue;
+            };
+            // A renamed or copied entry is followed by its original path
+            if matches!(x, b'R' | b'C') {
+                records.next();
+            }
+            let Some(status) = GitStatus::from_code(*x, *y) else {
+                continue;
+            };
+
+            let path = String::from_utf8_lossy(path);
+            let Some(relative) = path.strip_prefix(prefix) else {
+                continue;
+            };
+            if relative.ends_with('/') && status <= GitStatus::Untracked {
+                statuses.dirs.push((dir.join(relative), status));
+            }
+            statuses.insert(dir, dir.join(relative.trim_end_matches('/')), status);
+        }
+
+        statuses
+    }
+
+    /// Records the status of `path`, and aggregates it into its parents up to `dir`.
+    fn insert(&mut self, dir: &Path, path: PathBuf, status: GitStatus) {
+        if status != GitStatus::Ignored {
+            for parent in path.ancestors().skip(1) {
+                if parent == dir || !parent.starts_with(dir) {
+                    break;
+                }
+                let aggregated = self.statuses.entry(parent.to_path_buf()).or_insert(status);
+                *aggregated = (*aggregated).max(status);
+            }
+        }
+
+        let entry = self.statuses.entry(path).or_insert(status);
+        *entry = (*entry).max(status);
+    }
+
+    /// Returns the status of the entry at `path`, if it has one.
+    pub(crate) fn get(&self, path: &Path) -> Option<GitStatus> {
+        self.statuses.get(path).copied().or_else(|| {
+            self.dirs
+                .iter()
+                .find(|(dir, _)| path.starts_with(dir))
+                .map(|(_, status)| *status)
+        })
+    }
+}
+
+/// Runs git with the given arguments in `dir`, returning its standard output if it succeeds.
+fn git(dir: &Path, args: &[&str]) -> Option<Vec<u8>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()?;
+
+    output.status.success().then_some(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::FileExplorer;
+
+    use super::*;
+
+    fn run(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn parse() {
+        let output = b"UU src/lib.rs\0M  src/main.rs\0 M README.md\0R  new.rs\0old.rs\0?? docs/\0!! target/\0";
+        let statuses = GitStatuses::parse(Path::new("/repo"), "", output);
+
+        let get = |path: &str| statuses.get(Path::new(path));
+        assert_eq!(get("/repo/src/lib.rs"), Some(GitStatus::Conflicted));
+        assert_eq!(get("/repo/src/main.rs"), Some(GitStatus::Staged));
+        assert_eq!(get("/repo/src"), Some(GitStatus::Conflicted));
+        assert_eq!(get("/repo/README.md"), Some(GitStatus::Modified));
+        assert_eq!(get("/repo/new.rs"), Some(GitStatus::Staged));
+        assert_eq!(get("/repo/old.rs"), None);
+        assert_eq!(get("/repo/docs/guide/intro.md"), Some(GitStatus::Untracked));
+        assert_eq!(get("/repo/target/debug"), Some(GitStatus::Ignored));
+        assert_eq!(get("/repo/Cargo.toml"), None);
+
+        // In a subdirectory, the paths outside of it are skipped
+        let statuses = GitStatuses::parse(Path::new("/repo/src"), "src/", output);
+        assert_eq!(
+            statuses.get(Path::new("/repo/src/lib.rs")),
+            Some(GitStatus::Conflicted)
+        );
+        assert_eq!(statuses.get(Path::new("/repo/README.md")), None);
+    }
+
+    #[tokio::test]
+    async fn repository() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        run(root, &["init", "-q"]);
+        fs::create_dir_all(root.join("src/deep")).unwrap();
+        fs::write(root.join("src/deep/lib.rs"), "").unwrap();
+        fs::write(root.join("src/main.rs"), "").unwrap();
+        fs::write(root.join("README.md"), "").unwrap();
+        fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        run(root, &["add", "."]);
+        run(root, &["commit", "-q", "-m", "initial"]);
+
+        fs::write(root.join("src/deep/lib.rs"), "changed").unwrap();
+        fs::write(root.join("README.md"), "changed").unwrap();
+        run(root, &["add", "README.md"]);
+        fs::write(root.join("notes.txt"), "").unwrap();
+        fs::create_dir(root.join("target")).unwrap();
+        fs::write(root.join("target/app"), "").unwrap();
+
+        let mut file_explorer = FileExplorer::with_fs(
+            std::sync::Arc::new(crate::LocalFileSystem::new()),
+            root.to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+        file_explorer.set_git_status(true).await.unwrap();
+
+        let status = |name: &str| {
+            file_explorer
+                .files()
+                .iter()
+                .find(|file| file.name() == name)
+                .unwrap()
+                .git_status()
+        };
+        assert_eq!(status("src/"), Some(GitStatus::Modified));
+        assert_eq!(status("README.md"), Some(GitStatus::Staged));
+        assert_eq!(status("notes.txt"), Some(GitStatus::Untracked));
+        assert_eq!(status("target/"), Some(GitStatus::Ignored));
+        assert_eq!(status("../"), None);
+
+        // The statuses follow the current directory
+        file_explorer.set_cwd(root.join("src")).await.unwrap();
+        let deep = file_explorer
+            .files()
+            .iter()
+            .find(|file| file.name() == "deep/")
+            .unwrap();
+        assert_eq!(deep.git_status(), Some(GitStatus::Modified));
+        let main = file_explorer
+            .files()
+            .iter()
+            .find(|file| file.name() == "main.rs")
+            .unwrap();
+        assert_eq!(main.git_status(), None);
+    }
+
+    #[tokio::test]
+    async fn repository_is_looked_up_again_by_set_cwd() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("notes.txt"), "").unwrap();
+
+        let mut file_explorer = FileExplorer::with_fs(
+            std::sync::Arc::new(crate::LocalFileSystem::new()),
+            root.to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+        file_explorer.set_git_status(true).await.unwrap();
+        let status = |file_explorer: &FileExplorer| {
+            file_explorer
+                .files()
+                .iter()
+                .find(|file| file.name() == "notes.txt")
+                .unwrap()
+                .git_status()
+        };
+        assert_eq!(status(&file_explorer), None);
+
+        // The directory is remembered as being outside of a repository
+        run(root, &["init", "-q"]);
+        file_explorer.set_show_hidden(true).await.unwrap();
+        assert_eq!(status(&file_explorer), None);
+
+        file_explorer.set_cwd(root).await.unwrap();
+        assert_eq!(status(&file_explorer), Some(GitStatus::Untracked));
+    }
+
+    #[tokio::test]
+    async fn only_local_directories_have_statuses() {
+        use crate::filesystem::{Chroot, FileSystem, InMemoryFileSystem, LocalFileSystem};
+        use std::sync::Arc;
+
+        async fn status<F: FileSystem>(fs: F, cwd: &Path) -> Option<GitStatus> {
+            let mut file_explorer =
+                FileExplorer::with_fs(Arc::new(fs), cwd.to_string_lossy().to_string())
+                    .await
+                    .unwrap();
+            file_explorer.set_git_status(true).await.unwrap();
+            file_explorer
+                .files()
+                .iter()
+                .find(|file| file.name() == "notes.txt")
+                .unwrap()
+                .git_status()
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        run(root, &["init", "-q"]);
+        fs::write(root.join("notes.txt"), "").unwrap();
+
+        let chroot = Chroot::new(LocalFileSystem::new(), root);
+        assert_eq!(status(chroot, root).await, Some(GitStatus::Untracked));
+
+        // The same paths in memory are not looked up in the repository on the disk
+        let memory =
+            InMemoryFileSystem::new().with_file(&root.join("notes.txt").to_string_lossy(), "");
+        assert_eq!(status(memory, root).await, None);
+    }
+}
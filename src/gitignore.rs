@@ -0,0 +1,330 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::filesystem::{read_to_string, FileSystem};
+
+/// The most bytes read from a single ignore file.
+const MAX_IGNORE_FILE_SIZE: usize = 1024 * 1024;
+
+/// How many directories [`IgnoreCache`] keeps the rules of before forgetting the least recently
+/// used.
+const IGNORE_CACHE_LIMIT: usize = 64;
+
+/// The names of the files whose rules apply to their directory and everything below it.
+const IGNORE_FILES: [&str; 2] = [".gitignore", ".ignore"];
+
+/// The `.gitignore`, `.ignore` and `.git/info/exclude` rules applying to the entries of a
+/// directory, see [`FileExplorer::set_show_ignored`](crate::FileExplorer::set_show_ignored).
+///
+/// Like [ripgrep](https://github.com/BurntSushi/ripgrep), the rules of the directory and of its
+/// parents are read up to the root of the git repository, the rules of a deeper directory
+/// taking precedence, and the `.gitignore` and `.git/info/exclude` files only apply inside a
+/// git repository. Within a directory, `.ignore` takes precedence over `.gitignore`, which takes
+/// precedence over `.git/info/exclude`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct IgnoreRules {
+    /// The rules of every directory having some, the deepest first.
+    levels: Vec<Gitignore>,
+}
+
+impl IgnoreRules {
+    /// Reads the rules applying to the entries of `dir`. Missing or unreadable ignore files are
+    /// skipped, and so are the invalid lines.
+    pub(crate) async fn load<F: FileSystem>(filesystem: &F, dir: &Path) -> Self {
+        // Find the directories whose rules apply, up to the root of the repository
+        let mut dirs = Vec::new();
+        let mut repo_root = None;
+        for ancestor in dir.ancestors() {
+            dirs.push(ancestor);
+            let git_dir = ancestor.join(".git");
+            if filesystem
+                .exists(&git_dir.to_string_lossy())
+                .await
+                .unwrap_or(false)
+            {
+                repo_root = Some(ancestor);
+                break;
+            }
+        }
+
+        let mut levels = Vec::new();
+        for dir in dirs {
+            let mut files = Vec::new();
+            if repo_root == Some(dir) {
+                files.push(dir.join(".git").join("info").join("exclude"));
+            }
+            if repo_root.is_some() {
+                files.push(dir.join(".gitignore"));
+            }
+            files.push(dir.join(".ignore"));
+
+            let mut builder = GitignoreBuilder::new(dir);
+            for file in files {
+                let Ok(contents) =
+                    read_to_string(filesystem, &file.to_string_lossy(), MAX_IGNORE_FILE_SIZE).await
+                else {
+                    continue;
+                };
+                for line in contents.lines() {
+                    // An invalid glob only disables its own line, like git does
+                    let _ = builder.add_line(Some(file.clone()), line);
+                }
+            }
+
+            if let Ok(gitignore) = builder.build() {
+                if !gitignore.is_empty() {
+                    levels.push(gitignore);
+                }
+            }
+        }
+
+        Self { levels }
+    }
+
+    /// Returns `true` if the entry at `path` is ignored by the deepest rule matching it.
+    pub(crate) fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        for level in &self.levels {
+            let matched = level.matched(path, is_dir);
+            if matched.is_ignore() {
+                return true;
+            }
+            if matched.is_whitelist() {
+                return false;
+            }
+        }
+
+        false
+    }
+}
+
+/// A least recently used map from directories to the rules applying to their entries.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct IgnoreCache {
+    entries: HashMap<PathBuf, (IgnoreRules, u64)>,
+    clock: u64,
+}
+
+impl IgnoreCache {
+    /// Returns the rules applying to the entries of `dir`, reading them if they are not known
+    /// yet and forgetting the least recently used directory if full.
+    pub(crate) async fn get_or_load<F: FileSystem>(
+        &mut self,
+        filesystem: &F,
+        dir: &Path,
+    ) -> &IgnoreRules {
+        self.clock += 1;
+        if let Some((_, used)) = self.entries.get_mut(dir) {
+            *used = self.clock;
+        } else {
+            let rules = IgnoreRules::load(filesystem, dir).await;
+            self.entries.insert(dir.to_path_buf(), (rules, self.clock));
+
+            if self.entries.len() > IGNORE_CACHE_LIMIT {
+                if let Some(oldest) = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, (_, used))| *used)
+                    .map(|(dir, _)| dir.clone())
+                {
+                    self.entries.remove(&oldest);
+                }
+            }
+        }
+
+        &self.entries[dir].0
+    }
+
+    /// Forgets the rules that may have changed once the entry at `path` was created, deleted
+    /// or moved: those of the directories below it, and those of the whole repository or
+    /// directory when it is (or is inside) a `.git` directory or an ignore file.
+    pub(crate) fn invalidate(&mut self, path: &Path) {
+        self.entries.retain(|dir, _| !dir.starts_with(path));
+
+        let scope = path
+            .ancestors()
+            .find(|ancestor| ancestor.file_name().is_some_and(|name| name == ".git"))
+            .or_else(|| {
+                path.file_name()
+                    .filter(|name| IGNORE_FILES.iter().any(|file| name == file))
+                    .map(|_| path)
+            })
+            .and_then(Path::parent);
+        if let Some(scope) = scope {
+            self.entries.retain(|dir, _| !dir.starts_with(scope));
+        }
+    }
+
+    /// Forgets the rules of every directory.
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{filesystem::InMemoryFileSystem, FileExplorer, Input};
+
+    use super::*;
+
+    fn filesystem() -> InMemoryFileSystem {
+        InMemoryFileSystem::new()
+            .with_file("/.ignore", "repo/README.md")
+            .with_file("/repo/.git/info/exclude", "local.txt\n")
+            .with_file(
+                "/repo/.gitignore",
+                "target/\n*.log\n!keep.log\n/local.txt\n",
+            )
+            .with_file("/repo/.ignore", "!debug.log\n")
+            .with_file("/repo/README.md", "")
+            .with_file("/repo/local.txt", "")
+            .with_file("/repo/debug.log", "")
+            .with_file("/repo/keep.log", "")
+            .with_file("/repo/target/debug/app", "")
+            .with_file("/repo/src/.gitignore", "generated.rs\n")
+            .with_file("/repo/src/generated.rs", "")
+            .with_file("/repo/src/trace.log", "")
+            .with_file("/repo/src/main.rs", "")
+            .with_file("/outside/.gitignore", "*.log\n")
+            .with_file("/outside/.ignore", "*.tmp\n")
+            .with_file("/outside/app.log", "")
+            .with_file("/outside/app.tmp", "")
+    }
+
+    fn names<F: FileSystem>(file_explorer: &FileExplorer<F>) -> Vec<&str> {
+        file_explorer
+            .files()
+            .iter()
+            .map(|file| file.name())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn rules() {
+        let fs = filesystem();
+
+        let rules = IgnoreRules::load(&fs, Path::new("/repo")).await;
+        assert!(rules.is_ignored(Path::new("/repo/target"), true));
+        assert!(rules.is_ignored(Path::new("/repo/local.txt"), false));
+        assert!(!rules.is_ignored(Path::new("/repo/keep.log"), false));
+        assert!(!rules.is_ignored(Path::new("/repo/debug.log"), false));
+        // The rules above the repository do not apply
+        assert!(!rules.is_ignored(Path::new("/repo/README.md"), false));
+
+        let rules = IgnoreRules::load(&fs, Path::new("/repo/src")).await;
+        assert!(rules.is_ignored(Path::new("/repo/src/generated.rs"), false));
+        assert!(rules.is_ignored(Path::new("/repo/src/trace.log"), false));
+        assert!(!rules.is_ignored(Path::new("/repo/src/main.rs"), false));
+
+        // Outside a repository, only the .ignore files apply
+        let rules = IgnoreRules::load(&fs, Path::new("/outside")).await;
+        assert!(!rules.is_ignored(Path::new("/outside/app.log"), false));
+        assert!(rules.is_ignored(Path::new("/outside/app.tmp"), false));
+    }
+
+    #[tokio::test]
+    async fn toggle() {
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(filesystem()), "/repo".to_string())
+            .await
+            .unwrap();
+        assert!(file_explorer.show_ignored());
+        assert_eq!(names(&file_explorer).len(), 7);
+
+        file_explorer.set_show_ignored(false).await.unwrap();
+        assert_eq!(
+            names(&file_explorer),
+            ["../", "src/", "README.md", "debug.log", "keep.log"]
+        );
+
+        file_explorer
+            .handle(Input::ToggleShowIgnored)
+            .await
+            .unwrap();
+        assert!(file_explorer.show_ignored());
+        assert!(names(&file_explorer).contains(&"target/"));
+    }
+
+    #[tokio::test]
+    async fn rules_are_read_again_by_set_cwd() {
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(filesystem()), "/repo".to_string())
+            .await
+            .unwrap();
+        file_explorer.set_show_ignored(false).await.unwrap();
+        assert!(names(&file_explorer).contains(&"README.md"));
+
+        // Listing the directory again keeps the rules read before
+        file_explorer
+            .filesystem()
+            .write_file("/repo/.ignore", b"README.md\n")
+            .await
+            .unwrap();
+        file_explorer.set_show_hidden(true).await.unwrap();
+        assert!(names(&file_explorer).contains(&"README.md"));
+
+        file_explorer.set_cwd("/repo").await.unwrap();
+        assert!(!names(&file_explorer).contains(&"README.md"));
+    }
+
+    #[tokio::test]
+    async fn cache() {
+        let fs = filesystem();
+        let mut cache = IgnoreCache::default();
+        for i in 0..IGNORE_CACHE_LIMIT {
+            cache
+                .get_or_load(&fs, &PathBuf::from(format!("/{}", i)))
+                .await;
+        }
+
+        // Using the oldest directory makes the second oldest the one to forget
+        cache.get_or_load(&fs, Path::new("/0")).await;
+        cache.get_or_load(&fs, Path::new("/repo/src")).await;
+        assert!(cache.entries.contains_key(Path::new("/0")));
+        assert!(!cache.entries.contains_key(Path::new("/1")));
+
+        // An ignore file invalidates its directory and those below it
+        cache.get_or_load(&fs, Path::new("/repo")).await;
+        cache.invalidate(Path::new("/repo/src/.gitignore"));
+        assert!(cache.entries.contains_key(Path::new("/repo")));
+        assert!(!cache.entries.contains_key(Path::new("/repo/src")));
+
+        // And so does anything inside a .git directory, for the whole repository
+        cache.get_or_load(&fs, Path::new("/repo/src")).await;
+        cache.invalidate(Path::new("/repo/.git/info/exclude"));
+        assert!(!cache.entries.contains_key(Path::new("/repo")));
+        assert!(!cache.entries.contains_key(Path::new("/repo/src")));
+        assert!(cache.entries.contains_key(Path::new("/0")));
+
+        // Other entries only invalidate the directories they contain
+        cache.get_or_load(&fs, Path::new("/repo/src")).await;
+        cache.invalidate(Path::new("/repo/src/main.rs"));
+        assert!(cache.entries.contains_key(Path::new("/repo/src")));
+        cache.invalidate(Path::new("/repo"));
+        assert!(!cache.entries.contains_key(Path::new("/repo/src")));
+    }
+
+    #[tokio::test]
+    async fn rules_are_read_again_after_touching_an_ignore_file() {
+        let mut file_explorer =
+            FileExplorer::with_fs(Arc::new(filesystem()), "/repo/src".to_string())
+                .await
+                .unwrap();
+        file_explorer.set_show_hidden(true).await.unwrap();
+        file_explorer.set_show_ignored(false).await.unwrap();
+        assert!(!names(&file_explorer).contains(&"generated.rs"));
+
+        file_explorer.select_file(".gitignore");
+        file_explorer
+            .handle(Input::Rename("gitignore.bak".to_string()))
+            .await
+            .unwrap();
+        assert!(names(&file_explorer).contains(&"generated.rs"));
+
+        file_explorer.undo().await.unwrap();
+        assert!(!names(&file_explorer).contains(&"generated.rs"));
+    }
+}
@@ -0,0 +1,116 @@
+use std::path::{Path, PathBuf};
+
+/// How many locations [`History`] remembers in each direction.
+const HISTORY_LIMIT: usize = 100;
+
+/// A location visited by a [`FileExplorer`](crate::FileExplorer), as remembered by its
+/// navigation history.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HistoryEntry {
+    pub(crate) cwd: PathBuf,
+    pub(crate) selected: Option<PathBuf>,
+    pub(crate) scroll_offset: usize,
+}
+
+impl HistoryEntry {
+    /// Returns the working directory of this location.
+    #[inline]
+    #[must_use]
+    pub fn cwd(&self) -> &Path {
+        &self.cwd
+    }
+
+    /// Returns the path of the entry that was selected when leaving this location.
+    #[inline]
+    #[must_use]
+    pub fn selected(&self) -> Option<&Path> {
+        self.selected.as_deref()
+    }
+
+    /// Returns the scroll offset of the list when leaving this location.
+    #[inline]
+    #[must_use]
+    pub const fn scroll_offset(&self) -> usize {
+        self.scroll_offset
+    }
+}
+
+/// The back and forward stacks of a [`FileExplorer`](crate::FileExplorer), the most
+/// recent location being last.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct History {
+    pub back: Vec<HistoryEntry>,
+    pub forward: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Remember the location being left for a new one, which forgets the forward locations.
+    pub fn visit(&mut self, left: HistoryEntry) {
+        if self.back.last() != Some(&left) {
+            push(&mut self.back, left);
+        }
+        self.forward.clear();
+    }
+}
+
+/// Push `entry` on `stack`, forgetting the oldest entry when it is full.
+pub(crate) fn push(stack: &mut Vec<HistoryEntry>, entry: HistoryEntry) {
+    stack.push(entry);
+    if stack.len() > HISTORY_LIMIT {
+        stack.remove(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{filesystem::InMemoryFileSystem, FileExplorer, Input};
+    use std::{path::Path, sync::Arc};
+
+    #[tokio::test]
+    async fn back_restores_selection_and_scroll() {
+        let mut fs = InMemoryFileSystem::new().with_dir("/other");
+        for i in 0..40 {
+            fs = fs.with_file(&format!("/logs/{:02}.log", i), "");
+        }
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/logs".to_string())
+            .await
+            .unwrap();
+
+        file_explorer.select_file("30.log");
+        file_explorer.set_scroll_offset(20);
+        file_explorer.set_cwd("/other").await.unwrap();
+        file_explorer.set_scroll_offset(0);
+        file_explorer.handle(Input::Left).await.unwrap();
+        assert_eq!(file_explorer.back_history().len(), 2);
+
+        file_explorer.handle(Input::Back).await.unwrap();
+        file_explorer.handle(Input::Back).await.unwrap();
+        assert_eq!(file_explorer.cwd(), Path::new("/logs"));
+        assert_eq!(file_explorer.current().name(), "30.log");
+        assert_eq!(file_explorer.scroll_offset(), 20);
+        assert_eq!(file_explorer.forward_history().len(), 2);
+        assert!(!file_explorer.back().await.unwrap());
+
+        // Visiting a new location forgets the forward ones
+        file_explorer.handle(Input::Left).await.unwrap();
+        assert!(file_explorer.forward_history().is_empty());
+        assert!(!file_explorer.forward().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn unreachable_locations_are_kept() {
+        let fs = InMemoryFileSystem::new().with_dir("/a/b");
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/a/b".to_string())
+            .await
+            .unwrap();
+
+        file_explorer.handle(Input::Left).await.unwrap();
+        assert_eq!(file_explorer.current().name(), "b/");
+        file_explorer.handle(Input::Delete).await.unwrap();
+        file_explorer.handle(Input::Confirm).await.unwrap();
+
+        assert!(file_explorer.handle(Input::Back).await.is_err());
+        assert_eq!(file_explorer.cwd(), Path::new("/a"));
+        assert_eq!(file_explorer.back_history().len(), 1);
+    }
+}
@@ -1,7 +1,7 @@
 use crossterm::event::{Event, KeyCode};
 use ratatui::crossterm::{self, event::KeyModifiers};
 
-use super::Input;
+use super::{erased, typed, Input, TextEdit};
 
 impl From<&Event> for Input {
     /// Convert crossterm [`Event`](https://docs.rs/crossterm/latest/crossterm/event/enum.Event.html) to [`Input`].
@@ -29,7 +29,31 @@ impl From<&Event> for Input {
                     KeyCode::End => Input::End,
                     KeyCode::PageUp => Input::PageUp,
                     KeyCode::PageDown => Input::PageDown,
+                    KeyCode::Char('I') => Input::ToggleShowIgnored,
                     KeyCode::Char('d') => Input::Delete,
+                    KeyCode::Char('y') => Input::Confirm,
+                    KeyCode::Char('s') => Input::CycleSort,
+                    KeyCode::Char('S') => Input::ToggleSortOrder,
+                    KeyCode::Char('D') => Input::ToggleDirsFirst,
+                    KeyCode::Char(' ') => Input::ToggleSelection,
+                    KeyCode::Char('V') => Input::SelectRange,
+                    KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        Input::SelectAll
+                    }
+                    KeyCode::Char('*') => Input::InvertSelection,
+                    KeyCode::Char('U') => Input::ClearSelection,
+                    KeyCode::Char('c') => Input::Yank,
+                    KeyCode::Char('x') => Input::Cut,
+                    KeyCode::Char('p') => Input::Paste,
+                    KeyCode::Char('g') => Input::StartPathEntry,
+                    KeyCode::Tab => Input::CompletePath,
+                    KeyCode::Char('u') => Input::Undo,
+                    KeyCode::Char('H') => Input::Back,
+                    KeyCode::Char('L') => Input::Forward,
+                    KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        Input::Redo
+                    }
+                    KeyCode::Char('n') | KeyCode::Esc => Input::Cancel,
                     _ => Input::None,
                 };
 
@@ -40,3 +64,29 @@ impl From<&Event> for Input {
         Input::None
     }
 }
+
+impl TextEdit for Event {
+    /// Edit `text` with a crossterm key [`Event`](https://docs.rs/crossterm/latest/crossterm/event/enum.Event.html).
+    ///
+    /// **Note:** This implementation is only available when the `crossterm` feature is enabled.
+    fn edit(&self, text: &str) -> Option<String> {
+        let Event::Key(key) = self else {
+            return None;
+        };
+        if !matches!(
+            key.kind,
+            crossterm::event::KeyEventKind::Press | crossterm::event::KeyEventKind::Repeat
+        ) || key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            return None;
+        }
+
+        match key.code {
+            KeyCode::Char(c) => Some(typed(text, c)),
+            KeyCode::Backspace => Some(erased(text)),
+            _ => None,
+        }
+    }
+}
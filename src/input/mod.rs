@@ -7,8 +7,10 @@ mod termion;
 #[cfg(feature = "termwiz")]
 mod termwiz;
 
-/// Input enum to represent the fours different actions available inside a [`FileExplorer`](crate::FileExplorer).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+use std::path::PathBuf;
+
+/// Input enum to represent the different actions available inside a [`FileExplorer`](crate::FileExplorer).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Input {
     /// Move the selection up.
     Up,
@@ -28,11 +30,108 @@ pub enum Input {
     Right,
     /// Toggle between showing hidden files or not.
     ToggleShowHidden,
-    /// Delete the currently selected file (directories are not supported).
+    /// Toggle between showing the entries ignored by the `.gitignore`, `.ignore` and
+    /// `.git/info/exclude` files or not.
+    ToggleShowIgnored,
+    /// Ask to delete the currently selected file or directory. Nothing is deleted until
+    /// [`Input::Confirm`] is received.
     Delete,
+    /// Confirm the pending deletion, or go to the path typed in the "go to path" prompt.
+    Confirm,
+    /// Cancel the pending deletion, or close the "go to path" prompt.
+    Cancel,
+    /// Rename the currently selected entry to the given name, keeping it in the current directory.
+    ///
+    /// The name must be a plain file name: an empty name, `.`, `..`, an absolute path or a name
+    /// containing `/` is rejected with [`ErrorKind::InvalidInput`](std::io::ErrorKind::InvalidInput).
+    Rename(String),
+    /// Copy the currently selected entry into the given directory.
+    CopyTo(PathBuf),
+    /// Move the currently selected entry into the given directory.
+    MoveTo(PathBuf),
+    /// Sort the entries by the next [`SortBy`](crate::SortBy) key.
+    CycleSort,
+    /// Reverse the [`SortOrder`](crate::SortOrder).
+    ToggleSortOrder,
+    /// Toggle between listing directories before files or not.
+    ToggleDirsFirst,
+    /// Add the currently selected entry to the selected paths, or remove it from them.
+    ToggleSelection,
+    /// Select every visible entry between the last toggled entry and the current one.
+    SelectRange,
+    /// Select every visible entry.
+    SelectAll,
+    /// Toggle the selection of every visible entry.
+    InvertSelection,
+    /// Unselect every entry.
+    ClearSelection,
+    /// Capture the selected entries, or the current one, to copy them on [`Input::Paste`].
+    Yank,
+    /// Capture the selected entries, or the current one, to move them on [`Input::Paste`].
+    Cut,
+    /// Paste the captured entries into the current directory.
+    Paste,
+    /// Go back to the previous location, like the back button of a browser.
+    Back,
+    /// Go forward to the location left by going back.
+    Forward,
+    /// Open the "go to path" prompt, see
+    /// [`FileExplorer::start_path_entry`](crate::FileExplorer::start_path_entry).
+    ///
+    /// While the prompt is open, [`Input::Confirm`] and [`Input::Right`] go to the typed path,
+    /// and [`FileExplorer::handle_event`](crate::FileExplorer::handle_event) turns the printable
+    /// characters and Backspace into [`Input::PathEntryInput`]. The default key bindings map
+    /// Tab to [`Input::CompletePath`] and Escape to [`Input::Cancel`].
+    StartPathEntry,
+    /// Set the path typed in the "go to path" prompt.
+    PathEntryInput(String),
+    /// Complete the path typed in the "go to path" prompt.
+    CompletePath,
+    /// Bookmark the current directory under the given mark.
+    ///
+    /// This input has no default key binding, since it needs a second key for the mark
+    /// (e.g. `m` followed by the mark, like in vim).
+    AddBookmark(char),
+    /// Remove the bookmark under the given mark.
+    RemoveBookmark(char),
+    /// Go to the directory bookmarked under the given mark.
+    JumpToBookmark(char),
+    /// Switch the layer of the filter stack having the given name on or off, see
+    /// [`FileExplorer::toggle_filter_layer`](crate::FileExplorer::toggle_filter_layer).
+    ToggleFilterLayer(String),
+    /// Undo the last file operation.
+    Undo,
+    /// Redo the last undone file operation.
+    Redo,
     /// Do nothing (used for converting events from other libraries, like
     /// [crossterm](https://docs.rs/crossterm/latest/crossterm/event/enum.Event.html),
     /// [termion](https://docs.rs/termion/latest/termion/event/enum.Event.html) and
     /// [termwiz](https://docs.rs/termwiz/latest/termwiz/input/enum.InputEvent.html) to [`Input`]).
     None,
 }
+
+/// A terminal event which may edit a line of text, used to type in the "go to path" prompt by
+/// [`FileExplorer::handle_event`](crate::FileExplorer::handle_event).
+///
+/// It is implemented for the events of the enabled terminal backends.
+pub trait TextEdit {
+    /// Returns `text` edited by this event: followed by the character of a printable key, or
+    /// without its last character for Backspace. Returns `None` for any other event.
+    fn edit(&self, text: &str) -> Option<String>;
+}
+
+/// Returns `text` followed by `c`.
+#[cfg(any(feature = "crossterm", feature = "termion", feature = "termwiz"))]
+fn typed(text: &str, c: char) -> String {
+    let mut text = text.to_string();
+    text.push(c);
+    text
+}
+
+/// Returns `text` without its last character.
+#[cfg(any(feature = "crossterm", feature = "termion", feature = "termwiz"))]
+fn erased(text: &str) -> String {
+    let mut text = text.to_string();
+    text.pop();
+    text
+}
@@ -1,7 +1,7 @@
 use ratatui::termion;
 use ratatui::termion::event::{Event, Key};
 
-use super::Input;
+use super::{erased, typed, Input, TextEdit};
 
 impl From<&Event> for Input {
     /// Convert termion [`Event`](https://docs.rs/termion/latest/termion/event/enum.Event.html) to [`Input`].
@@ -19,10 +19,43 @@ impl From<&Event> for Input {
                 Key::PageUp => Input::PageUp,
                 Key::PageDown => Input::PageDown,
                 Key::Ctrl('h') => Input::ToggleShowHidden,
+                Key::Char('I') => Input::ToggleShowIgnored,
                 Key::Char('d') => Input::Delete,
+                Key::Char('y') => Input::Confirm,
+                Key::Char('s') => Input::CycleSort,
+                Key::Char('S') => Input::ToggleSortOrder,
+                Key::Char('D') => Input::ToggleDirsFirst,
+                Key::Char(' ') => Input::ToggleSelection,
+                Key::Char('V') => Input::SelectRange,
+                Key::Ctrl('a') => Input::SelectAll,
+                Key::Char('*') => Input::InvertSelection,
+                Key::Char('U') => Input::ClearSelection,
+                Key::Char('c') => Input::Yank,
+                Key::Char('x') => Input::Cut,
+                Key::Char('p') => Input::Paste,
+                Key::Char('g') => Input::StartPathEntry,
+                Key::Char('\t') => Input::CompletePath,
+                Key::Char('u') => Input::Undo,
+                Key::Char('H') => Input::Back,
+                Key::Char('L') => Input::Forward,
+                Key::Ctrl('r') => Input::Redo,
+                Key::Char('n') | Key::Esc => Input::Cancel,
                 _ => Input::None,
             },
             _ => Input::None,
         }
     }
 }
+
+impl TextEdit for Event {
+    /// Edit `text` with a termion key [`Event`](https://docs.rs/termion/latest/termion/event/enum.Event.html).
+    ///
+    /// **Note:** This implementation is only available when the `termion` feature is enabled.
+    fn edit(&self, text: &str) -> Option<String> {
+        match self {
+            Event::Key(Key::Char(c)) if !c.is_control() => Some(typed(text, *c)),
+            Event::Key(Key::Backspace) => Some(erased(text)),
+            _ => None,
+        }
+    }
+}
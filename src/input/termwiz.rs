@@ -1,7 +1,7 @@
 use ratatui::termwiz;
 use ratatui::termwiz::{input::InputEvent, input::KeyCode, input::Modifiers};
 
-use super::Input;
+use super::{erased, typed, Input, TextEdit};
 
 impl From<&InputEvent> for Input {
     /// Convert termwiz [`InputEvent`](https://docs.rs/termwiz/latest/termwiz/input/enum.InputEvent.html) to [`Input`].
@@ -26,10 +26,50 @@ impl From<&InputEvent> for Input {
                 KeyCode::End => Input::End,
                 KeyCode::PageUp => Input::PageUp,
                 KeyCode::PageDown => Input::PageDown,
+                KeyCode::Char('I') => Input::ToggleShowIgnored,
                 KeyCode::Char('d') => Input::Delete,
+                KeyCode::Char('y') => Input::Confirm,
+                KeyCode::Char('s') => Input::CycleSort,
+                KeyCode::Char('S') => Input::ToggleSortOrder,
+                KeyCode::Char('D') => Input::ToggleDirsFirst,
+                KeyCode::Char(' ') => Input::ToggleSelection,
+                KeyCode::Char('V') => Input::SelectRange,
+                KeyCode::Char('a') if key.modifiers.contains(Modifiers::CTRL) => Input::SelectAll,
+                KeyCode::Char('*') => Input::InvertSelection,
+                KeyCode::Char('U') => Input::ClearSelection,
+                KeyCode::Char('c') => Input::Yank,
+                KeyCode::Char('x') => Input::Cut,
+                KeyCode::Char('p') => Input::Paste,
+                KeyCode::Char('g') => Input::StartPathEntry,
+                KeyCode::Tab => Input::CompletePath,
+                KeyCode::Char('u') => Input::Undo,
+                KeyCode::Char('H') => Input::Back,
+                KeyCode::Char('L') => Input::Forward,
+                KeyCode::Char('r') if key.modifiers.contains(Modifiers::CTRL) => Input::Redo,
+                KeyCode::Char('n') | KeyCode::Escape => Input::Cancel,
                 _ => Input::None,
             },
             _ => Input::None,
         }
     }
 }
+
+impl TextEdit for InputEvent {
+    /// Edit `text` with a termwiz key [`InputEvent`](https://docs.rs/termwiz/latest/termwiz/input/enum.InputEvent.html).
+    ///
+    /// **Note:** This implementation is only available when the `termwiz` feature is enabled.
+    fn edit(&self, text: &str) -> Option<String> {
+        let InputEvent::Key(key) = self else {
+            return None;
+        };
+        if key.modifiers.intersects(Modifiers::CTRL | Modifiers::ALT) {
+            return None;
+        }
+
+        match key.key {
+            KeyCode::Char(c) => Some(typed(text, c)),
+            KeyCode::Backspace => Some(erased(text)),
+            _ => None,
+        }
+    }
+}
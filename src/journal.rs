@@ -0,0 +1,411 @@
+use std::{
+    io::{Error, ErrorKind, Result},
+    path::{Path, PathBuf},
+};
+
+use crate::filesystem::{FilePermissions, FileSystem, LocalFileSystem, Trash, TrashItem};
+
+/// How many entries [`Journal`] keeps before forgetting the oldest ones.
+const JOURNAL_LIMIT: usize = 100;
+
+/// A reversible file operation performed by a [`FileExplorer`](crate::FileExplorer).
+#[derive(educe::Educe)]
+#[educe(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Operation<F: FileSystem = LocalFileSystem> {
+    /// `from` was renamed to `to`.
+    Rename { from: PathBuf, to: PathBuf },
+    /// `from` was moved to `to`, possibly across devices.
+    Move { from: PathBuf, to: PathBuf },
+    /// `from` was copied to `to`.
+    Copy { from: PathBuf, to: PathBuf },
+    /// `path` was created, along with its missing ancestors up to `root`.
+    Create {
+        path: PathBuf,
+        root: PathBuf,
+        is_dir: bool,
+    },
+    /// An entry was moved to `trash`.
+    Trash { trash: Trash<F>, item: TrashItem },
+    /// The permissions of `path` were changed from `from` to `to`.
+    SetPermissions {
+        path: PathBuf,
+        from: FilePermissions,
+        to: FilePermissions,
+    },
+}
+
+impl<F: FileSystem> Operation<F> {
+    /// Perform the operation again, returning it as it should be recorded.
+    pub async fn apply(&self, fs: &F) -> Result<Self> {
+        match self {
+            Self::Rename { from, to } => fs.rename(&lossy(from), &lossy(to)).await?,
+            Self::Move { from, to } => fs.move_to(&lossy(from), &lossy(to)).await?,
+            Self::Copy { from, to } => fs.copy(&lossy(from), &lossy(to)).await?,
+            Self::Create { path, is_dir, .. } => {
+                if *is_dir {
+                    fs.create_dir_all(&lossy(path)).await?;
+                } else {
+                    if let Some(parent) = path.parent() {
+                        fs.create_dir_all(&lossy(parent)).await?;
+                    }
+                    fs.create_file(&lossy(path)).await?;
+                }
+            }
+            Self::Trash { trash, item } => {
+                // Trashing again stores the entry under a new name
+                let item = trash.trash(&lossy(&item.original_path)).await?;
+                return Ok(Self::Trash {
+                    trash: trash.clone(),
+                    item,
+                });
+            }
+            Self::SetPermissions { path, to, .. } => fs.set_permissions(&lossy(path), *to).await?,
+        }
+
+        Ok(self.clone())
+    }
+
+    /// Reverse the operation, returning it as it should be recorded to redo it.
+    pub async fn revert(&self, fs: &F) -> Result<Self> {
+        match self {
+            Self::Rename { from, to } => fs.rename(&lossy(to), &lossy(from)).await?,
+            Self::Move { from, to } => fs.move_to(&lossy(to), &lossy(from)).await?,
+            Self::Copy { to, .. } => remove(fs, to).await?,
+            Self::Create { path, root, is_dir } => {
+                if *is_dir {
+                    remove_empty_dir(fs, path).await?;
+                } else {
+                    fs.delete(&lossy(path)).await?;
+                }
+
+                // Only remove the ancestors that were created along, if still empty
+                let mut dir = path.parent();
+                while let Some(current) = dir.filter(|dir| dir.starts_with(root)) {
+                    remove_empty_dir(fs, current).await?;
+                    dir = current.parent();
+                }
+            }
+            Self::Trash { trash, item } => trash.restore(item).await?,
+            Self::SetPermissions { path, from, .. } => {
+                fs.set_permissions(&lossy(path), *from).await?;
+            }
+        }
+
+        Ok(self.clone())
+    }
+
+    /// Returns the paths the operation creates, deletes or moves entries at.
+    pub fn paths(&self) -> Vec<&Path> {
+        match self {
+            Self::Rename { from, to } | Self::Move { from, to } => vec![from, to],
+            Self::Copy { to, .. } => vec![to],
+            Self::Create { path, .. } | Self::SetPermissions { path, .. } => vec![path],
+            Self::Trash { item, .. } => vec![&item.original_path],
+        }
+    }
+}
+
+/// The undo and redo stacks of a [`FileExplorer`](crate::FileExplorer).
+///
+/// Each entry groups the operations of a single action, such as a batch move.
+#[derive(educe::Educe)]
+#[educe(Debug, Clone)]
+pub(crate) struct Journal<F: FileSystem = LocalFileSystem> {
+    pub undo: Vec<Vec<Operation<F>>>,
+    pub redo: Vec<Vec<Operation<F>>>,
+}
+
+impl<F: FileSystem> Default for Journal<F> {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+}
+
+impl<F: FileSystem> Journal<F> {
+    /// Record a new action, which makes the undone actions impossible to redo.
+    pub fn record(&mut self, operations: Vec<Operation<F>>) {
+        if operations.is_empty() {
+            return;
+        }
+
+        self.undo.push(operations);
+        if self.undo.len() > JOURNAL_LIMIT {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+}
+
+fn lossy(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+async fn remove<F: FileSystem>(fs: &F, path: &Path) -> Result<()> {
+    let path = lossy(path);
+
+    if fs.is_dir(&path).await? {
+        fs.delete_dir_all(&path).await
+    } else {
+        fs.delete(&path).await
+    }
+}
+
+/// Remove the directory `path`, refusing to lose anything that was added to it since.
+async fn remove_empty_dir<F: FileSystem>(fs: &F, path: &Path) -> Result<()> {
+    let path = lossy(path);
+
+    if !fs.read_dir(&path).await?.is_empty() {
+        return Err(Error::new(
+            ErrorKind::DirectoryNotEmpty,
+            format!("{} is not empty anymore", path),
+        ));
+    }
+
+    fs.delete_dir_all(&path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        filesystem::{FilePermissions, FileSystem, InMemoryFileSystem, LocalFileSystem, Trash},
+        FileExplorer, Input,
+    };
+    use std::{io::ErrorKind, sync::Arc};
+
+    #[tokio::test]
+    async fn batch_operations_are_undone_as_a_whole() {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/a.txt", "")
+            .with_file("/b.txt", "")
+            .with_dir("/archive");
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string())
+            .await
+            .unwrap();
+
+        file_explorer.select_file("a.txt");
+        file_explorer.handle(Input::ToggleSelection).await.unwrap();
+        file_explorer.select_file("b.txt");
+        file_explorer.handle(Input::ToggleSelection).await.unwrap();
+        file_explorer.move_selected_to("/archive").await.unwrap();
+        assert!(!file_explorer.filesystem().exists("/a.txt").await.unwrap());
+
+        file_explorer.handle(Input::Undo).await.unwrap();
+        let fs = file_explorer.filesystem();
+        assert!(fs.exists("/a.txt").await.unwrap());
+        assert!(fs.exists("/b.txt").await.unwrap());
+        assert!(fs.read_dir("/archive").await.unwrap().is_empty());
+
+        file_explorer.handle(Input::Redo).await.unwrap();
+        assert_eq!(
+            file_explorer
+                .filesystem()
+                .read_dir("/archive")
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(!file_explorer.can_redo());
+    }
+
+    #[tokio::test]
+    async fn permissions_and_creations() {
+        let fs = InMemoryFileSystem::new().with_file("/run.sh", "");
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string())
+            .await
+            .unwrap();
+
+        file_explorer.handle(Input::ToggleSelection).await.unwrap();
+        file_explorer
+            .chmod_selected(FilePermissions::from_mode(0o755))
+            .await
+            .unwrap();
+        file_explorer.undo().await.unwrap();
+        assert_eq!(
+            file_explorer.current().permissions(),
+            Some(FilePermissions::from_mode(0o644))
+        );
+
+        // Undoing a creation never removes what was added since
+        file_explorer.create_dir("src/bin").await.unwrap();
+        file_explorer
+            .filesystem()
+            .create_file("/src/lib.rs")
+            .await
+            .unwrap();
+        let err = file_explorer.undo().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DirectoryNotEmpty);
+        assert!(file_explorer
+            .filesystem()
+            .exists("/src/lib.rs")
+            .await
+            .unwrap());
+
+        file_explorer.create_file("docs/README.md").await.unwrap();
+        file_explorer.undo().await.unwrap();
+        assert!(!file_explorer.filesystem().exists("/docs").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn only_trashed_deletions_are_undoable() {
+        let dir = tempfile::tempdir().unwrap();
+        let cwd = dir.path().join("files");
+        std::fs::create_dir(&cwd).unwrap();
+        std::fs::write(cwd.join("notes.txt"), "notes").unwrap();
+        std::fs::write(cwd.join("tmp.txt"), "tmp").unwrap();
+
+        let mut file_explorer = FileExplorer::with_fs(
+            Arc::new(LocalFileSystem::new()),
+            cwd.to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+
+        file_explorer.select_file("tmp.txt");
+        file_explorer.handle(Input::Delete).await.unwrap();
+        file_explorer.handle(Input::Confirm).await.unwrap();
+        assert!(!file_explorer.can_undo());
+
+        file_explorer.set_trash(Some(Trash::new(dir.path().join("Trash"))));
+        file_explorer.select_file("notes.txt");
+        file_explorer.handle(Input::Delete).await.unwrap();
+        file_explorer.handle(Input::Confirm).await.unwrap();
+        assert!(!cwd.join("notes.txt").exists());
+
+        file_explorer.handle(Input::Undo).await.unwrap();
+        assert_eq!(std::fs::read(cwd.join("notes.txt")).unwrap(), b"notes");
+
+        file_explorer.handle(Input::Redo).await.unwrap();
+        assert!(!cwd.join("notes.txt").exists());
+        file_explorer.handle(Input::Undo).await.unwrap();
+        assert!(cwd.join("notes.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn batch_failing_midway() {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/a.txt", "")
+            .with_file("/b.txt", "")
+            .with_file("/c.txt", "")
+            .with_dir("/archive");
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string())
+            .await
+            .unwrap();
+
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            file_explorer.select_file(name);
+            file_explorer.handle(Input::ToggleSelection).await.unwrap();
+        }
+        file_explorer.move_selected_to("/archive").await.unwrap();
+
+        // Moving b.txt back fails, after c.txt was moved back
+        file_explorer
+            .filesystem()
+            .create_dir("/b.txt")
+            .await
+            .unwrap();
+        let err = file_explorer.undo().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+        let fs = file_explorer.filesystem();
+        assert!(fs.exists("/c.txt").await.unwrap());
+        assert!(fs.exists("/archive/a.txt").await.unwrap());
+        assert!(fs.exists("/archive/b.txt").await.unwrap());
+        assert!(file_explorer.can_undo());
+        assert!(file_explorer.can_redo());
+
+        // The rest of the batch can still be undone, and everything redone in order
+        file_explorer
+            .filesystem()
+            .delete_dir_all("/b.txt")
+            .await
+            .unwrap();
+        assert!(file_explorer.undo().await.unwrap());
+        assert!(!file_explorer.can_undo());
+        assert!(file_explorer.redo().await.unwrap());
+        assert!(file_explorer.redo().await.unwrap());
+        assert!(!file_explorer.can_redo());
+        assert_eq!(
+            file_explorer
+                .filesystem()
+                .read_dir("/archive")
+                .await
+                .unwrap()
+                .len(),
+            3
+        );
+
+        // Likewise, redoing stops at the failing operation
+        assert!(file_explorer.undo().await.unwrap());
+        assert!(file_explorer.undo().await.unwrap());
+        file_explorer
+            .filesystem()
+            .create_dir("/archive/b.txt")
+            .await
+            .unwrap();
+        assert!(file_explorer.redo().await.is_err());
+        let fs = file_explorer.filesystem();
+        assert!(fs.exists("/archive/a.txt").await.unwrap());
+        assert!(fs.exists("/b.txt").await.unwrap());
+        assert!(fs.exists("/c.txt").await.unwrap());
+
+        file_explorer
+            .filesystem()
+            .delete_dir_all("/archive/b.txt")
+            .await
+            .unwrap();
+        for _ in 0..2 {
+            assert!(file_explorer.redo().await.unwrap());
+        }
+        assert!(!file_explorer.can_redo());
+        for _ in 0..3 {
+            assert!(file_explorer.undo().await.unwrap());
+        }
+        assert!(!file_explorer.can_undo());
+        assert_eq!(
+            file_explorer
+                .filesystem()
+                .read_dir("/archive")
+                .await
+                .unwrap()
+                .len(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn copy_is_recorded_before_listing() {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/work/notes.txt", "")
+            .with_dir("/backup");
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/work".to_string())
+            .await
+            .unwrap();
+        file_explorer.select_file("notes.txt");
+
+        // The copy succeeds, but the current directory can not be listed afterwards
+        let (readable, unreadable) = (
+            FilePermissions::from_mode(0o755),
+            FilePermissions::from_mode(0o300),
+        );
+        let fs = file_explorer.filesystem();
+        fs.set_permissions("/work", unreadable).await.unwrap();
+        let err = file_explorer
+            .handle(Input::CopyTo("/backup".into()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let fs = file_explorer.filesystem();
+        assert!(fs.exists("/backup/notes.txt").await.unwrap());
+
+        fs.set_permissions("/work", readable).await.unwrap();
+        assert!(file_explorer.undo().await.unwrap());
+        assert!(!file_explorer
+            .filesystem()
+            .exists("/backup/notes.txt")
+            .await
+            .unwrap());
+    }
+}
@@ -1,22 +1,46 @@
 #![doc = include_str!("../README.md")]
 //! # Features
-//! - `crossterm` (default): Enables the [`From<&Event>`](enum.Input.html#method.from-2) implementation for [`Input`].
-//! - `termion`: Enables the [`From<&Event>`](enum.Input.html#method.from-1) implementation for [`Input`].
-//! - `termwiz`: Enables the [`From<&InputEvent>`](enum.Input.html#method.from) implementation for [`Input`].
+//! - `crossterm` (default): Enables the [`From<&Event>`](enum.Input.html#method.from-2) implementation for [`Input`], and the [`TextEdit`] implementation of `Event`.
+//! - `termion`: Enables the [`From<&Event>`](enum.Input.html#method.from-1) implementation for [`Input`], and the [`TextEdit`] implementation of `Event`.
+//! - `termwiz`: Enables the [`From<&InputEvent>`](enum.Input.html#method.from) implementation for [`Input`], and the [`TextEdit`] implementation of `InputEvent`.
 
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 #![warn(rustdoc::missing_crate_level_docs)]
 #![warn(rustdoc::unescaped_backticks)]
+mod bookmarks;
+mod clipboard;
 mod file_explorer;
+mod filter;
+mod frecency;
+mod fuzzy;
+mod git;
+mod gitignore;
+mod history;
 mod icon;
 mod input;
+mod journal;
+mod path_entry;
+mod positions;
+mod preview;
+mod search;
+mod sort;
 mod widget;
 
 pub mod filesystem;
 
-pub use file_explorer::{File, FileExplorer, FileFilter};
+pub use bookmarks::Bookmarks;
+pub use clipboard::{Clipboard, ClipboardMode, ConflictPolicy};
+pub use file_explorer::{BatchResults, File, FileExplorer, FileFilter};
 pub use filesystem::{FileEntry, FilePermissions, FileSystem, LocalFileSystem};
+pub use filter::{EntryKind, FilterLayer, FilterStack, GlobPatterns};
+pub use frecency::Frecency;
+pub use git::GitStatus;
+pub use history::HistoryEntry;
 pub use icon::IconDisplay;
-pub use input::Input;
+pub use input::{Input, TextEdit};
+pub use path_entry::PathEntry;
+pub use preview::{Preview, PreviewContent, PreviewLoader, PreviewOptions};
+pub use search::{ContentMatch, Search, SearchKind, SearchOptions};
+pub use sort::{SortBy, SortOrder};
 pub use widget::{StatefulRenderer, Theme};
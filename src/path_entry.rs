@@ -0,0 +1,236 @@
+use std::path::{Component, Path, PathBuf};
+
+/// The state of the "go to path" prompt of a [`FileExplorer`](crate::FileExplorer), opened
+/// with [`Input::StartPathEntry`](crate::Input::StartPathEntry).
+///
+/// It holds the path typed so far and the completions offered for it, which are read from
+/// the parent directory of the typed path through the [`FileSystem`](crate::FileSystem)
+/// trait, so they work on any backend.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathEntry {
+    pub(crate) input: String,
+    pub(crate) completions: Vec<String>,
+    pub(crate) selected_completion: Option<usize>,
+}
+
+impl PathEntry {
+    /// Returns the path typed so far.
+    #[inline]
+    #[must_use]
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Returns the completions of the typed path, in the order they are cycled through. Every
+    /// completion is a whole path replacing the input, directories ending with a `/`.
+    #[inline]
+    #[must_use]
+    pub fn completions(&self) -> &[String] {
+        &self.completions
+    }
+
+    /// Returns the index of the completion the input was last completed to, if it was
+    /// completed by cycling through several of them.
+    #[inline]
+    #[must_use]
+    pub fn selected_completion(&self) -> Option<usize> {
+        self.selected_completion
+    }
+
+    /// Completes the input, like the Tab key of a shell: to the only completion, to the
+    /// prefix shared by every completion, and otherwise to each completion in turn.
+    ///
+    /// Returns `true` when the input became a whole completion, whose own completions must
+    /// then be read.
+    pub(crate) fn complete(&mut self) -> bool {
+        if self.completions.is_empty() {
+            return false;
+        }
+
+        if let Some(idx) = self.selected_completion {
+            let idx = (idx + 1) % self.completions.len();
+            self.selected_completion = Some(idx);
+            self.input.clone_from(&self.completions[idx]);
+            return false;
+        }
+
+        if let [completion] = self.completions.as_slice() {
+            self.input.clone_from(completion);
+            return true;
+        }
+
+        let prefix = common_prefix(&self.completions);
+        if prefix.len() > self.input.len() {
+            self.input = prefix.to_string();
+        } else {
+            self.selected_completion = Some(0);
+            self.input.clone_from(&self.completions[0]);
+        }
+
+        false
+    }
+}
+
+/// Splits `input` into the directory to read the completions from, the typed part of that
+/// directory, and the prefix of the entry names to complete.
+///
+/// Relative paths are resolved from `cwd`.
+pub(crate) fn completion_parts<'a>(input: &'a str, cwd: &Path) -> (PathBuf, &'a str, &'a str) {
+    let (typed_dir, prefix) = match input.rfind('/') {
+        Some(idx) => input.split_at(idx + 1),
+        None => ("", input),
+    };
+
+    (resolve(typed_dir, cwd), typed_dir, prefix)
+}
+
+/// Expands `input` and resolves it from `cwd`, removing the `.` and `..` components without
+/// following symbolic links.
+pub(crate) fn resolve(input: &str, cwd: &Path) -> PathBuf {
+    let mut resolved = PathBuf::new();
+
+    for component in cwd.join(expand(input)).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            component => resolved.push(component),
+        }
+    }
+
+    resolved
+}
+
+/// Expands a leading `~` to the home directory and `$VAR` or `${VAR}` to the value of the
+/// environment variable. Unknown variables are left untouched.
+pub(crate) fn expand(input: &str) -> String {
+    let mut expanded = String::with_capacity(input.len());
+
+    let mut rest = input;
+    if rest == "~" || rest.starts_with("~/") {
+        if let Some(home) = std::env::var_os("HOME") {
+            expanded.push_str(&home.to_string_lossy());
+            rest = &rest[1..];
+        }
+    }
+
+    while let Some(idx) = rest.find('$') {
+        expanded.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+
+        let (name, len) = match rest[1..].strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], end + 3),
+                None => ("", 1),
+            },
+            None => {
+                let end = rest[1..]
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len() - 1);
+                (&rest[1..=end], end + 1)
+            }
+        };
+
+        match std::env::var_os(name).filter(|_| !name.is_empty()) {
+            Some(value) => expanded.push_str(&value.to_string_lossy()),
+            None => expanded.push_str(&rest[..len]),
+        }
+        rest = &rest[len..];
+    }
+    expanded.push_str(rest);
+
+    expanded
+}
+
+/// Returns the longest prefix shared by every string, on a character boundary.
+fn common_prefix(strings: &[String]) -> &str {
+    let first = &strings[0];
+    let len = strings[1..].iter().fold(first.len(), |len, string| {
+        first[..len]
+            .char_indices()
+            .zip(string.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(len.min(string.len()), |((idx, _), _)| idx)
+    });
+
+    &first[..len]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{filesystem::InMemoryFileSystem, FileExplorer};
+
+    use super::*;
+
+    #[test]
+    fn expansion() {
+        std::env::set_var("PATH_ENTRY_TEST", "value");
+        let home = std::env::var("HOME").unwrap();
+
+        assert_eq!(expand("~/src"), format!("{}/src", home));
+        assert_eq!(expand("~user/src"), "~user/src");
+        assert_eq!(expand("/a/$PATH_ENTRY_TEST/b"), "/a/value/b");
+        assert_eq!(expand("/a/${PATH_ENTRY_TEST}b"), "/a/valueb");
+        assert_eq!(expand("/a/$PATH_ENTRY_UNSET/${"), "/a/$PATH_ENTRY_UNSET/${");
+        assert_eq!(expand("$"), "$");
+
+        let cwd = Path::new("/home/me");
+        assert_eq!(resolve("../you/./src", cwd), Path::new("/home/you/src"));
+        assert_eq!(resolve("/etc", cwd), Path::new("/etc"));
+    }
+
+    #[test]
+    fn completion() {
+        tokio_test::block_on(async {
+            let fs = InMemoryFileSystem::new()
+                .with_dir("/project/src")
+                .with_dir("/project/scripts")
+                .with_file("/project/src/main.rs", "")
+                .with_file("/project/.env", "");
+            let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string())
+                .await
+                .unwrap();
+
+            file_explorer.start_path_entry().await;
+            file_explorer.set_path_entry_input("proj").await;
+            file_explorer.complete_path_entry().await;
+            let entry = file_explorer.path_entry().unwrap();
+            assert_eq!(entry.input(), "project/");
+            assert_eq!(entry.completions(), ["project/scripts/", "project/src/"]);
+
+            // Completes the common prefix, then cycles through the completions
+            file_explorer.complete_path_entry().await;
+            assert_eq!(file_explorer.path_entry().unwrap().input(), "project/s");
+            file_explorer.complete_path_entry().await;
+            assert_eq!(
+                file_explorer.path_entry().unwrap().input(),
+                "project/scripts/"
+            );
+            file_explorer.complete_path_entry().await;
+            assert_eq!(file_explorer.path_entry().unwrap().input(), "project/src/");
+
+            // Hidden entries are only offered when asked for
+            file_explorer.set_path_entry_input("/project/.").await;
+            assert_eq!(
+                file_explorer.path_entry().unwrap().completions(),
+                ["/project/.env"]
+            );
+
+            file_explorer
+                .set_path_entry_input("/project/src/main.rs")
+                .await;
+            file_explorer.submit_path_entry().await.unwrap();
+            assert!(file_explorer.path_entry().is_none());
+            assert_eq!(file_explorer.cwd(), Path::new("/project/src"));
+            assert_eq!(file_explorer.current().name(), "main.rs");
+
+            file_explorer.start_path_entry().await;
+            file_explorer.set_path_entry_input("../missing").await;
+            assert!(file_explorer.submit_path_entry().await.is_err());
+            assert!(file_explorer.path_entry().is_some());
+        });
+    }
+}
@@ -0,0 +1,125 @@
+use std::{collections::HashMap, path::PathBuf};
+
+/// How many directories [`Positions`] remembers before forgetting the least recently used.
+const POSITIONS_LIMIT: usize = 256;
+
+/// Where the user was in a directory when leaving it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Position {
+    /// The name of the selected entry, as returned by [`File::name`](crate::File::name).
+    pub selected: String,
+    pub scroll_offset: usize,
+}
+
+/// A least recently used map from directories to the position the user left them at.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Positions {
+    entries: HashMap<PathBuf, (Position, u64)>,
+    clock: u64,
+}
+
+impl Positions {
+    /// Remember the position in `dir`, forgetting the least recently used directory if full.
+    pub fn insert(&mut self, dir: PathBuf, position: Position) {
+        self.clock += 1;
+        self.entries.insert(dir, (position, self.clock));
+
+        if self.entries.len() > POSITIONS_LIMIT {
+            if let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(dir, _)| dir.clone())
+            {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    /// Returns the position the user left `dir` at, marking it as recently used.
+    pub fn get(&mut self, dir: &PathBuf) -> Option<Position> {
+        self.clock += 1;
+        let (position, used) = self.entries.get_mut(dir)?;
+        *used = self.clock;
+
+        Some(position.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{filesystem::InMemoryFileSystem, FileExplorer, Input};
+    use std::sync::Arc;
+
+    fn position(selected: &str) -> Position {
+        Position {
+            selected: selected.to_string(),
+            scroll_offset: 0,
+        }
+    }
+
+    #[test]
+    fn least_recently_used_is_forgotten() {
+        let mut positions = Positions::default();
+        for i in 0..POSITIONS_LIMIT {
+            positions.insert(PathBuf::from(format!("/{}", i)), position("a"));
+        }
+
+        // Using the oldest directory makes the second oldest the one to forget
+        assert!(positions.get(&PathBuf::from("/0")).is_some());
+        positions.insert(PathBuf::from("/new"), position("b"));
+
+        assert!(positions.get(&PathBuf::from("/0")).is_some());
+        assert!(positions.get(&PathBuf::from("/1")).is_none());
+        assert_eq!(positions.get(&PathBuf::from("/new")), Some(position("b")));
+    }
+
+    #[tokio::test]
+    async fn positions_come_back_when_reentering() {
+        let fs = InMemoryFileSystem::from_tree(
+            "/\n├── docs/\n│   ├── a.md\n│   ├── b.md\n│   └── c.md\n└── src/\n    └── main.rs",
+        );
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string())
+            .await
+            .unwrap();
+
+        file_explorer.handle(Input::Right).await.unwrap();
+        file_explorer.select_file("c.md");
+        file_explorer.handle(Input::Left).await.unwrap();
+        assert_eq!(file_explorer.current().name(), "docs/");
+
+        file_explorer.handle(Input::Right).await.unwrap();
+        assert_eq!(file_explorer.current().name(), "c.md");
+
+        file_explorer.set_cwd("/src").await.unwrap();
+        file_explorer.set_cwd("/docs").await.unwrap();
+        assert_eq!(file_explorer.current().name(), "c.md");
+
+        // Directories never visited start at the top
+        file_explorer.set_cwd("/").await.unwrap();
+        file_explorer.select_file("src/");
+        file_explorer.handle(Input::Right).await.unwrap();
+        assert_eq!(file_explorer.current().name(), "../");
+    }
+
+    #[tokio::test]
+    async fn going_up() {
+        let fs = InMemoryFileSystem::from_tree(
+            "/\n├── docs/\n│   └── a.md\n├── src/\n│   └── main.rs\n└── tests/",
+        );
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/src".to_string())
+            .await
+            .unwrap();
+
+        // Without a position in the parent, the directory we came from is selected
+        file_explorer.handle(Input::Left).await.unwrap();
+        assert_eq!(file_explorer.current().name(), "src/");
+
+        // Otherwise the position is restored
+        file_explorer.select_file("tests/");
+        file_explorer.set_cwd("/docs").await.unwrap();
+        file_explorer.handle(Input::Left).await.unwrap();
+        assert_eq!(file_explorer.current().name(), "tests/");
+    }
+}
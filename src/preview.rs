@@ -0,0 +1,495 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Text},
+    widgets::{Block, Paragraph, Widget},
+};
+
+use crate::{filesystem::FileSystem, sort::SortSettings, File};
+
+/// The number of spaces a tab is rendered as by the [`Preview`] widget.
+const TAB_WIDTH: usize = 4;
+
+/// Options of the previews loaded by a [`FileExplorer`](crate::FileExplorer), see
+/// [`FileExplorer::set_preview`](crate::FileExplorer::set_preview).
+///
+/// # Examples
+///
+/// ```
+/// use ratatui_async_explorer::PreviewOptions;
+///
+/// let options = PreviewOptions::new().with_max_bytes(4096);
+/// assert_eq!(options.max_bytes(), 4096);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PreviewOptions {
+    max_bytes: usize,
+    max_entries: usize,
+}
+
+impl Default for PreviewOptions {
+    fn default() -> Self {
+        Self {
+            max_bytes: 64 * 1024,
+            max_entries: 1000,
+        }
+    }
+}
+
+impl PreviewOptions {
+    /// Creates the default options: the first 64 KiB of the files and the first 1000 entries
+    /// of the directories are previewed.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of bytes read at most from a previewed file.
+    #[must_use]
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Sets the number of entries listed at most in the preview of a directory.
+    #[must_use]
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Returns the number of bytes read at most from a previewed file.
+    #[inline]
+    #[must_use]
+    pub const fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Returns the number of entries listed at most in the preview of a directory.
+    #[inline]
+    #[must_use]
+    pub const fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+}
+
+/// What a [`PreviewLoader`] has loaded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PreviewContent {
+    /// The preview is still loading.
+    Loading,
+    /// The beginning of a text file, `truncated` if the file is longer than
+    /// [`PreviewOptions::max_bytes`].
+    Text {
+        /// The text read, invalid UTF-8 being replaced by `U+FFFD`.
+        text: String,
+        /// Whether the file goes on past the text.
+        truncated: bool,
+    },
+    /// A file whose beginning contains a NUL byte, like `grep` detects binary files.
+    Binary,
+    /// The entries of a directory, sorted like the entries of the
+    /// [`FileExplorer`](crate::FileExplorer), `truncated` if there are more than
+    /// [`PreviewOptions::max_entries`].
+    Directory {
+        /// The entries listed.
+        entries: Vec<File>,
+        /// Whether there are entries left out.
+        truncated: bool,
+    },
+    /// The file or directory cannot be read.
+    Error(String),
+}
+
+/// The preview of the current entry of a [`FileExplorer`](crate::FileExplorer), loaded in the
+/// background, see [`FileExplorer::poll_preview`](crate::FileExplorer::poll_preview).
+#[derive(Debug, Clone)]
+pub struct PreviewLoader {
+    path: PathBuf,
+    content: PreviewContent,
+    state: Arc<State>,
+}
+
+/// The state shared between a [`PreviewLoader`] and the task loading the preview.
+#[derive(Debug, Default)]
+struct State {
+    /// The loaded content, until it is taken.
+    content: Mutex<Option<PreviewContent>>,
+    cancelled: AtomicBool,
+}
+
+impl PreviewLoader {
+    /// Spawns a task loading the preview of `file`.
+    pub(crate) fn spawn<F: FileSystem + 'static>(
+        filesystem: Arc<F>,
+        file: &File,
+        show_hidden: bool,
+        sort: SortSettings,
+        options: PreviewOptions,
+    ) -> Self {
+        let state = Arc::new(State::default());
+        tokio::spawn(load(
+            filesystem,
+            file.clone(),
+            show_hidden,
+            sort,
+            options,
+            state.clone(),
+        ));
+
+        Self {
+            path: file.path().to_path_buf(),
+            content: PreviewContent::Loading,
+            state,
+        }
+    }
+
+    /// Returns the path of the previewed file or directory.
+    #[inline]
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns what has been loaded so far.
+    #[inline]
+    #[must_use]
+    pub const fn content(&self) -> &PreviewContent {
+        &self.content
+    }
+
+    /// Returns whether the preview is still loading.
+    #[inline]
+    #[must_use]
+    pub const fn is_loading(&self) -> bool {
+        matches!(self.content, PreviewContent::Loading)
+    }
+
+    /// Makes the task drop what it loads, since the preview is stale.
+    pub(crate) fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Takes the content loaded by the task, returning `true` if there was some.
+    pub(crate) fn poll(&mut self) -> bool {
+        let content = self
+            .state
+            .content
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+
+        match content {
+            Some(content) => {
+                self.content = content;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Load the preview of `file`, dropping it once cancelled.
+async fn load<F: FileSystem>(
+    filesystem: Arc<F>,
+    file: File,
+    show_hidden: bool,
+    sort: SortSettings,
+    options: PreviewOptions,
+    state: Arc<State>,
+) {
+    // The selection may have moved on before the task started
+    if state.cancelled.load(Ordering::Acquire) {
+        return;
+    }
+
+    let path = file.path().to_string_lossy();
+
+    let content = if file.is_dir() {
+        match filesystem.read_dir(&path).await {
+            Ok(entries) => {
+                let mut entries: Vec<File> = entries
+                    .into_iter()
+                    .filter(|entry| show_hidden || !entry.is_hidden)
+                    .map(File::from_entry)
+                    .collect();
+                sort.sort(&mut entries);
+
+                let truncated = entries.len() > options.max_entries;
+                entries.truncate(options.max_entries);
+                PreviewContent::Directory { entries, truncated }
+            }
+            Err(e) => PreviewContent::Error(e.to_string()),
+        }
+    } else if file.is_file() {
+        // One more byte tells whether the file goes on
+        match filesystem
+            .read_chunk(&path, 0, options.max_bytes.saturating_add(1))
+            .await
+        {
+            Ok(mut bytes) => {
+                let truncated = bytes.len() > options.max_bytes;
+                bytes.truncate(options.max_bytes);
+                decode(bytes, truncated)
+            }
+            Err(e) => PreviewContent::Error(e.to_string()),
+        }
+    } else {
+        PreviewContent::Error("not a regular file".to_string())
+    };
+
+    if !state.cancelled.load(Ordering::Acquire) {
+        *state.content.lock().unwrap_or_else(|e| e.into_inner()) = Some(content);
+    }
+}
+
+/// Decode the beginning of a file, dropping a character cut by the end of a truncated read.
+fn decode(mut bytes: Vec<u8>, truncated: bool) -> PreviewContent {
+    if bytes.contains(&0) {
+        return PreviewContent::Binary;
+    }
+
+    if truncated {
+        if let Err(e) = std::str::from_utf8(&bytes) {
+            if e.error_len().is_none() {
+                bytes.truncate(e.valid_up_to());
+            }
+        }
+    }
+
+    PreviewContent::Text {
+        text: String::from_utf8_lossy(&bytes).into_owned(),
+        truncated,
+    }
+}
+
+/// A widget rendering a [`PreviewContent`], typically next to the file explorer.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ratatui::widgets::*;
+/// use ratatui_async_explorer::{FileExplorer, Preview, PreviewContent, PreviewOptions};
+///
+/// # tokio_test::block_on(async {
+/// let mut file_explorer = FileExplorer::new().await.unwrap();
+/// file_explorer.set_preview(Some(PreviewOptions::new()));
+///
+/// // On every tick of the event loop
+/// file_explorer.poll_preview();
+/// let content = file_explorer
+///     .preview()
+///     .map_or(&PreviewContent::Loading, |preview| preview.content());
+/// let widget = Preview::new(content).block(Block::default().borders(Borders::ALL));
+/// /* render the widget */
+/// # })
+/// ```
+#[derive(Debug, Clone)]
+pub struct Preview<'a> {
+    content: &'a PreviewContent,
+    block: Option<Block<'a>>,
+    style: Style,
+    dir_style: Style,
+    placeholder_style: Style,
+}
+
+impl<'a> Preview<'a> {
+    /// Creates a widget rendering `content`, with directories in light blue and the
+    /// placeholders, like "Loading…", in dark gray.
+    #[must_use]
+    pub fn new(content: &'a PreviewContent) -> Self {
+        Self {
+            content,
+            block: None,
+            style: Style::default(),
+            dir_style: Style::default().fg(Color::LightBlue),
+            placeholder_style: Style::default().fg(Color::DarkGray),
+        }
+    }
+
+    /// Wraps the widget in a block.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Sets the style of the text and of the files.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn style<S: Into<Style>>(mut self, style: S) -> Self {
+        self.style = style.into();
+        self
+    }
+
+    /// Sets the style of the directories listed in the preview of a directory.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn dir_style<S: Into<Style>>(mut self, dir_style: S) -> Self {
+        self.dir_style = dir_style.into();
+        self
+    }
+
+    /// Sets the style of the placeholders, shown while loading, for binary files, empty
+    /// directories, errors and truncated previews.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn placeholder_style<S: Into<Style>>(mut self, placeholder_style: S) -> Self {
+        self.placeholder_style = placeholder_style.into();
+        self
+    }
+
+    /// Returns the lines to render.
+    fn text(&self) -> Text<'a> {
+        let placeholder = |text: &str| Line::styled(text.to_string(), self.placeholder_style);
+
+        match self.content {
+            PreviewContent::Loading => placeholder("Loading…").into(),
+            PreviewContent::Binary => placeholder("<binary file>").into(),
+            PreviewContent::Error(e) => placeholder(e).into(),
+            PreviewContent::Text { text, truncated } => {
+                let mut lines: Vec<Line> = text
+                    .lines()
+                    .map(|line| {
+                        Line::styled(line.replace('\t', &" ".repeat(TAB_WIDTH)), self.style)
+                    })
+                    .collect();
+                if *truncated {
+                    lines.push(placeholder("…"));
+                }
+                lines.into()
+            }
+            PreviewContent::Directory { entries, truncated } => {
+                if entries.is_empty() {
+                    return placeholder("<empty directory>").into();
+                }
+
+                let mut lines: Vec<Line> = entries
+                    .iter()
+                    .map(|entry| {
+                        let style = if entry.is_dir() {
+                            self.dir_style
+                        } else {
+                            self.style
+                        };
+                        Line::styled(entry.name().to_string(), style)
+                    })
+                    .collect();
+                if *truncated {
+                    lines.push(placeholder("…"));
+                }
+                lines.into()
+            }
+        }
+    }
+}
+
+impl Widget for Preview<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut paragraph = Paragraph::new(self.text()).style(self.style);
+        if let Some(block) = self.block {
+            paragraph = paragraph.block(block);
+        }
+
+        paragraph.render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{filesystem::InMemoryFileSystem, FileExplorer, Input};
+
+    use super::*;
+
+    async fn explorer() -> FileExplorer<InMemoryFileSystem> {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/dir/a.txt", "")
+            .with_file("/dir/.hidden", "")
+            .with_dir("/dir/sub")
+            .with_file("/image.png", b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec())
+            .with_file("/notes.txt", "first line\nsecond line\n")
+            .with_file("/long.txt", "héllo wörld");
+
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string())
+            .await
+            .unwrap();
+        file_explorer.set_preview(Some(PreviewOptions::new().with_max_bytes(9)));
+        file_explorer
+    }
+
+    async fn finish(file_explorer: &mut FileExplorer<InMemoryFileSystem>) -> PreviewContent {
+        file_explorer.poll_preview();
+        while file_explorer.preview().unwrap().is_loading() {
+            tokio::task::yield_now().await;
+            file_explorer.poll_preview();
+        }
+        file_explorer.preview().unwrap().content().clone()
+    }
+
+    #[tokio::test]
+    async fn contents() {
+        let mut file_explorer = explorer().await;
+
+        file_explorer.select_file("dir/");
+        let PreviewContent::Directory { entries, truncated } = finish(&mut file_explorer).await
+        else {
+            panic!("expected a directory preview");
+        };
+        let names: Vec<_> = entries.iter().map(File::name).collect();
+        assert_eq!(names, ["sub/", "a.txt"]);
+        assert!(!truncated);
+
+        file_explorer.select_file("image.png");
+        assert_eq!(finish(&mut file_explorer).await, PreviewContent::Binary);
+
+        // The "ö" cut by the limit is dropped
+        file_explorer.select_file("long.txt");
+        assert_eq!(
+            finish(&mut file_explorer).await,
+            PreviewContent::Text {
+                text: "héllo w".to_string(),
+                truncated: true
+            }
+        );
+
+        file_explorer.set_preview(Some(PreviewOptions::new()));
+        file_explorer.select_file("notes.txt");
+        assert_eq!(
+            finish(&mut file_explorer).await,
+            PreviewContent::Text {
+                text: "first line\nsecond line\n".to_string(),
+                truncated: false
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn stale() {
+        let mut file_explorer = explorer().await;
+        file_explorer.select_file("dir/");
+        assert!(file_explorer.poll_preview());
+        let stale = file_explorer.preview().unwrap().clone();
+
+        // Moving the selection cancels the load of the previous entry
+        file_explorer.handle(Input::Down).await.unwrap();
+        assert!(file_explorer.poll_preview());
+        assert!(stale.state.cancelled.load(Ordering::Acquire));
+        assert_eq!(
+            file_explorer.preview().unwrap().path(),
+            file_explorer.current().path()
+        );
+
+        finish(&mut file_explorer).await;
+        assert!(!file_explorer.poll_preview());
+
+        file_explorer.set_preview(None);
+        assert!(file_explorer.preview().is_none());
+        assert!(!file_explorer.poll_preview());
+    }
+}
@@ -0,0 +1,646 @@
+use std::{
+    collections::VecDeque,
+    io::{Error, ErrorKind, Result},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use regex::{Regex, RegexBuilder};
+use tokio::task::AbortHandle;
+
+use crate::{
+    filesystem::{FileEntry, FileSystem},
+    File,
+};
+
+/// The size of the chunks files are read by during a content search.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Options of a recursive search, see [`FileExplorer::start_search`](crate::FileExplorer::start_search)
+/// and [`FileExplorer::start_content_search`](crate::FileExplorer::start_content_search).
+///
+/// # Examples
+///
+/// ```
+/// use ratatui_async_explorer::SearchOptions;
+///
+/// let options = SearchOptions::new().with_max_depth(3).with_show_hidden(true);
+/// assert_eq!(options.max_depth(), Some(3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SearchOptions {
+    max_depth: Option<usize>,
+    show_hidden: bool,
+    regex: bool,
+    case_sensitive: bool,
+    max_file_size: u64,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: None,
+            show_hidden: false,
+            regex: false,
+            case_sensitive: false,
+            max_file_size: 1024 * 1024,
+        }
+    }
+}
+
+impl SearchOptions {
+    /// Creates the default options: no depth limit, hidden entries skipped, and the query
+    /// matched literally, ignoring the case, in the first MiB of every file.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the search to `max_depth` levels below the current working directory, `1`
+    /// searching its entries only.
+    #[must_use]
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets whether hidden entries are searched, and hidden directories walked into.
+    #[must_use]
+    pub fn with_show_hidden(mut self, show_hidden: bool) -> Self {
+        self.show_hidden = show_hidden;
+        self
+    }
+
+    /// Sets whether the query is a [regular expression](https://docs.rs/regex/latest/regex/#syntax)
+    /// rather than literal text.
+    #[must_use]
+    pub fn with_regex(mut self, regex: bool) -> Self {
+        self.regex = regex;
+        self
+    }
+
+    /// Sets whether the query is matched with respect to the case.
+    #[must_use]
+    pub fn with_case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Sets the number of bytes read at most from every file by a content search. The rest
+    /// of larger files is not searched.
+    #[must_use]
+    pub fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Returns the number of levels searched below the current working directory, if limited.
+    #[inline]
+    #[must_use]
+    pub const fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Returns whether hidden entries are searched.
+    #[inline]
+    #[must_use]
+    pub const fn show_hidden(&self) -> bool {
+        self.show_hidden
+    }
+
+    /// Returns whether the query is a regular expression.
+    #[inline]
+    #[must_use]
+    pub const fn regex(&self) -> bool {
+        self.regex
+    }
+
+    /// Returns whether the query is matched with respect to the case.
+    #[inline]
+    #[must_use]
+    pub const fn case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// Returns the number of bytes read at most from every file by a content search.
+    #[inline]
+    #[must_use]
+    pub const fn max_file_size(&self) -> u64 {
+        self.max_file_size
+    }
+}
+
+/// What a [`Search`] matches its query against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchKind {
+    /// The names of the entries.
+    Name,
+    /// The lines of the files, like `grep -r`.
+    Content,
+}
+
+/// A line matched by a content search.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContentMatch {
+    file: File,
+    line_number: usize,
+    line: String,
+}
+
+impl ContentMatch {
+    /// Returns the file holding the line, named after its path relative to the searched
+    /// directory.
+    #[inline]
+    #[must_use]
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+
+    /// Returns the number of the line in the file, starting at 1.
+    #[inline]
+    #[must_use]
+    pub const fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Returns the text of the line, without its line ending.
+    #[inline]
+    #[must_use]
+    pub fn line(&self) -> &str {
+        &self.line
+    }
+}
+
+/// A recursive search running in the background of a [`FileExplorer`](crate::FileExplorer).
+///
+/// The directories stop being walked once the search and all its clones are dropped, e.g.
+/// along with the file explorer.
+#[derive(Debug, Clone)]
+pub struct Search {
+    query: String,
+    kind: SearchKind,
+    options: SearchOptions,
+    content_matches: Vec<ContentMatch>,
+    state: Arc<State>,
+    _walk: Arc<WalkGuard>,
+}
+
+/// Aborts the task walking the directories when dropped.
+#[derive(Debug)]
+struct WalkGuard(AbortHandle);
+
+impl Drop for WalkGuard {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// An entry found by the task walking the directories, named after its path relative to
+/// the searched directory, with the matched line of a content search.
+#[derive(Debug)]
+pub(crate) struct Found {
+    pub(crate) entry: FileEntry,
+    pub(crate) line: Option<(usize, String)>,
+}
+
+/// The state shared between a [`Search`] and the task walking the directories.
+#[derive(Debug, Default)]
+struct State {
+    /// Entries found since they were last taken.
+    found: Mutex<Vec<Found>>,
+    finished: AtomicBool,
+    cancelled: AtomicBool,
+}
+
+impl State {
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+}
+
+impl Search {
+    /// Spawns a task walking `root` and collecting what matches `query`.
+    pub(crate) fn spawn<F: FileSystem + 'static>(
+        filesystem: Arc<F>,
+        root: PathBuf,
+        query: String,
+        kind: SearchKind,
+        options: SearchOptions,
+    ) -> Result<Self> {
+        let pattern = if options.regex {
+            query.clone()
+        } else {
+            regex::escape(&query)
+        };
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(!options.case_sensitive)
+            .build()
+            .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+
+        let state = Arc::new(State::default());
+        let task = tokio::spawn(walk(filesystem, root, regex, kind, options, state.clone()));
+
+        Ok(Self {
+            query,
+            kind,
+            options,
+            content_matches: Vec::new(),
+            state,
+            _walk: Arc::new(WalkGuard(task.abort_handle())),
+        })
+    }
+
+    /// Returns the searched text.
+    #[inline]
+    #[must_use]
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Returns what the query is matched against.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> SearchKind {
+        self.kind
+    }
+
+    /// Returns the options of the search.
+    #[inline]
+    #[must_use]
+    pub const fn options(&self) -> &SearchOptions {
+        &self.options
+    }
+
+    /// Returns the lines matched by a content search so far, in the order they were found.
+    #[inline]
+    #[must_use]
+    pub fn content_matches(&self) -> &[ContentMatch] {
+        &self.content_matches
+    }
+
+    /// Returns whether the search is over, either because every directory has been walked
+    /// or because it was cancelled.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.state.finished.load(Ordering::Acquire)
+    }
+
+    /// Returns whether the search was cancelled before every directory was walked.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.state.is_cancelled()
+    }
+
+    /// Stops walking the directories. The matches found so far are kept.
+    pub(crate) fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Takes the entries found since the last call.
+    pub(crate) fn take_found(&self) -> Vec<Found> {
+        std::mem::take(&mut *self.state.found.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Records a line matched by a content search.
+    pub(crate) fn push_content_match(&mut self, file: File, line_number: usize, line: String) {
+        self.content_matches.push(ContentMatch {
+            file,
+            line_number,
+            line,
+        });
+    }
+}
+
+/// Walk the directories below `root` breadth first, stopping once cancelled.
+async fn walk<F: FileSystem>(
+    filesystem: Arc<F>,
+    root: PathBuf,
+    regex: Regex,
+    kind: SearchKind,
+    options: SearchOptions,
+    state: Arc<State>,
+) {
+    let mut directories = VecDeque::from([(root.clone(), 0)]);
+
+    while let Some((dir, depth)) = directories.pop_front() {
+        if state.is_cancelled() {
+            break;
+        }
+
+        // Directories which cannot be read are skipped, like `find` does
+        let Ok(mut entries) = filesystem.read_dir(&dir.to_string_lossy()).await else {
+            continue;
+        };
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for mut entry in entries {
+            if entry.is_hidden && !options.show_hidden {
+                continue;
+            }
+
+            let path = PathBuf::from(&entry.path);
+            let can_descend = options.max_depth.is_none_or(|max| depth + 1 < max);
+            if entry.is_dir && !entry.is_symlink && can_descend {
+                directories.push_back((path.clone(), depth + 1));
+            }
+
+            let found: Vec<Found> = match kind {
+                SearchKind::Name if regex.is_match(entry.name.trim_end_matches('/')) => {
+                    entry.name = relative_name(&root, &path, entry.is_dir);
+                    vec![Found { entry, line: None }]
+                }
+                SearchKind::Content if entry.is_file => {
+                    let lines =
+                        grep(filesystem.as_ref(), &entry.path, &regex, &options, &state).await;
+
+                    entry.name = relative_name(&root, &path, false);
+                    lines
+                        .into_iter()
+                        .map(|line| Found {
+                            entry: entry.clone(),
+                            line: Some(line),
+                        })
+                        .collect()
+                }
+                _ => continue,
+            };
+
+            if !found.is_empty() {
+                state
+                    .found
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .extend(found);
+            }
+        }
+    }
+
+    state.finished.store(true, Ordering::Release);
+}
+
+/// Returns the numbered lines of the file at `path` matching `regex`, reading at most
+/// [`SearchOptions::max_file_size`] bytes. Binary files, recognized by a NUL byte in their
+/// first chunk like git does, and files which cannot be read give no line.
+async fn grep<F: FileSystem>(
+    filesystem: &F,
+    path: &str,
+    regex: &Regex,
+    options: &SearchOptions,
+    state: &State,
+) -> Vec<(usize, String)> {
+    let mut matches = Vec::new();
+    let mut line_number = 0;
+    let mut check = |line: &[u8]| {
+        line_number += 1;
+
+        let line = String::from_utf8_lossy(line);
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+        if regex.is_match(line) {
+            matches.push((line_number, line.to_string()));
+        }
+    };
+
+    let mut pending: Vec<u8> = Vec::new();
+    let mut offset = 0;
+    while offset < options.max_file_size && !state.is_cancelled() {
+        let len =
+            CHUNK_SIZE.min(usize::try_from(options.max_file_size - offset).unwrap_or(CHUNK_SIZE));
+        let Ok(chunk) = filesystem.read_chunk(path, offset, len).await else {
+            return Vec::new();
+        };
+        if offset == 0 && chunk.contains(&0) {
+            return Vec::new();
+        }
+
+        offset += chunk.len() as u64;
+        let end_of_file = chunk.len() < len;
+        pending.extend(chunk);
+
+        let mut start = 0;
+        while let Some(idx) = pending[start..].iter().position(|byte| *byte == b'\n') {
+            check(&pending[start..start + idx]);
+            start += idx + 1;
+        }
+        pending.drain(..start);
+
+        if end_of_file {
+            break;
+        }
+    }
+
+    // The last line has no line ending, or was cut by the size limit
+    if !pending.is_empty() {
+        check(&pending);
+    }
+
+    matches
+}
+
+/// The name of a match in the results: its path relative to the searched directory.
+fn relative_name(root: &Path, path: &Path, is_dir: bool) -> String {
+    let name = path.strip_prefix(root).unwrap_or(path).to_string_lossy();
+
+    if is_dir {
+        format!("{}/", name)
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{filesystem::InMemoryFileSystem, FileExplorer, Input};
+
+    use super::*;
+
+    async fn explorer() -> FileExplorer<InMemoryFileSystem> {
+        let fs = InMemoryFileSystem::new()
+            .with_file("/project/README.md", "")
+            .with_file("/project/src/main.rs", "")
+            .with_file("/project/src/bin/readme.rs", "")
+            .with_file("/project/.git/README", "");
+
+        FileExplorer::with_fs(Arc::new(fs), "/project".to_string())
+            .await
+            .unwrap()
+    }
+
+    async fn finish(file_explorer: &mut FileExplorer<InMemoryFileSystem>) {
+        while !file_explorer.search().unwrap().is_finished() {
+            tokio::task::yield_now().await;
+        }
+        file_explorer.poll_search();
+    }
+
+    fn names(file_explorer: &FileExplorer<InMemoryFileSystem>) -> Vec<&str> {
+        file_explorer
+            .files()
+            .iter()
+            .map(|file| file.name())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn dropping_the_explorer_stops_the_walk() {
+        let mut file_explorer = explorer().await;
+        file_explorer
+            .start_search("readme", SearchOptions::new())
+            .unwrap();
+        let state = Arc::clone(&file_explorer.search().unwrap().state);
+
+        // The walk has not started yet on this single threaded runtime
+        drop(file_explorer);
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        assert!(!state.finished.load(Ordering::Acquire));
+        assert_eq!(Arc::strong_count(&state), 1);
+    }
+
+    #[tokio::test]
+    async fn search() {
+        let mut file_explorer = explorer().await;
+
+        file_explorer
+            .start_search("readme", SearchOptions::new())
+            .unwrap();
+        finish(&mut file_explorer).await;
+        assert_eq!(
+            names(&file_explorer),
+            ["./", "README.md", "src/bin/readme.rs"]
+        );
+
+        file_explorer
+            .start_search("readme", SearchOptions::new().with_max_depth(2))
+            .unwrap();
+        finish(&mut file_explorer).await;
+        assert_eq!(names(&file_explorer), ["./", "README.md"]);
+
+        file_explorer
+            .start_search("readme", SearchOptions::new().with_show_hidden(true))
+            .unwrap();
+        finish(&mut file_explorer).await;
+        assert_eq!(
+            names(&file_explorer),
+            ["./", "README.md", ".git/README", "src/bin/readme.rs"]
+        );
+    }
+
+    #[tokio::test]
+    async fn navigation() {
+        let mut file_explorer = explorer().await;
+
+        file_explorer
+            .start_search("rs", SearchOptions::new())
+            .unwrap();
+        finish(&mut file_explorer).await;
+        assert_eq!(
+            names(&file_explorer),
+            ["./", "src/main.rs", "src/bin/readme.rs"]
+        );
+
+        file_explorer.handle(Input::End).await.unwrap();
+        file_explorer.handle(Input::Right).await.unwrap();
+        assert!(file_explorer.search().is_none());
+        assert_eq!(file_explorer.cwd(), Path::new("/project/src/bin"));
+        assert_eq!(file_explorer.current().name(), "readme.rs");
+
+        // Leaving the results goes back to the listing of the searched directory
+        file_explorer
+            .start_search("rs", SearchOptions::new())
+            .unwrap();
+        file_explorer.handle(Input::Left).await.unwrap();
+        assert!(file_explorer.search().is_none());
+        assert_eq!(file_explorer.cwd(), Path::new("/project/src/bin"));
+        assert_eq!(names(&file_explorer), ["../", "readme.rs"]);
+    }
+
+    #[tokio::test]
+    async fn cancellation() {
+        let mut file_explorer = explorer().await;
+
+        // The walk does not start before the current task yields
+        file_explorer
+            .start_search("rs", SearchOptions::new())
+            .unwrap();
+        file_explorer.handle(Input::Cancel).await.unwrap();
+        finish(&mut file_explorer).await;
+
+        let search = file_explorer.search().unwrap();
+        assert!(search.is_cancelled());
+        assert_eq!(names(&file_explorer), ["./"]);
+    }
+
+    #[tokio::test]
+    async fn content_search() {
+        let big = format!("{}needle\n", "x\n".repeat(CHUNK_SIZE));
+        let fs = InMemoryFileSystem::new()
+            .with_file("/logs/app.log", "starting\r\nNeedle found\nstopping")
+            .with_file("/logs/big.log", big.as_str())
+            .with_file("/logs/image.png", "\0needle")
+            .with_file("/logs/last.log", "no line ending: needle");
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/logs".to_string())
+            .await
+            .unwrap();
+
+        let lines = |file_explorer: &FileExplorer<InMemoryFileSystem>| {
+            let search = file_explorer.search().unwrap();
+            search
+                .content_matches()
+                .iter()
+                .map(|found| {
+                    (
+                        found.file().name().to_string(),
+                        found.line_number(),
+                        found.line().to_string(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        file_explorer
+            .start_content_search("needle", SearchOptions::new())
+            .unwrap();
+        finish(&mut file_explorer).await;
+        assert_eq!(
+            lines(&file_explorer),
+            [
+                ("app.log".to_string(), 2, "Needle found".to_string()),
+                ("big.log".to_string(), CHUNK_SIZE + 1, "needle".to_string()),
+                (
+                    "last.log".to_string(),
+                    1,
+                    "no line ending: needle".to_string()
+                ),
+            ]
+        );
+        assert_eq!(file_explorer.files()[1].name(), "app.log:2: Needle found");
+
+        // Only the beginning of large files is searched
+        let options = SearchOptions::new()
+            .with_case_sensitive(true)
+            .with_max_file_size(1024);
+        file_explorer
+            .start_content_search("needle", options)
+            .unwrap();
+        finish(&mut file_explorer).await;
+        assert_eq!(
+            lines(&file_explorer),
+            [(
+                "last.log".to_string(),
+                1,
+                "no line ending: needle".to_string()
+            )]
+        );
+
+        let options = SearchOptions::new().with_regex(true);
+        let err = file_explorer
+            .start_content_search("(", options)
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(file_explorer.search().unwrap().query(), "needle");
+    }
+}
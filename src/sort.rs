@@ -0,0 +1,294 @@
+//! Sorting of the entries of a [`FileExplorer`](crate::FileExplorer): by name, size,
+//! modification time or extension, in either order, with directories first or mixed in and
+//! names compared naturally or case-insensitively.
+
+use std::cmp::Ordering;
+
+use crate::File;
+
+/// The key used to sort the entries of a [`FileExplorer`](crate::FileExplorer).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SortBy {
+    /// Sort by name.
+    #[default]
+    Name,
+    /// Sort by size, directories being considered empty.
+    Size,
+    /// Sort by last modification time, entries without one coming first.
+    Modified,
+    /// Sort by extension, then by name. Entries without extension come first.
+    Extension,
+}
+
+impl SortBy {
+    /// Returns the sort key following this one, wrapping around after the last one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratatui_async_explorer::SortBy;
+    ///
+    /// assert_eq!(SortBy::Name.next(), SortBy::Size);
+    /// assert_eq!(SortBy::Extension.next(), SortBy::Name);
+    /// ```
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Name => Self::Size,
+            Self::Size => Self::Modified,
+            Self::Modified => Self::Extension,
+            Self::Extension => Self::Name,
+        }
+    }
+}
+
+/// The direction in which the entries of a [`FileExplorer`](crate::FileExplorer) are sorted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SortOrder {
+    /// Smallest first.
+    #[default]
+    Ascending,
+    /// Largest first.
+    Descending,
+}
+
+impl SortOrder {
+    /// Returns the opposite order.
+    #[must_use]
+    pub const fn reversed(self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
+}
+
+/// The sort configuration of a [`FileExplorer`](crate::FileExplorer).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SortSettings {
+    pub sort_by: SortBy,
+    pub order: SortOrder,
+    /// Keep directories before files, whatever the order.
+    pub dirs_first: bool,
+    /// Compare digit sequences by their numeric value.
+    pub natural: bool,
+    /// Compare names regardless of their case.
+    pub case_insensitive: bool,
+}
+
+impl Default for SortSettings {
+    fn default() -> Self {
+        Self {
+            sort_by: SortBy::default(),
+            order: SortOrder::default(),
+            dirs_first: true,
+            natural: false,
+            case_insensitive: false,
+        }
+    }
+}
+
+impl SortSettings {
+    /// Sort `files` according to these settings.
+    pub fn sort(&self, files: &mut [File]) {
+        files.sort_by(|a, b| {
+            let group = if self.dirs_first {
+                b.is_dir().cmp(&a.is_dir())
+            } else {
+                Ordering::Equal
+            };
+
+            let ordering = match self.sort_by {
+                SortBy::Name => Ordering::Equal,
+                SortBy::Size => a.size().unwrap_or(0).cmp(&b.size().unwrap_or(0)),
+                SortBy::Modified => a.modified().cmp(&b.modified()),
+                SortBy::Extension => self.collate(extension(a), extension(b)),
+            }
+            .then_with(|| self.collate(a.name(), b.name()));
+
+            group.then(match self.order {
+                SortOrder::Ascending => ordering,
+                SortOrder::Descending => ordering.reverse(),
+            })
+        });
+    }
+
+    /// Compare two names according to the collation options.
+    ///
+    /// Names that only differ by what the options ignore (case, leading zeros) are
+    /// still ordered by their bytes, so that the order is always deterministic.
+    fn collate(&self, a: &str, b: &str) -> Ordering {
+        if !self.natural && !self.case_insensitive {
+            return a.cmp(b);
+        }
+
+        let mut a_chars = a.chars().peekable();
+        let mut b_chars = b.chars().peekable();
+
+        while let (Some(&x), Some(&y)) = (a_chars.peek(), b_chars.peek()) {
+            let ordering = if self.natural && x.is_ascii_digit() && y.is_ascii_digit() {
+                compare_numbers(&digits(&mut a_chars), &digits(&mut b_chars))
+            } else {
+                a_chars.next();
+                b_chars.next();
+
+                if self.case_insensitive {
+                    x.to_lowercase().cmp(y.to_lowercase())
+                } else {
+                    x.cmp(&y)
+                }
+            };
+
+            if ordering.is_ne() {
+                return ordering;
+            }
+        }
+
+        a_chars.count().cmp(&b_chars.count()).then_with(|| a.cmp(b))
+    }
+}
+
+/// Consume the sequence of ASCII digits at the front of `chars`.
+fn digits(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut digits = String::new();
+    while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+        digits.push(digit);
+    }
+    digits
+}
+
+/// Compare two sequences of ASCII digits by their value, whatever their length.
+fn compare_numbers(a: &str, b: &str) -> Ordering {
+    let a = a.trim_start_matches('0');
+    let b = b.trim_start_matches('0');
+
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+fn extension(file: &File) -> &str {
+    if file.is_dir() {
+        return "";
+    }
+
+    match file.name().rsplit_once('.') {
+        // A leading dot marks a hidden file, not an extension
+        Some((stem, extension)) if !stem.is_empty() => extension,
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{filesystem::InMemoryFileSystem, FileExplorer, Input};
+    use std::{
+        sync::Arc,
+        time::{Duration, SystemTime},
+    };
+
+    async fn explorer() -> FileExplorer<InMemoryFileSystem> {
+        let at = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+
+        let fs = InMemoryFileSystem::new()
+            .with_file("/b.txt", "b".repeat(30))
+            .with_dir("/src")
+            .with_file("/a.rs", "a".repeat(10))
+            .with_file("/.env", "e".repeat(20))
+            .with_dir("/docs")
+            .with_modified("/b.txt", at(1))
+            .with_modified("/src", at(4))
+            .with_modified("/a.rs", at(3))
+            .with_modified("/.env", at(2));
+
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string())
+            .await
+            .unwrap();
+        file_explorer.set_show_hidden(true).await.unwrap();
+        file_explorer
+    }
+
+    fn names<F: crate::FileSystem>(file_explorer: &FileExplorer<F>) -> Vec<&str> {
+        file_explorer.files().iter().map(File::name).collect()
+    }
+
+    #[tokio::test]
+    async fn sort_keys() {
+        let mut file_explorer = explorer().await;
+        assert_eq!(
+            names(&file_explorer),
+            ["docs/", "src/", ".env", "a.rs", "b.txt"]
+        );
+
+        file_explorer.set_sort_by(SortBy::Size);
+        assert_eq!(
+            names(&file_explorer),
+            ["docs/", "src/", "a.rs", ".env", "b.txt"]
+        );
+
+        file_explorer.set_sort_by(SortBy::Modified);
+        file_explorer.set_sort_order(SortOrder::Descending);
+        assert_eq!(
+            names(&file_explorer),
+            ["src/", "docs/", "a.rs", ".env", "b.txt"]
+        );
+
+        file_explorer.set_sort_by(SortBy::Extension);
+        file_explorer.set_sort_order(SortOrder::Ascending);
+        file_explorer.set_dirs_first(false);
+        assert_eq!(
+            names(&file_explorer),
+            [".env", "docs/", "src/", "a.rs", "b.txt"]
+        );
+
+        file_explorer.set_sort_by(SortBy::Name);
+        file_explorer.set_sort_order(SortOrder::Descending);
+        assert_eq!(
+            names(&file_explorer),
+            ["src/", "docs/", "b.txt", "a.rs", ".env"]
+        );
+    }
+
+    #[test]
+    fn collation() {
+        let collate = |natural, case_insensitive, a, b| {
+            SortSettings {
+                natural,
+                case_insensitive,
+                ..SortSettings::default()
+            }
+            .collate(a, b)
+        };
+
+        assert_eq!(collate(false, false, "file10", "file2"), Ordering::Less);
+        assert_eq!(collate(true, false, "file10", "file2"), Ordering::Greater);
+        assert_eq!(collate(true, false, "v1.10.0", "v1.9.3"), Ordering::Greater);
+        assert_eq!(collate(true, false, "file010", "file10"), Ordering::Less);
+        assert_eq!(collate(true, false, "file", "file1"), Ordering::Less);
+
+        assert_eq!(collate(false, false, "Zeta", "alpha"), Ordering::Less);
+        assert_eq!(collate(false, true, "Zeta", "alpha"), Ordering::Greater);
+        assert_eq!(collate(false, true, "README", "readme"), Ordering::Less);
+        assert_eq!(collate(true, true, "Build10", "build9"), Ordering::Greater);
+    }
+
+    #[tokio::test]
+    async fn inputs_keep_the_selection() {
+        let mut file_explorer = explorer().await;
+        file_explorer.select_file("src/");
+        file_explorer.handle(Input::Right).await.unwrap();
+        file_explorer.handle(Input::Left).await.unwrap();
+        assert_eq!(file_explorer.current().name(), "src/");
+
+        file_explorer.handle(Input::CycleSort).await.unwrap();
+        assert_eq!(file_explorer.sort_by(), SortBy::Size);
+        assert_eq!(file_explorer.current().name(), "src/");
+
+        file_explorer.handle(Input::ToggleSortOrder).await.unwrap();
+        assert_eq!(file_explorer.sort_order(), SortOrder::Descending);
+        assert_eq!(file_explorer.selected_idx(), 0);
+
+        file_explorer.handle(Input::ToggleDirsFirst).await.unwrap();
+        assert!(!file_explorer.dirs_first());
+        assert_eq!(file_explorer.current().name(), "src/");
+    }
+}
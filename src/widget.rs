@@ -2,15 +2,16 @@ use std::sync::Arc;
 
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Rect},
+    layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{
-        Block, Borders, Cell, HighlightSpacing, Row, StatefulWidget, Table, TableState, WidgetRef,
+        Block, Borders, Cell, HighlightSpacing, Row, StatefulWidget, Table, TableState, Widget,
+        WidgetRef,
     },
 };
 
-use crate::{filesystem::FileSystem, icon::IconDisplay, File, FileExplorer};
+use crate::{filesystem::FileSystem, icon::IconDisplay, File, FileExplorer, GitStatus};
 
 type LineFactory<F> = Arc<dyn Fn(&FileExplorer<F>) -> Line<'static> + Send + Sync>;
 
@@ -32,11 +33,11 @@ impl<F: FileSystem> StatefulRenderer<'_, F> {
             .with_offset(self.0.scroll_offset());
 
         // Check if current item is selected
-        let current = self.0.current();
-        let current_is_selected = self.0.is_file_selected(current);
+        let current = self.0.current_entry();
+        let current_is_selected = current.is_some_and(|current| self.0.is_file_selected(current));
 
         // Choose highlight style based on whether current item is a directory
-        let base_highlight_style = if current.is_dir() {
+        let base_highlight_style = if current.is_some_and(File::is_dir) {
             self.0.theme().highlight_dir_style
         } else {
             self.0.theme().highlight_item_style
@@ -85,19 +86,7 @@ impl<F: FileSystem> StatefulRenderer<'_, F> {
             table = table.highlight_symbol(symbol);
         }
 
-        if let Some(block) = self.0.theme().block.as_ref() {
-            let mut block = block.clone();
-
-            for title_top in self.0.theme().title_top(self.0) {
-                block = block.title_top(title_top);
-            }
-            for title_bottom in self.0.theme().title_bottom(self.0) {
-                block = block.title_bottom(title_bottom);
-            }
-
-            table = table.block(block);
-        }
-
+        let (table, area) = frame(self.0, table, area, buf);
         StatefulWidget::render(table, area, buf, &mut state);
 
         // Update scroll offset after rendering
@@ -119,11 +108,11 @@ impl<F: FileSystem> WidgetRef for Renderer<'_, F> {
             .with_offset(self.0.scroll_offset());
 
         // Check if current item is selected
-        let current = self.0.current();
-        let current_is_selected = self.0.is_file_selected(current);
+        let current = self.0.current_entry();
+        let current_is_selected = current.is_some_and(|current| self.0.is_file_selected(current));
 
         // Choose highlight style based on whether current item is a directory
-        let base_highlight_style = if current.is_dir() {
+        let base_highlight_style = if current.is_some_and(File::is_dir) {
             self.0.theme().highlight_dir_style
         } else {
             self.0.theme().highlight_item_style
@@ -172,21 +161,48 @@ impl<F: FileSystem> WidgetRef for Renderer<'_, F> {
             table = table.highlight_symbol(symbol);
         }
 
-        if let Some(block) = self.0.theme().block.as_ref() {
-            let mut block = block.clone();
-
-            for title_top in self.0.theme().title_top(self.0) {
-                block = block.title_top(title_top);
-            }
-            for title_bottom in self.0.theme().title_bottom(self.0) {
-                block = block.title_bottom(title_bottom);
-            }
+        let (table, area) = frame(self.0, table, area, buf);
+        StatefulWidget::render(table, area, buf, &mut state);
+    }
+}
 
-            table = table.block(block);
-        }
+/// Wraps `table` in the block of the theme, with its titles, and shows the delete
+/// confirmation prompt: as a bottom title of the block, or on the last line of `area` when
+/// the theme has no block. Returns the table and the area left to render it in.
+fn frame<'a, F: FileSystem>(
+    file_explorer: &'a FileExplorer<F>,
+    mut table: Table<'a>,
+    area: Rect,
+    buf: &mut Buffer,
+) -> (Table<'a>, Rect) {
+    let theme = file_explorer.theme();
+    let prompt = file_explorer.pending_delete().map(|file| {
+        Line::from(format!("Delete {}? (y/n)", file.name())).style(theme.delete_prompt_style)
+    });
+
+    let Some(block) = theme.block.as_ref() else {
+        let Some(prompt) = prompt else {
+            return (table, area);
+        };
+        let [area, prompt_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        prompt.render(prompt_area, buf);
+        return (table, area);
+    };
 
-        StatefulWidget::render(table, area, buf, &mut state);
+    let mut block = block.clone();
+    for title_top in theme.title_top(file_explorer) {
+        block = block.title_top(title_top);
     }
+    for title_bottom in theme.title_bottom(file_explorer) {
+        block = block.title_bottom(title_bottom);
+    }
+    if let Some(prompt) = prompt {
+        block = block.title_bottom(prompt);
+    }
+
+    table = table.block(block);
+    (table, area)
 }
 
 impl File {
@@ -203,6 +219,9 @@ impl File {
         } else {
             style
         };
+        let name_style = self.git_status().map_or(final_style, |status| {
+            final_style.patch(*theme.git_status_style(status))
+        });
 
         let permissions_str = self
             .permissions()
@@ -215,37 +234,39 @@ impl File {
             None
         };
 
-        let base_name = if let Some(target) = &self.symlink_target() {
-            format!("{} -> {}", self.name(), target)
-        } else {
-            self.name().to_string()
-        };
+        let mut name_spans = highlight_matches(
+            self.name(),
+            self.match_positions(),
+            name_style,
+            name_style.patch(*theme.match_style()),
+        );
+        if let Some(target) = &self.symlink_target() {
+            name_spans.push(Span::styled(format!(" -> {}", target), final_style));
+        }
+        if let Some(status) = self.git_status() {
+            name_spans.push(Span::styled(format!(" {}", status.symbol()), name_style));
+        }
 
         let name_cell = if let Some(icon) = icon {
             let icon_style = icon.color.map_or(final_style, |fg| Style::default().fg(fg));
 
-            let spans = if is_selected {
-                vec![
-                    Span::styled(icon.text, icon_style),
-                    Span::raw(" "),
-                    Span::styled(format!("{} ", theme.selected_marker()), final_style),
-                    Span::styled(base_name, final_style),
-                ]
-            } else {
-                vec![
-                    Span::styled(icon.text, icon_style),
-                    Span::raw(" "),
-                    Span::styled(base_name, final_style),
-                ]
-            };
+            let mut spans = vec![Span::styled(icon.text, icon_style), Span::raw(" ")];
+            if is_selected {
+                spans.push(Span::styled(
+                    format!("{} ", theme.selected_marker()),
+                    final_style,
+                ));
+            }
+            spans.extend(name_spans);
             Cell::from(Line::from(spans))
         } else {
-            let name = if is_selected {
-                format!("{} {}", theme.selected_marker(), base_name)
-            } else {
-                base_name
-            };
-            Cell::from(name).style(final_style)
+            if is_selected {
+                name_spans.insert(
+                    0,
+                    Span::styled(format!("{} ", theme.selected_marker()), final_style),
+                );
+            }
+            Cell::from(Line::from(name_spans)).style(final_style)
         };
 
         let size_str = self.size().map(format_size).unwrap_or_default();
@@ -260,6 +281,38 @@ impl File {
     }
 }
 
+/// Split `name` into spans, styling the characters at `positions` with `match_style`.
+fn highlight_matches(
+    name: &str,
+    positions: &[usize],
+    style: Style,
+    match_style: Style,
+) -> Vec<Span<'static>> {
+    if positions.is_empty() {
+        return vec![Span::styled(name.to_string(), style)];
+    }
+
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+
+    for (idx, c) in name.chars().enumerate() {
+        let matched = positions.binary_search(&idx).is_ok();
+        if matched != run_matched && !run.is_empty() {
+            let style = if run_matched { match_style } else { style };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_matched = matched;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        let style = if run_matched { match_style } else { style };
+        spans.push(Span::styled(run, style));
+    }
+
+    spans
+}
+
 /// Format file size in human-readable format
 fn format_size(bytes: u64) -> String {
     const KB: u64 = 1024;
@@ -311,6 +364,9 @@ pub struct Theme<F: FileSystem = crate::filesystem::LocalFileSystem> {
     scroll_padding: usize,
     selected_marker: String,
     header_style: Style,
+    delete_prompt_style: Style,
+    match_style: Style,
+    git_status_styles: [Style; 5],
     icon_display: IconDisplay,
 }
 
@@ -340,6 +396,9 @@ impl<F: FileSystem> Theme<F> {
             scroll_padding: 0,
             selected_marker: "[✓]".to_string(),
             header_style: Style::new(),
+            delete_prompt_style: Style::new(),
+            match_style: Style::new(),
+            git_status_styles: [Style::new(); 5],
             icon_display: IconDisplay::None,
         }
     }
@@ -612,6 +671,55 @@ impl<F: FileSystem> Theme<F> {
         self
     }
 
+    /// Sets the style of the confirmation prompt shown while a deletion is pending, at the
+    /// bottom of the wrapping block if it exists, or on the last line of the widget otherwise.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ratatui::prelude::*;
+    /// # use ratatui_async_explorer::Theme;
+    /// let theme: Theme = Theme::default().with_delete_prompt_style(Style::default().fg(Color::Yellow));
+    /// ```
+    #[inline]
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn with_delete_prompt_style<S: Into<Style>>(mut self, delete_prompt_style: S) -> Self {
+        self.delete_prompt_style = delete_prompt_style.into();
+        self
+    }
+
+    /// Sets the style of the characters matched by the
+    /// [fuzzy filter](crate::FileExplorer::set_fuzzy_filter), on top of the style of the entry.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ratatui::prelude::*;
+    /// # use ratatui_async_explorer::Theme;
+    /// let theme: Theme = Theme::default().with_match_style(Style::default().fg(Color::Green));
+    /// ```
+    #[inline]
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn with_match_style<S: Into<Style>>(mut self, match_style: S) -> Self {
+        self.match_style = match_style.into();
+        self
+    }
+
+    /// Set the style of the names of the entries having the given
+    /// [git status](crate::File::git_status).
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ratatui::prelude::*;
+    /// # use ratatui_async_explorer::{GitStatus, Theme};
+    /// let theme: Theme = Theme::default()
+    ///     .with_git_status_style(GitStatus::Untracked, Style::default().fg(Color::Cyan));
+    /// ```
+    #[inline]
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn with_git_status_style<S: Into<Style>>(mut self, status: GitStatus, style: S) -> Self {
+        self.git_status_styles[status as usize] = style.into();
+        self
+    }
+
     /// Add a top title factory to the theme.
     ///
     /// `title_top` is a function that take a reference to the current [`FileExplorer`] and returns
@@ -751,6 +859,27 @@ impl<F: FileSystem> Theme<F> {
         &self.header_style
     }
 
+    /// Returns the style of the deletion confirmation prompt.
+    #[inline]
+    #[must_use]
+    pub const fn delete_prompt_style(&self) -> &Style {
+        &self.delete_prompt_style
+    }
+
+    /// Returns the style of the characters matched by the fuzzy filter.
+    #[inline]
+    #[must_use]
+    pub const fn match_style(&self) -> &Style {
+        &self.match_style
+    }
+
+    /// Returns the style of the names of the entries having the given git status.
+    #[inline]
+    #[must_use]
+    pub const fn git_status_style(&self, status: GitStatus) -> &Style {
+        &self.git_status_styles[status as usize]
+    }
+
     /// Returns the generated top titles of the theme.
     #[inline]
     #[must_use]
@@ -800,7 +929,70 @@ impl<F: FileSystem> Default for Theme<F> {
             header_style: Style::default()
                 .fg(Color::White)
                 .add_modifier(Modifier::BOLD),
+            delete_prompt_style: Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            match_style: Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            git_status_styles: [
+                Style::default().fg(Color::DarkGray),
+                Style::default().fg(Color::LightRed),
+                Style::default().fg(Color::Green),
+                Style::default().fg(Color::Yellow),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ],
             icon_display: IconDisplay::None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{filesystem::InMemoryFileSystem, Input};
+
+    use super::*;
+
+    fn lines(buf: &Buffer) -> Vec<String> {
+        (0..buf.area.height)
+            .map(|y| {
+                (0..buf.area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn delete_prompt() {
+        let fs = InMemoryFileSystem::new().with_file("/notes.txt", "");
+        let mut file_explorer = FileExplorer::with_fs(Arc::new(fs), "/".to_string())
+            .await
+            .unwrap();
+        file_explorer.select_file("notes.txt");
+        file_explorer.handle(Input::Delete).await.unwrap();
+
+        // Without a block, on the last line of the area
+        file_explorer.set_theme(Theme::new());
+        let area = Rect::new(0, 0, 40, 5);
+        let mut buf = Buffer::empty(area);
+        file_explorer.widget().render_ref(area, &mut buf);
+        assert!(lines(&buf)[4].starts_with("Delete notes.txt? (y/n)"));
+
+        let mut buf = Buffer::empty(area);
+        file_explorer.widget_stateful().render(area, &mut buf);
+        assert!(lines(&buf)[4].starts_with("Delete notes.txt? (y/n)"));
+
+        // With a block, in its bottom border
+        file_explorer.set_theme(Theme::new().with_block(Block::default().borders(Borders::ALL)));
+        let mut buf = Buffer::empty(area);
+        file_explorer.widget().render_ref(area, &mut buf);
+        assert!(lines(&buf)[4].starts_with("└Delete notes.txt? (y/n)"));
+
+        // No prompt once the deletion is abandoned
+        file_explorer.handle(Input::Down).await.unwrap();
+        let mut buf = Buffer::empty(area);
+        file_explorer.widget().render_ref(area, &mut buf);
+        assert!(!lines(&buf)[4].contains("Delete"));
+    }
+}